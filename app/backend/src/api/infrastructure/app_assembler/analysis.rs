@@ -1,23 +1,30 @@
 use crate::api::infrastructure::event::tokio_bus::TokioBus;
+use crate::api::infrastructure::executor::tokio_blocking::TokioBlocking;
 use crate::api::infrastructure::repository::mongo::analysis::Mongo as AnalysisRepository;
 use crate::api::infrastructure::repository::mongo::lap::Mongo as LapRepository;
 use crate::api::infrastructure::settings::Settings;
 
 use shared::analysis::application::analyze::service::Analyzer;
+use shared::analysis::application::cancel::service::Canceller as AnalysisCanceller;
 use shared::analysis::application::create::service::Creator as AnalysisCreator;
 use shared::analysis::application::delete::service::Deleter as AnalysisDeleter;
 use shared::analysis::application::find::by_criteria::service::Finder as AnalysisByCriteriaFinder;
 use shared::analysis::application::find::by_id::service::Finder as AnalysisByIdFinder;
 use shared::analysis::application::find::header_by_criteria::service::Finder as AnalysisHeaderByCriteriaFinder;
 use shared::analysis::application::find::header_by_id::service::Finder as AnalysisHeaderByIdFinder;
+use shared::analysis::application::reanalyze::service::Reanalyzer as AnalysisReanalyzer;
 use shared::analysis::application::update::service::Updater as AnalysisUpdater;
 
 use shared::analysis::domain::analysis::fcm_grid::Config;
+use shared::analysis::domain::analysis::joint_clusters::JointConfig;
 use std::sync::Arc;
 
 pub struct Assembler {
-    pub analyzer: Arc<Analyzer<AnalysisRepository, LapRepository>>,
+    pub repository: Arc<AnalysisRepository>,
+    pub analyzer: Arc<Analyzer<AnalysisRepository, LapRepository, TokioBlocking>>,
     pub creator: Arc<AnalysisCreator<AnalysisRepository, LapRepository, TokioBus>>,
+    pub reanalyzer: Arc<AnalysisReanalyzer<AnalysisRepository, TokioBus>>,
+    pub canceller: Arc<AnalysisCanceller<AnalysisRepository>>,
     pub updater: Arc<AnalysisUpdater<AnalysisRepository>>,
     pub deleter: Arc<AnalysisDeleter<AnalysisRepository>>,
     pub by_id_finder: Arc<AnalysisByIdFinder<AnalysisRepository>>,
@@ -37,6 +44,8 @@ impl Assembler {
     ///   from their respective constructors:
     ///     * `Analyzer::new`
     ///     * `AnalysisCreator::new`
+    ///     * `AnalysisReanalyzer::new`
+    ///     * `AnalysisCanceller::new`
     ///     * `AnalysisUpdater::new`
     ///     * `AnalysisDeleter::new`
     ///     * `AnalysisByIdFinder::new`
@@ -57,12 +66,18 @@ impl Assembler {
         let analyzer = Arc::new(Analyzer::new(
             Arc::clone(&repository),
             Arc::clone(&lap_repository),
+            Arc::new(TokioBlocking),
         ));
         let creator = Arc::new(AnalysisCreator::new(
             Arc::clone(&repository),
             Arc::clone(&lap_repository),
             Arc::clone(event_bus),
         ));
+        let reanalyzer = Arc::new(AnalysisReanalyzer::new(
+            Arc::clone(&repository),
+            Arc::clone(event_bus),
+        ));
+        let canceller = Arc::new(AnalysisCanceller::new(Arc::clone(&repository)));
         let updater = Arc::new(AnalysisUpdater::new(Arc::clone(&repository)));
         let deleter = Arc::new(AnalysisDeleter::new(Arc::clone(&repository)));
         let by_id_finder = Arc::new(AnalysisByIdFinder::new(Arc::clone(&repository)));
@@ -93,10 +108,36 @@ impl Assembler {
                 settings.fcm_grid.error.inc,
             ),
         );
+        let fcm_grid_config = match &settings.fcm_grid.joint {
+            Some(joint) => fcm_grid_config.with_joint(JointConfig {
+                channels: joint.channels.clone(),
+                window: joint.window,
+            }),
+            None => fcm_grid_config,
+        };
+        let fcm_grid_config = match &settings.fcm_grid.algorithm {
+            Some(algorithm) => fcm_grid_config.with_algorithm(algorithm.clone()),
+            None => fcm_grid_config,
+        };
+        let fcm_grid_config = match settings.fcm_grid.criterion {
+            Some(criterion) => fcm_grid_config.with_criterion(criterion),
+            None => fcm_grid_config,
+        };
+        let fcm_grid_config = match settings.fcm_grid.seed {
+            Some(seed) => fcm_grid_config.with_seed(seed),
+            None => fcm_grid_config,
+        };
+        let fcm_grid_config = match &settings.fcm_grid.tag_policies {
+            Some(tag_policies) => fcm_grid_config.with_tag_policies(tag_policies.clone()),
+            None => fcm_grid_config,
+        };
 
         Ok(Self {
+            repository,
             analyzer,
             creator,
+            reanalyzer,
+            canceller,
             updater,
             deleter,
             by_id_finder,
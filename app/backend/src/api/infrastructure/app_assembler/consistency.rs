@@ -0,0 +1,60 @@
+use crate::api::infrastructure::repository::mongo::analysis::Mongo as AnalysisRepository;
+use crate::api::infrastructure::repository::mongo::consistency::Mongo as ConsistencyRepository;
+use crate::api::infrastructure::repository::mongo::lap::Mongo as LapRepository;
+use crate::api::infrastructure::settings::Settings;
+
+use shared::consistency::application::create::service::Creator as ConsistencyCreator;
+use shared::consistency::application::delete::service::Deleter as ConsistencyDeleter;
+use shared::consistency::application::find::by_id::service::Finder as ConsistencyByIdFinder;
+use shared::consistency::application::focus::service::Focuser as ConsistencyFocuser;
+
+use std::sync::Arc;
+
+pub struct Assembler {
+    pub creator: Arc<ConsistencyCreator<ConsistencyRepository, LapRepository>>,
+    pub deleter: Arc<ConsistencyDeleter<ConsistencyRepository>>,
+    pub by_id_finder: Arc<ConsistencyByIdFinder<ConsistencyRepository>>,
+    pub focuser: Arc<ConsistencyFocuser<ConsistencyRepository, AnalysisRepository>>,
+}
+
+impl Assembler {
+    /// # Errors
+    ///
+    /// This function will return an error in the following situations:
+    ///
+    /// * If `ConsistencyRepository::new` fails, it will return an error string propagated from that function.
+    /// * If any of the following initializations fail, they will return an error string propagated
+    ///   from their respective constructors:
+    ///     * `ConsistencyCreator::new`
+    ///     * `ConsistencyDeleter::new`
+    ///     * `ConsistencyByIdFinder::new`
+    ///     * `ConsistencyFocuser::new`
+    ///
+    /// Each of these functions could fail due to various reasons such as configuration issues,
+    /// resource allocation failures, or other runtime errors specific to the initialization process
+    /// of each component.
+    pub async fn new(
+        settings: &Settings,
+        lap_repository: &Arc<LapRepository>,
+        analysis_repository: &Arc<AnalysisRepository>,
+    ) -> Result<Self, String> {
+        let repository = Arc::new(ConsistencyRepository::new(settings).await?);
+        let creator = Arc::new(ConsistencyCreator::new(
+            Arc::clone(&repository),
+            Arc::clone(lap_repository),
+        ));
+        let deleter = Arc::new(ConsistencyDeleter::new(Arc::clone(&repository)));
+        let by_id_finder = Arc::new(ConsistencyByIdFinder::new(Arc::clone(&repository)));
+        let focuser = Arc::new(ConsistencyFocuser::new(
+            Arc::clone(&repository),
+            Arc::clone(analysis_repository),
+        ));
+
+        Ok(Self {
+            creator,
+            deleter,
+            by_id_finder,
+            focuser,
+        })
+    }
+}
@@ -1,12 +1,17 @@
 use crate::api::infrastructure::event::tokio_bus::TokioBus;
 use crate::api::infrastructure::repository::mongo::lap::Mongo as LapRepository;
 
+use shared::lap::application::analyze_grip::service::GripAnalyzer as LapGripAnalyzer;
+use shared::lap::application::analyze_tyres::service::TyreAnalyzer as LapTyreAnalyzer;
 use shared::lap::application::create::service::Creator as LapCreator;
 use shared::lap::application::delete::service::Deleter as LapDeleter;
 use shared::lap::application::find::by_criteria::service::Finder as ByCriteriaLapFinder;
 use shared::lap::application::find::by_id::service::Finder as ByIdLapFinder;
 use shared::lap::application::find::header_by_id::service::Finder as ByIdLapHeaderFinder;
 use shared::lap::application::find::headers_by_criteria::service::Finder as ByCriteriaLapHeaderFinder;
+use shared::lap::application::plan_fuel::service::FuelPlanner as LapFuelPlanner;
+use shared::lap::application::summarize::service::Summarizer as LapSummarizer;
+use shared::lap::application::synthesize::service::Synthesizer as LapSynthesizer;
 
 use crate::api::infrastructure::settings::Settings;
 use std::sync::Arc;
@@ -19,6 +24,11 @@ pub struct Assembler {
     pub by_id_header_finder: Arc<ByIdLapHeaderFinder<LapRepository>>,
     pub by_criteria_header_finder: Arc<ByCriteriaLapHeaderFinder<LapRepository>>,
     pub deleter: Arc<LapDeleter<LapRepository>>,
+    pub synthesizer: Arc<LapSynthesizer<LapRepository>>,
+    pub summarizer: Arc<LapSummarizer<LapRepository>>,
+    pub fuel_planner: Arc<LapFuelPlanner<LapRepository>>,
+    pub grip_analyzer: Arc<LapGripAnalyzer<LapRepository>>,
+    pub tyre_analyzer: Arc<LapTyreAnalyzer<LapRepository>>,
 }
 
 impl Assembler {
@@ -35,6 +45,11 @@ impl Assembler {
     ///     * `ByIdLapHeaderFinder::new`
     ///     * `ByCriteriaLapHeaderFinder::new`
     ///     * `LapDeleter::new`
+    ///     * `LapSynthesizer::new`
+    ///     * `LapSummarizer::new`
+    ///     * `LapFuelPlanner::new`
+    ///     * `LapGripAnalyzer::new`
+    ///     * `LapTyreAnalyzer::new`
     ///
     /// Each of these functions could fail due to various reasons such as configuration issues,
     /// resource allocation failures, or other runtime errors specific to the initialization process
@@ -48,6 +63,11 @@ impl Assembler {
         let by_criteria_header_finder =
             Arc::new(ByCriteriaLapHeaderFinder::new(Arc::clone(&repository)));
         let deleter = Arc::new(LapDeleter::new(Arc::clone(&repository)));
+        let synthesizer = Arc::new(LapSynthesizer::new(Arc::clone(&repository)));
+        let summarizer = Arc::new(LapSummarizer::new(Arc::clone(&repository)));
+        let fuel_planner = Arc::new(LapFuelPlanner::new(Arc::clone(&repository)));
+        let grip_analyzer = Arc::new(LapGripAnalyzer::new(Arc::clone(&repository)));
+        let tyre_analyzer = Arc::new(LapTyreAnalyzer::new(Arc::clone(&repository)));
         Ok(Self {
             repository,
             creator,
@@ -56,6 +76,11 @@ impl Assembler {
             by_id_header_finder,
             by_criteria_header_finder,
             deleter,
+            synthesizer,
+            summarizer,
+            fuel_planner,
+            grip_analyzer,
+            tyre_analyzer,
         })
     }
 }
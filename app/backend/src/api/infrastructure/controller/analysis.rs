@@ -2,19 +2,25 @@ use crate::api::infrastructure::event::tokio_bus::TokioBus;
 use crate::api::infrastructure::repository::mongo::analysis::Mongo as AnalysisRepository;
 use crate::api::infrastructure::repository::mongo::lap::Mongo as LapRepository;
 
+use shared::analysis::application::cancel::service::Canceller;
 use shared::analysis::application::create::service::Creator;
 use shared::analysis::application::delete::service::Deleter;
 use shared::analysis::application::find::by_criteria::service::Finder as ByCriteriaFinder;
 use shared::analysis::application::find::by_id::service::Finder as ByIdFinder;
 use shared::analysis::application::find::header_by_criteria::service::Finder as ByCriteriaHeaderFinder;
 use shared::analysis::application::find::header_by_id::service::Finder as ByIdHeaderFinder;
+use shared::analysis::application::reanalyze::service::Reanalyzer;
 use shared::analysis::domain::analyses::Analyses;
+use shared::analysis::domain::analysis::catalogue::Language;
 use shared::analysis::domain::analysis::header::Header;
 use shared::analysis::domain::analysis::headers::Headers;
+use shared::analysis::domain::analysis::overrides::Overrides;
 use shared::analysis::domain::analysis::Analysis;
+use shared::analysis::domain::repository::Repository;
 use shared::common::domain::criteria::Criteria;
 
-use axum::extract::{Path, State};
+use axum::body::Bytes;
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::Json;
 use chrono::Utc;
@@ -30,6 +36,14 @@ pub struct Args {
     target_lap_id: Uuid,
 }
 
+/// Query parameters of the endpoints returning whole analyses.
+#[derive(Debug, Deserialize)]
+pub struct Localisation {
+    /// Language of the coaching messages, English by default.
+    #[serde(default)]
+    language: Language,
+}
+
 /// # Errors
 ///
 /// Will return `Err` if the service call produces any error
@@ -52,16 +66,67 @@ pub async fn creator(
     }
 }
 
+/// Runs an analysis again, optionally replacing some of the configured parameters with the
+/// ones in the body. An empty body keeps the configured parameters.
+///
+/// # Errors
+///
+/// Will return `Err` if the body is not valid overrides or if the service call produces any
+/// error
+pub async fn reanalyze<R: Repository>(
+    State(reanalyzer): State<Arc<Reanalyzer<R, TokioBus>>>,
+    Path(analysis_id): Path<Uuid>,
+    body: Bytes,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let overrides = if body.iter().all(u8::is_ascii_whitespace) {
+        None
+    } else {
+        let overrides: Overrides = serde_json::from_slice(&body).map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid overrides in the body: {e}"),
+            )
+        })?;
+        Some(overrides)
+    };
+
+    match reanalyzer.reanalyze(analysis_id, overrides).await {
+        Ok(()) => Ok(StatusCode::ACCEPTED),
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e)),
+    }
+}
+
+/// Cancels a queued or running analysis.
+///
+/// # Errors
+///
+/// Will return `Err` if the service call produces any error
+pub async fn cancel(
+    State(canceller): State<Arc<Canceller<AnalysisRepository>>>,
+    Path(analysis_id): Path<Uuid>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    match canceller.cancel(analysis_id).await {
+        Ok(()) => Ok(StatusCode::ACCEPTED),
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e)),
+    }
+}
+
 /// # Errors
 ///
 /// Will return `Err` if the service call produces any error
 pub async fn find_by_criteria(
     State(finder): State<Arc<ByCriteriaFinder<AnalysisRepository>>>,
+    Query(localisation): Query<Localisation>,
     Json(criteria): Json<Criteria>,
 ) -> Result<Json<Analyses>, (StatusCode, String)> {
     let analyses = finder.find(&criteria).await;
     match analyses {
-        Ok(Some(analyses)) => Ok(Json(analyses)),
+        Ok(Some(mut analyses)) => {
+            for analysis in analyses.iter_mut() {
+                analysis.localise(localisation.language);
+            }
+            Ok(Json(analyses))
+        }
         Ok(None) => {
             let msg = if criteria.is_empty() {
                 "No analyses found"
@@ -80,10 +145,14 @@ pub async fn find_by_criteria(
 pub async fn find_by_id(
     State(finder): State<Arc<ByIdFinder<AnalysisRepository>>>,
     Path(analysis_id): Path<Uuid>,
+    Query(localisation): Query<Localisation>,
 ) -> Result<Json<Analysis>, (StatusCode, String)> {
     let analysis = finder.find(&analysis_id).await;
     match analysis {
-        Ok(Some(analysis)) => Ok(Json(analysis)),
+        Ok(Some(mut analysis)) => {
+            analysis.localise(localisation.language);
+            Ok(Json(analysis))
+        }
         Ok(None) => Err((
             StatusCode::NOT_FOUND,
             format!("No analysis found with id `{analysis_id}`"),
@@ -142,3 +211,48 @@ pub async fn delete(
     let result = deleter.delete(&analysis_id).await;
     result.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::analysis::infrastructure::repository::in_memory::InMemory;
+
+    fn reanalyzer() -> State<Arc<Reanalyzer<InMemory, TokioBus>>> {
+        State(Arc::new(Reanalyzer::new(
+            Arc::new(InMemory::default()),
+            Arc::new(TokioBus::new(1)),
+        )))
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_overrides() {
+        for body in ["{\"seed\": ", "{\"seed\": \"one\"}"] {
+            let result = reanalyze(
+                reanalyzer(),
+                Path(Uuid::new_v4()),
+                Bytes::from_static(body.as_bytes()),
+            )
+            .await;
+
+            let (status, message) = result.unwrap_err();
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert!(message.starts_with("Invalid overrides in the body"));
+        }
+    }
+
+    #[tokio::test]
+    async fn runs_again_without_overrides_on_an_empty_body() {
+        let id = Uuid::new_v4();
+
+        let result = reanalyze(reanalyzer(), Path(id), Bytes::new()).await;
+
+        // The body is accepted and the missing analysis is reported by the service
+        assert_eq!(
+            result,
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Cannot find analysis with id `{id}` to run it again")
+            ))
+        );
+    }
+}
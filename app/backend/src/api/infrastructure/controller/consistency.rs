@@ -0,0 +1,96 @@
+use crate::api::infrastructure::repository::mongo::analysis::Mongo as AnalysisRepository;
+use crate::api::infrastructure::repository::mongo::consistency::Mongo as ConsistencyRepository;
+use crate::api::infrastructure::repository::mongo::lap::Mongo as LapRepository;
+
+use shared::analysis::domain::analysis::catalogue::Language;
+use shared::analysis::domain::analysis::coaching::Coaching;
+use shared::consistency::application::create::service::Creator;
+use shared::consistency::application::delete::service::Deleter;
+use shared::consistency::application::find::by_id::service::Finder as ByIdFinder;
+use shared::consistency::application::focus::service::Focuser;
+use shared::consistency::domain::consistency::scope::Scope;
+use shared::consistency::domain::consistency::Consistency;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct Args {
+    id: Uuid,
+    /// File or session whose laps are compared
+    scope: Scope,
+    driver: String,
+}
+
+/// Query parameters of the endpoints returning coaching.
+#[derive(Debug, Deserialize)]
+pub struct Localisation {
+    /// Language of the coaching messages, English by default.
+    #[serde(default)]
+    language: Language,
+}
+
+/// # Errors
+///
+/// Will return `Err` if the service call produces any error
+pub async fn creator(
+    State(creator): State<Arc<Creator<ConsistencyRepository, LapRepository>>>,
+    Json(args): Json<Args>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    match creator.create(args.id, args.scope, &args.driver).await {
+        Ok(()) => Ok(StatusCode::CREATED),
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e)),
+    }
+}
+
+/// # Errors
+///
+/// Will return `Err` if the service call produces any error
+pub async fn find_by_id(
+    State(finder): State<Arc<ByIdFinder<ConsistencyRepository>>>,
+    Path(consistency_id): Path<Uuid>,
+) -> Result<Json<Consistency>, (StatusCode, String)> {
+    match finder.find(&consistency_id).await {
+        Ok(Some(consistency)) => Ok(Json(consistency)),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            format!("No consistency found with id `{consistency_id}`"),
+        )),
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e)),
+    }
+}
+
+/// Coaching of an analysis with the advice on the sections where the driver is least
+/// consistent first.
+///
+/// # Errors
+///
+/// Will return `Err` if the service call produces any error
+pub async fn focus(
+    State(focuser): State<Arc<Focuser<ConsistencyRepository, AnalysisRepository>>>,
+    Path((consistency_id, analysis_id)): Path<(Uuid, Uuid)>,
+    Query(localisation): Query<Localisation>,
+) -> Result<Json<Coaching>, (StatusCode, String)> {
+    match focuser.focus(&consistency_id, &analysis_id).await {
+        Ok(mut coaching) => {
+            coaching.localise(localisation.language);
+            Ok(Json(coaching))
+        }
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e)),
+    }
+}
+
+/// # Errors
+///
+/// Will return `Err` if the service call produces any error
+pub async fn delete(
+    State(deleter): State<Arc<Deleter<ConsistencyRepository>>>,
+    Path(consistency_id): Path<Uuid>,
+) -> Result<(), (StatusCode, String)> {
+    let result = deleter.delete(&consistency_id).await;
+    result.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))
+}
@@ -1,5 +1,6 @@
 use crate::api::infrastructure::event::tokio_bus::TokioBus;
 use crate::api::infrastructure::repository::mongo::file::Mongo as FileRepository;
+use crate::api::infrastructure::repository::mongo::lap::Mongo as LapRepository;
 
 use shared::common::domain::criteria::Criteria;
 use shared::file::application::delete::service::Deleter;
@@ -7,12 +8,26 @@ use shared::file::application::find::by_criteria::service::Finder as ByCriteriaF
 use shared::file::application::find::by_id::service::Finder as ByIdFinder;
 use shared::file::domain::file::File;
 use shared::file::domain::files::Files;
+use shared::lap::application::analyze_tyres::service::TyreAnalyzer;
+use shared::lap::application::plan_fuel::service::FuelPlanner;
+use shared::lap::application::summarize::service::Summarizer;
+use shared::lap::domain::lap::fuel::{Consumption, Plan, RaceLength};
+use shared::lap::domain::lap::summary::Summary;
+use shared::lap::domain::lap::tyres::TyreReport;
 
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
 use axum::Json;
+use serde::Deserialize;
 use std::sync::Arc;
 
+#[derive(Debug, Deserialize)]
+pub struct FuelPlanArgs {
+    race: RaceLength,
+    /// Laps of fuel kept in the tank at the end of every stint, one by default
+    reserve_laps: Option<f32>,
+}
+
 /// # Errors
 ///
 /// Will return `Err` if the service call produces any error
@@ -63,3 +78,78 @@ pub async fn delete(
     let result = deleter.delete(&user_id).await;
     result.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))
 }
+
+/// Summarises the laps extracted from a file.
+///
+/// # Errors
+///
+/// Will return `Err` if the service call produces any error
+pub async fn summary(
+    State(summarizer): State<Arc<Summarizer<LapRepository>>>,
+    Path(file_id): Path<String>,
+) -> Result<Json<Summary>, (StatusCode, String)> {
+    match summarizer.summarize(&file_id).await {
+        Ok(Some(summary)) => Ok(Json(summary)),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            format!("No laps found in file with id `{file_id}`"),
+        )),
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e)),
+    }
+}
+
+/// Measures the fuel consumption over the laps extracted from a file.
+///
+/// # Errors
+///
+/// Will return `Err` if the service call produces any error
+pub async fn fuel_consumption(
+    State(planner): State<Arc<FuelPlanner<LapRepository>>>,
+    Path(file_id): Path<String>,
+) -> Result<Json<Consumption>, (StatusCode, String)> {
+    match planner.consumption(&file_id).await {
+        Ok(Some(consumption)) => Ok(Json(consumption)),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            format!("No fuel consumption measured in file with id `{file_id}`"),
+        )),
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e)),
+    }
+}
+
+/// Plans the stints and pit stops of a race from the consumption measured in a file.
+///
+/// # Errors
+///
+/// Will return `Err` if the service call produces any error
+pub async fn fuel_plan(
+    State(planner): State<Arc<FuelPlanner<LapRepository>>>,
+    Path(file_id): Path<String>,
+    Json(args): Json<FuelPlanArgs>,
+) -> Result<Json<Plan>, (StatusCode, String)> {
+    planner
+        .plan(&file_id, args.race, args.reserve_laps)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e))
+}
+
+/// Reports the temperature balance, pressure build-up and wear of the tyres over the laps
+/// extracted from a file.
+///
+/// # Errors
+///
+/// Will return `Err` if the service call produces any error
+pub async fn tyres(
+    State(analyzer): State<Arc<TyreAnalyzer<LapRepository>>>,
+    Path(file_id): Path<String>,
+) -> Result<Json<TyreReport>, (StatusCode, String)> {
+    match analyzer.analyze(&file_id).await {
+        Ok(Some(report)) => Ok(Json(report)),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            format!("No laps found in file with id `{file_id}`"),
+        )),
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e)),
+    }
+}
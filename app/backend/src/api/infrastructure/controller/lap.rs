@@ -1,22 +1,44 @@
 use crate::api::infrastructure::repository::mongo::lap::Mongo as LapRepository;
 
+use shared::analysis::domain::analysis::grip::GripUtilisation;
+use shared::lap::application::analyze_grip::service::GripAnalyzer;
 use shared::lap::application::delete::service::Deleter;
 use shared::lap::application::find::by_criteria::service::Finder as ByCriteriaFinder;
 use shared::lap::application::find::by_id::service::Finder as ByIdFinder;
 use shared::lap::application::find::header_by_id::service::Finder as ByIdHeaderFinder;
 use shared::lap::application::find::headers_by_criteria::service::Finder as ByCriteriaHeadersFinder;
+use shared::lap::application::synthesize::service::Synthesizer;
 use shared::lap::domain::lap::header::Header;
 use shared::lap::domain::lap::headers::Headers;
+use shared::lap::domain::lap::synthetic::Segmentation;
 use shared::lap::domain::lap::Lap;
 use shared::lap::domain::laps::Laps;
 
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
 use axum::Json;
+use serde::Deserialize;
 use shared::common::domain::criteria::Criteria;
 use std::sync::Arc;
 use uuid::Uuid;
 
+const DEFAULT_SYNTHETIC_BLEND_PCT: f32 = 0.002;
+
+#[derive(Debug, Deserialize)]
+pub struct SynthesizeArgs {
+    id: Uuid,
+    driver: String,
+    car: String,
+    circuit: String,
+    /// Sector start points (lap fraction), the `SplitTimeInfo` of the session by default
+    sectors: Option<Vec<f32>>,
+    /// Number of equally sized segments, used when no sectors are given and the session has
+    /// none
+    segments: Option<usize>,
+    /// Lap fraction used to blend telemetry at each seam
+    blend_pct: Option<f32>,
+}
+
 /// # Errors
 ///
 /// Will return `Err` if the service call produces any error
@@ -107,3 +129,48 @@ pub async fn delete(
     let result = deleter.delete(&lap_id).await;
     result.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))
 }
+
+/// # Errors
+///
+/// Will return `Err` if the service call produces any error
+pub async fn grip(
+    State(analyzer): State<Arc<GripAnalyzer<LapRepository>>>,
+    Path(lap_id): Path<Uuid>,
+) -> Result<Json<GripUtilisation>, (StatusCode, String)> {
+    match analyzer.analyze(&lap_id).await {
+        Ok(Some(grip)) => Ok(Json(grip)),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            format!("No lap found with id `{lap_id}`"),
+        )),
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e)),
+    }
+}
+
+/// # Errors
+///
+/// Will return `Err` if the service call produces any error
+pub async fn synthesize(
+    State(synthesizer): State<Arc<Synthesizer<LapRepository>>>,
+    Json(args): Json<SynthesizeArgs>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let segmentation = args
+        .sectors
+        .map(Segmentation::Sectors)
+        .or(args.segments.map(Segmentation::Fixed));
+
+    match synthesizer
+        .synthesize(
+            args.id,
+            &args.driver,
+            &args.car,
+            &args.circuit,
+            segmentation.as_ref(),
+            args.blend_pct.unwrap_or(DEFAULT_SYNTHETIC_BLEND_PCT),
+        )
+        .await
+    {
+        Ok(()) => Ok(StatusCode::CREATED),
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e)),
+    }
+}
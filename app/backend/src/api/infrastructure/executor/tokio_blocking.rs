@@ -0,0 +1,21 @@
+use shared::common::domain::executor::{Executor, Work};
+
+use async_trait::async_trait;
+use std::time::Instant;
+
+/// Executor running the work on the blocking thread pool of tokio, so the async workers stay
+/// free to serve requests. The time taken by every work is logged at debug level.
+#[derive(Default, Debug)]
+pub struct TokioBlocking;
+
+#[async_trait]
+impl Executor for TokioBlocking {
+    async fn run<T: Send + 'static>(&self, label: &str, work: Work<T>) -> Result<T, String> {
+        let start = Instant::now();
+        let result = tokio::task::spawn_blocking(work)
+            .await
+            .map_err(|e| format!("{label} failed: {e}"));
+        tracing::debug!("{label} took {:?}", start.elapsed());
+        result
+    }
+}
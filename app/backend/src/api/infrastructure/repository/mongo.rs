@@ -16,6 +16,7 @@ use serde::de::DeserializeOwned;
 use std::fmt::{Debug, Display};
 
 pub mod analysis;
+pub mod consistency;
 pub mod file;
 pub mod lap;
 
@@ -113,24 +114,7 @@ where
             .iter()
             .map(|filter| {
                 let field = filter.field().name().to_owned(); // TODO: Semantic access in all accesses of this type
-                let value = match Self::map_criteria_field(&field, filter.value().get()) {
-                    Ok(v) => v,
-                    Err(e) => return Err(e),
-                };
-                let doc = match filter.condition() {
-                    Condition::Equal => doc!(field: value),
-                    Condition::NotEqual => doc!(field: doc!("$ne": value)),
-                    Condition::GreaterThan => doc!(field: doc!("$gt": value)),
-                    Condition::LowerThan => doc!(field: doc!("$lt": value)),
-                    Condition::Contains => {
-                        //doc!(field : doc!( "$regex" : doc!("$toString" : value)))
-                        doc!(field : doc!( "$regex" : value, "$options": "i" ))
-                    }
-                    // TODO: Revisar filtro
-                    Condition::NotContains => {
-                        doc!(field : doc!( "$not" : doc!("$toString" : value)))
-                    }
-                };
+                let doc = Self::filter_criteria(&field, &filter.condition(), filter.value().get())?;
                 tracing::warn!("{doc:?}");
                 Ok(bson::Bson::from(doc))
             })
@@ -156,6 +140,21 @@ where
         //Ok(self.query(collection, query, Some(find_opts)).await?)
     }
 
+    /// Builds the query of a single criteria filter. Fields not stored as plain values may
+    /// override it, the rest are compared through `map_criteria_field`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the provided field name or value cannot be converted
+    /// into a valid BSON format.
+    fn filter_criteria(
+        field: &str,
+        condition: &Condition,
+        value: &str,
+    ) -> Result<Document, String> {
+        Self::map_criteria_field(field, value).map(|value| filter_value(field, condition, value))
+    }
+
     /// # Errors
     ///
     /// This function will return an error in the following situations:
@@ -165,6 +164,24 @@ where
     fn map_criteria_field(name: &str, value: &str) -> Result<Bson, String>;
 }
 
+/// Query comparing `field` with `value` according to `condition`.
+fn filter_value(field: &str, condition: &Condition, value: Bson) -> Document {
+    match condition {
+        Condition::Equal => doc!(field: value),
+        Condition::NotEqual => doc!(field: doc!("$ne": value)),
+        Condition::GreaterThan => doc!(field: doc!("$gt": value)),
+        Condition::LowerThan => doc!(field: doc!("$lt": value)),
+        Condition::Contains => {
+            //doc!(field : doc!( "$regex" : doc!("$toString" : value)))
+            doc!(field : doc!( "$regex" : value, "$options": "i" ))
+        }
+        // TODO: Revisar filtro
+        Condition::NotContains => {
+            doc!(field : doc!( "$not" : doc!("$toString" : value)))
+        }
+    }
+}
+
 #[derive(PartialEq, Eq, Debug, thiserror::Error)]
 pub enum Error {
     #[error("Error parsing client options: {0}")]
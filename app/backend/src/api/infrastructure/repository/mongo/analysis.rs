@@ -1,19 +1,23 @@
 mod entity;
 
 use crate::api::infrastructure::repository::mongo::analysis::entity::Entity;
-use crate::api::infrastructure::repository::mongo::Mongo as MongoTrait;
+use crate::api::infrastructure::repository::mongo::{filter_value, Mongo as MongoTrait};
 use crate::api::infrastructure::settings::Settings;
 
 use shared::analysis::domain::analyses::Analyses;
 use shared::analysis::domain::analysis::header::Header;
 use shared::analysis::domain::analysis::headers::Headers;
+use shared::analysis::domain::analysis::segments::Segments;
 use shared::analysis::domain::analysis::status::Status;
 use shared::analysis::domain::analysis::Analysis;
 use shared::analysis::domain::repository::Repository;
+use shared::common::domain::criteria::filter::condition::Condition;
 use shared::common::domain::criteria::Criteria;
 
 use async_trait::async_trait;
-use bson::{doc, Bson};
+use bson::{doc, Bson, Document};
+use chrono::DateTime;
+use mongodb::options::FindOneOptions;
 use mongodb::Collection;
 use uuid::Uuid;
 
@@ -22,6 +26,41 @@ pub struct Mongo {
 }
 
 impl MongoTrait<Analysis, Entity> for Mongo {
+    /// Statuses are filtered by their variant, e.g. `Completed`, whatever their fields. Statuses
+    /// stored before the stages were tracked match their current variant too.
+    fn filter_criteria(
+        field: &str,
+        condition: &Condition,
+        value: &str,
+    ) -> Result<Document, String> {
+        if field != "status" {
+            return Self::map_criteria_field(field, value)
+                .map(|value| filter_value(field, condition, value));
+        }
+
+        // Documents stored before the stages were tracked keep the legacy variant
+        let mut variants = vec![doc! { format!("status.{value}"): doc! { "$exists": true } }];
+        match value {
+            "Queued" => variants.push(doc! { "status.Pending": doc! { "$exists": true } }),
+            "Failed" => variants.push(doc! { "status.Error": doc! { "$exists": true } }),
+            "Completed" => variants.push(doc! { "status": "Completed" }),
+            "Running" | "Cancelled" => {}
+            unknown => {
+                return Err(format!(
+                    "Status `{unknown}` not registered in criteria search"
+                ))
+            }
+        }
+
+        match condition {
+            Condition::Equal => Ok(doc! { "$or": variants }),
+            Condition::NotEqual => Ok(doc! { "$nor": variants }),
+            _ => Err(format!(
+                "Condition `{condition:?}` not supported by the status in criteria search"
+            )),
+        }
+    }
+
     fn map_criteria_field(name: &str, value: &str) -> Result<Bson, String> {
         match name {
             "id" => bson::Uuid::parse_str(value)
@@ -31,10 +70,6 @@ impl MongoTrait<Analysis, Entity> for Mongo {
             "date" => bson::DateTime::parse_rfc3339_str(value)
                 .map_err(|e| e.to_string())
                 .map(Bson::from),
-            "status" => {
-                let status: Status = serde_json::from_str(value).map_err(|e| e.to_string())?;
-                bson::to_bson(&status).map_err(|e| e.to_string())
-            }
             unknown => Err(format!(
                 "Field `{unknown}` not registered in criteria search"
             )),
@@ -43,6 +78,45 @@ impl MongoTrait<Analysis, Entity> for Mongo {
 }
 
 impl Mongo {
+    /// Filter matching the analysis `id` while its status is still `current`.
+    ///
+    /// Statuses read from documents stored before the stages were tracked are set at the Unix
+    /// epoch and are serialised differently from the stored ones, so those documents are
+    /// matched by their legacy variant.
+    fn status_filter(id: &Uuid, current: &Status) -> Result<Document, String> {
+        let bson_id = bson::Uuid::parse_str(id.to_string())
+            .map_err(|e| format!("Error parsing uuid: {e}"))?;
+        let mut variants =
+            vec![doc! { "status": bson::to_bson(current).map_err(|e| e.to_string())? }];
+        let legacy = match current {
+            Status::Queued { at, .. } if *at == DateTime::UNIX_EPOCH => {
+                Some(doc! { "status.Pending": doc! { "$exists": true } })
+            }
+            Status::Failed {
+                stage: None, at, ..
+            } if *at == DateTime::UNIX_EPOCH => {
+                Some(doc! { "status.Error": doc! { "$exists": true } })
+            }
+            Status::Completed { at } if *at == DateTime::UNIX_EPOCH => {
+                Some(doc! { "status": "Completed" })
+            }
+            _ => None,
+        };
+        variants.extend(legacy);
+        Ok(doc! { "_id": bson_id, "$or": variants })
+    }
+
+    /// Filter matching the analyses of `circuit` other than `exclude` that have segments.
+    fn circuit_segments_filter(circuit: &str, exclude: &Uuid) -> Result<Document, String> {
+        let bson_id = bson::Uuid::parse_str(exclude.to_string())
+            .map_err(|e| format!("Error parsing uuid: {e}"))?;
+        Ok(doc! {
+            "circuit": circuit,
+            "_id": doc! { "$ne": bson_id },
+            "segments": doc! { "$ne": Bson::Null },
+        })
+    }
+
     /// # Errors
     ///
     /// This function will return an error in the following situations:
@@ -94,6 +168,38 @@ impl Repository for Mongo {
         Ok(())
     }
 
+    async fn update_if_status(
+        &self,
+        analysis: &Analysis,
+        current: &Status,
+    ) -> Result<bool, String> {
+        let filter = Self::status_filter(&analysis.header.id, current)?;
+        let entity = Entity::try_from(analysis.clone())?;
+        let result = self
+            .collection
+            .replace_one(filter, entity, None)
+            .await
+            .map_err(|e| format!("{e}"))?;
+        Ok(result.matched_count > 0)
+    }
+
+    async fn update_status(
+        &self,
+        id: &Uuid,
+        current: &Status,
+        status: &Status,
+    ) -> Result<bool, String> {
+        let filter = Self::status_filter(id, current)?;
+        let status = bson::to_bson(status).map_err(|e| e.to_string())?;
+        let update = doc! { "$set": doc! { "status": status } };
+        let result = self
+            .collection
+            .update_one(filter, update, None)
+            .await
+            .map_err(|e| format!("{e}"))?;
+        Ok(result.matched_count > 0)
+    }
+
     async fn find_by_id(&self, id: &Uuid) -> Result<Option<Analysis>, String> {
         let bson_id = bson::Uuid::parse_str(id.to_string())
             .map_err(|e| format!("Error parsing uuid: {e}"))?;
@@ -128,4 +234,118 @@ impl Repository for Mongo {
             })
             .map_err(|e| e.to_string())
     }
+
+    async fn find_circuit_segments(
+        &self,
+        circuit: &str,
+        exclude: &Uuid,
+    ) -> Result<Option<Segments>, String> {
+        let filter = Self::circuit_segments_filter(circuit, exclude)?;
+        // Only the segments of the earliest analysis are read, not its whole results
+        let options = FindOneOptions::builder()
+            .sort(doc! { "date": 1 })
+            .projection(doc! { "_id": 0, "segments": 1 })
+            .build();
+        let found = self
+            .collection
+            .clone_with_type::<Document>()
+            .find_one(filter, options)
+            .await
+            .map_err(|e| format!("{e}"))?;
+        found
+            .and_then(|mut document| document.remove("segments"))
+            .map(|segments| bson::from_bson(segments).map_err(|e| e.to_string()))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn filters_statuses_by_variant() {
+        assert_eq!(
+            Mongo::filter_criteria("status", &Condition::Equal, "Completed"),
+            Ok(doc! { "$or": [
+                { "status.Completed": { "$exists": true } },
+                { "status": "Completed" },
+            ] })
+        );
+        assert_eq!(
+            Mongo::filter_criteria("status", &Condition::NotEqual, "Queued"),
+            Ok(doc! { "$nor": [
+                { "status.Queued": { "$exists": true } },
+                { "status.Pending": { "$exists": true } },
+            ] })
+        );
+        assert_eq!(
+            Mongo::filter_criteria("status", &Condition::Equal, "Cancelled"),
+            Ok(doc! { "$or": [{ "status.Cancelled": { "$exists": true } }] })
+        );
+        assert!(Mongo::filter_criteria("status", &Condition::Equal, "Pending").is_err());
+        assert!(Mongo::filter_criteria("status", &Condition::GreaterThan, "Queued").is_err());
+    }
+
+    #[test]
+    fn matches_the_legacy_status_of_documents_stored_before_the_stages() {
+        let id = Uuid::new_v4();
+        let bson_id = bson::Uuid::parse_str(id.to_string()).unwrap();
+        let legacy = doc! {
+            "_id": bson_id,
+            "status": { "Pending": {
+                "ref_id": Uuid::new_v4().to_string(),
+                "target_id": Uuid::new_v4().to_string(),
+            } },
+        };
+        let current: Status = bson::from_bson(legacy.get("status").unwrap().clone()).unwrap();
+
+        let filter = Mongo::status_filter(&id, &current).unwrap();
+
+        assert_eq!(
+            filter,
+            doc! { "_id": bson_id, "$or": [
+                { "status": bson::to_bson(&current).unwrap() },
+                { "status.Pending": { "$exists": true } },
+            ] }
+        );
+        assert!(legacy
+            .get_document("status")
+            .unwrap()
+            .contains_key("Pending"));
+    }
+
+    #[test]
+    fn matches_only_the_current_status_of_new_documents() {
+        let id = Uuid::new_v4();
+        let current = Status::Queued {
+            ref_id: Uuid::new_v4(),
+            target_id: Uuid::new_v4(),
+            at: Utc::now(),
+        };
+
+        let filter = Mongo::status_filter(&id, &current).unwrap();
+
+        assert_eq!(
+            filter.get_array("$or").unwrap(),
+            &vec![Bson::from(
+                doc! { "status": bson::to_bson(&current).unwrap() }
+            )]
+        );
+    }
+
+    #[test]
+    fn filters_the_segments_of_other_analyses_of_the_circuit() {
+        let id = Uuid::new_v4();
+        let bson_id = bson::Uuid::parse_str(id.to_string()).unwrap();
+        assert_eq!(
+            Mongo::circuit_segments_filter("Spa", &id),
+            Ok(doc! {
+                "circuit": "Spa",
+                "_id": { "$ne": bson_id },
+                "segments": { "$ne": Bson::Null },
+            })
+        );
+    }
 }
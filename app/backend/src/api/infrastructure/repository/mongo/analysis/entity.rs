@@ -1,7 +1,22 @@
+use shared::analysis::domain::analysis::braking_zones::BrakingZones;
+use shared::analysis::domain::analysis::clustering_model::Algorithm;
 use shared::analysis::domain::analysis::clusters_memberships::ClustersMemberships;
+use shared::analysis::domain::analysis::coaching::Coaching;
+use shared::analysis::domain::analysis::corners::Corners;
+use shared::analysis::domain::analysis::gear_shifts::GearShifts;
+use shared::analysis::domain::analysis::grip::GripComparison;
 use shared::analysis::domain::analysis::header::Header as DomainHeader;
+use shared::analysis::domain::analysis::joint_clusters::JointConfig;
+use shared::analysis::domain::analysis::overrides::Overrides as DomainOverrides;
+use shared::analysis::domain::analysis::racing_line::RacingLine;
 use shared::analysis::domain::analysis::reference_lap::ReferenceLap;
+use shared::analysis::domain::analysis::segments::Segments;
+use shared::analysis::domain::analysis::smoothness::Smoothness;
 use shared::analysis::domain::analysis::status::Status;
+use shared::analysis::domain::analysis::tag_policy::ChannelTagPolicy;
+use shared::analysis::domain::analysis::technique::Technique;
+use shared::analysis::domain::analysis::validity::Criterion;
+use shared::analysis::domain::analysis::version::Version as DomainVersion;
 use shared::analysis::domain::analysis::Analysis;
 use shared::lap::domain::lap::variables::Variables;
 
@@ -17,11 +32,125 @@ pub struct Entity {
     pub date: bson::DateTime,
     pub circuit: String,
     pub status: Status,
+    #[serde(default)]
+    pub ref_lap_id: Option<Uuid>,
+    #[serde(default)]
+    pub target_lap_id: Option<Uuid>,
     pub reference: Option<ReferenceLap>,
     pub target: Option<ReferenceLap>,
     pub union_distances: Vec<f32>,
     pub differences: Option<Variables>,
+    pub segments: Option<Segments>,
+    pub corners: Option<Corners>,
+    pub braking_zones: Option<BrakingZones>,
+    pub gear_shifts: Option<GearShifts>,
+    pub technique: Option<Technique>,
+    pub smoothness: Option<Smoothness>,
+    pub racing_line: Option<RacingLine>,
+    #[serde(default)]
+    pub grip: Option<GripComparison>,
     pub clustering: Option<ClustersMemberships>,
+    // BSON has no unsigned 64 bit integers, so seeds are stored with the same bits as i64
+    pub seed: Option<i64>,
+    pub coaching: Option<Coaching>,
+    #[serde(default = "first_version")]
+    pub version: u32,
+    pub overrides: Option<Overrides>,
+    #[serde(default)]
+    pub versions: Vec<Version>,
+}
+
+/// Analyses stored before versions existed are in their first version.
+const fn first_version() -> u32 {
+    1
+}
+
+/// Stored `DomainOverrides`, with the seed stored as i64.
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+pub struct Overrides {
+    pub c: Option<(usize, Option<usize>, Option<usize>)>,
+    pub m: Option<(f64, Option<f64>, Option<f64>)>,
+    pub max_iter: Option<(usize, Option<usize>, Option<usize>)>,
+    pub error: Option<(f64, Option<f64>, Option<f64>)>,
+    pub joint: Option<JointConfig>,
+    pub algorithm: Option<Algorithm>,
+    pub criterion: Option<Criterion>,
+    pub seed: Option<i64>,
+    pub tag_policies: Option<Vec<ChannelTagPolicy>>,
+}
+
+/// Stored `DomainVersion`, with the seed stored as i64.
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+pub struct Version {
+    pub number: u32,
+    pub overrides: Option<Overrides>,
+    pub seed: Option<i64>,
+    pub clustering: Option<ClustersMemberships>,
+    pub coaching: Option<Coaching>,
+}
+
+const fn seed_to_i64(seed: u64) -> i64 {
+    i64::from_ne_bytes(seed.to_ne_bytes())
+}
+
+const fn seed_from_i64(seed: i64) -> u64 {
+    u64::from_ne_bytes(seed.to_ne_bytes())
+}
+
+impl From<DomainOverrides> for Overrides {
+    fn from(overrides: DomainOverrides) -> Self {
+        Self {
+            c: overrides.c,
+            m: overrides.m,
+            max_iter: overrides.max_iter,
+            error: overrides.error,
+            joint: overrides.joint,
+            algorithm: overrides.algorithm,
+            criterion: overrides.criterion,
+            seed: overrides.seed.map(seed_to_i64),
+            tag_policies: overrides.tag_policies,
+        }
+    }
+}
+
+impl From<Overrides> for DomainOverrides {
+    fn from(overrides: Overrides) -> Self {
+        Self {
+            c: overrides.c,
+            m: overrides.m,
+            max_iter: overrides.max_iter,
+            error: overrides.error,
+            joint: overrides.joint,
+            algorithm: overrides.algorithm,
+            criterion: overrides.criterion,
+            seed: overrides.seed.map(seed_from_i64),
+            tag_policies: overrides.tag_policies,
+        }
+    }
+}
+
+impl From<DomainVersion> for Version {
+    fn from(version: DomainVersion) -> Self {
+        Self {
+            number: version.number,
+            overrides: version.overrides.map(Overrides::from),
+            seed: version.seed.map(seed_to_i64),
+            clustering: version.clustering,
+            coaching: version.coaching,
+        }
+    }
+}
+
+impl From<Version> for DomainVersion {
+    fn from(version: Version) -> Self {
+        Self {
+            number: version.number,
+            overrides: version.overrides.map(DomainOverrides::from),
+            seed: version.seed.map(seed_from_i64),
+            clustering: version.clustering,
+            coaching: version.coaching,
+        }
+    }
 }
 impl TryInto<Analysis> for Entity {
     type Error = String;
@@ -35,12 +164,32 @@ impl TryInto<Analysis> for Entity {
                 date: self.date.to_chrono(),
                 circuit: self.circuit,
                 status: self.status,
+                ref_lap_id: self.ref_lap_id,
+                target_lap_id: self.target_lap_id,
             },
             reference: self.reference,
             target: self.target,
             union_distances: self.union_distances,
             differences: self.differences,
+            segments: self.segments,
+            corners: self.corners,
+            braking_zones: self.braking_zones,
+            gear_shifts: self.gear_shifts,
+            technique: self.technique,
+            smoothness: self.smoothness,
+            racing_line: self.racing_line,
+            grip: self.grip,
             clustering: self.clustering,
+            seed: self.seed.map(seed_from_i64),
+            coaching: self.coaching,
+            version: self.version,
+            overrides: self.overrides.map(DomainOverrides::from),
+            versions: self
+                .versions
+                .into_iter()
+                .map(DomainVersion::from)
+                .collect::<Vec<_>>()
+                .into(),
         })
     }
 }
@@ -68,11 +217,31 @@ impl TryFrom<Analysis> for Entity {
             date,
             circuit: analysis.header.circuit,
             status: analysis.header.status,
+            ref_lap_id: analysis.header.ref_lap_id,
+            target_lap_id: analysis.header.target_lap_id,
             reference: analysis.reference,
             target: analysis.target,
             union_distances: analysis.union_distances,
             differences: analysis.differences,
+            segments: analysis.segments,
+            corners: analysis.corners,
+            braking_zones: analysis.braking_zones,
+            gear_shifts: analysis.gear_shifts,
+            technique: analysis.technique,
+            smoothness: analysis.smoothness,
+            racing_line: analysis.racing_line,
+            grip: analysis.grip,
             clustering: analysis.clustering,
+            seed: analysis.seed.map(seed_to_i64),
+            coaching: analysis.coaching,
+            version: analysis.version,
+            overrides: analysis.overrides.map(Overrides::from),
+            versions: analysis
+                .versions
+                .iter()
+                .cloned()
+                .map(Version::from)
+                .collect(),
         })
     }
 }
@@ -0,0 +1,83 @@
+mod entity;
+
+use crate::api::infrastructure::repository::mongo::consistency::entity::Entity;
+use crate::api::infrastructure::repository::mongo::Mongo as MongoTrait;
+use crate::api::infrastructure::settings::Settings;
+
+use shared::consistency::domain::consistency::Consistency;
+use shared::consistency::domain::repository::Repository;
+
+use async_trait::async_trait;
+use bson::{doc, Bson};
+use mongodb::Collection;
+use uuid::Uuid;
+
+pub struct Mongo {
+    collection: Collection<Entity>,
+}
+
+impl MongoTrait<Consistency, Entity> for Mongo {
+    fn map_criteria_field(name: &str, value: &str) -> Result<Bson, String> {
+        match name {
+            "id" => bson::Uuid::parse_str(value)
+                .map(Bson::from)
+                .map_err(|e| e.to_string()),
+            "driver" | "circuit" => Ok(Bson::from(value)),
+            "date" => bson::DateTime::parse_rfc3339_str(value)
+                .map_err(|e| e.to_string())
+                .map(Bson::from),
+            unknown => Err(format!(
+                "Field `{unknown}` not registered in criteria search"
+            )),
+        }
+    }
+}
+
+impl Mongo {
+    /// # Errors
+    ///
+    /// This function will return an error in the following situations:
+    ///
+    /// * If the collection cannot be obtained due to an issue with the database connection or
+    ///   configuration, an error string describing the problem will be returned.
+    pub async fn new(settings: &Settings) -> Result<Self, String> {
+        let collection = Self::get_collection(settings, &settings.mongo.collections.consistency)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(Self { collection })
+    }
+}
+
+#[async_trait]
+impl Repository for Mongo {
+    async fn create(&self, consistency: Consistency) -> Result<(), String> {
+        let entity = Entity::try_from(consistency)?;
+        let _ = self
+            .collection
+            .insert_one(entity, None)
+            .await
+            .map_err(|e| format!("{e}"))?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<(), String> {
+        let bson_id = bson::Uuid::parse_str(id.to_string())
+            .map_err(|e| format!("Error parsing uuid: {e}"))?;
+        let filter = doc! { "_id": bson_id };
+        let _ = self
+            .collection
+            .delete_one(filter, None)
+            .await
+            .map_err(|e| format!("{e}"))?;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &Uuid) -> Result<Option<Consistency>, String> {
+        let bson_id = bson::Uuid::parse_str(id.to_string())
+            .map_err(|e| format!("Error parsing uuid: {e}"))?;
+        let query = Some(doc! { "_id": bson_id });
+        let result = self.query(&self.collection, query, None).await;
+        let consistencies: Vec<Consistency> = result.map_err(|e| format!("{e}"))?;
+        Ok(consistencies.first().cloned())
+    }
+}
@@ -0,0 +1,72 @@
+use shared::consistency::domain::consistency::envelope::Envelopes;
+use shared::consistency::domain::consistency::scope::Scope;
+use shared::consistency::domain::consistency::section::Sections;
+use shared::consistency::domain::consistency::Consistency;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+pub struct Entity {
+    // id is moved out to identify the consistency as an entire document
+    #[serde(rename = "_id")]
+    pub id: bson::uuid::Uuid,
+    pub scope: Scope,
+    pub driver: String,
+    pub circuit: String,
+    pub date: bson::DateTime,
+    pub laps: Vec<Uuid>,
+    pub distances: Vec<f32>,
+    pub envelopes: Envelopes,
+    pub sections: Sections,
+}
+
+impl TryInto<Consistency> for Entity {
+    type Error = String;
+
+    fn try_into(self) -> Result<Consistency, Self::Error> {
+        let id = Uuid::parse_str(&self.id.to_string()).map_err(|e| e.to_string())?;
+        Ok(Consistency {
+            id,
+            scope: self.scope,
+            driver: self.driver,
+            circuit: self.circuit,
+            date: self.date.to_chrono(),
+            laps: self.laps,
+            distances: self.distances,
+            envelopes: self.envelopes,
+            sections: self.sections,
+        })
+    }
+}
+
+impl TryFrom<Consistency> for Entity {
+    type Error = String;
+
+    fn try_from(consistency: Consistency) -> Result<Self, Self::Error> {
+        let id = bson::Uuid::parse_str(consistency.id.to_string()).map_err(|e| {
+            format!(
+                "uuid::Uuid {} cannot be cast to bson::Uuid: {e}",
+                consistency.id
+            )
+        })?;
+        let date =
+            bson::DateTime::parse_rfc3339_str(consistency.date.to_rfc3339()).map_err(|e| {
+                format!(
+                    "chrono::DateTime {} cannot be cast to bson::DateTime: {e}",
+                    consistency.date
+                )
+            })?;
+        Ok(Self {
+            id,
+            scope: consistency.scope,
+            driver: consistency.driver,
+            circuit: consistency.circuit,
+            date,
+            laps: consistency.laps,
+            distances: consistency.distances,
+            envelopes: consistency.envelopes,
+            sections: consistency.sections,
+        })
+    }
+}
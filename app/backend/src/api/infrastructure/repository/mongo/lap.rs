@@ -32,6 +32,10 @@ impl MongoTrait<Lap, Entity> for Mongo {
             "date" => bson::DateTime::parse_rfc3339_str(value)
                 .map_err(|e| e.to_string())
                 .map(Bson::from),
+            "synthetic" => value
+                .parse::<bool>()
+                .map(Bson::from)
+                .map_err(|e| e.to_string()),
             unknown => Err(format!(
                 "Field `{unknown}` not registered in criteria search"
             )),
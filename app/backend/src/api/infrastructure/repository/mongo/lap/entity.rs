@@ -1,7 +1,12 @@
 use crate::shared::lap::domain::lap::Lap;
 
+use shared::lap::domain::lap::events::Event;
+use shared::lap::domain::lap::fuel::Tank;
 use shared::lap::domain::lap::header::Header as DomainHeader;
+use shared::lap::domain::lap::synthetic::Segmentation;
+use shared::lap::domain::lap::tyres::Tyres;
 use shared::lap::domain::lap::variables::Variables;
+use shared::lap::domain::lap::wheel_slip::WheelSlip;
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -19,7 +24,21 @@ pub struct Entity {
     pub circuit: String,
     pub date: bson::DateTime,
     pub time: f32,
+    #[serde(default)]
+    pub synthetic: bool,
+    #[serde(default)]
+    pub smoothness: Option<f32>,
     pub variables: Variables,
+    #[serde(default)]
+    pub wheel_slips: Vec<WheelSlip>,
+    #[serde(default)]
+    pub tyres: Tyres,
+    #[serde(default)]
+    pub events: Vec<Event>,
+    #[serde(default)]
+    pub tank: Option<Tank>,
+    #[serde(default)]
+    pub sectors: Option<Segmentation>,
 }
 
 impl TryInto<Lap> for Entity {
@@ -38,8 +57,15 @@ impl TryInto<Lap> for Entity {
                 circuit: self.circuit,
                 car: self.car,
                 time: self.time,
+                synthetic: self.synthetic,
+                smoothness: self.smoothness,
             },
             variables: self.variables,
+            wheel_slips: self.wheel_slips,
+            tyres: self.tyres,
+            events: self.events,
+            tank: self.tank,
+            sectors: self.sectors,
         })
     }
 }
@@ -71,7 +97,14 @@ impl TryFrom<Lap> for Entity {
             circuit: lap.header.circuit,
             car: lap.header.car,
             time: lap.header.time,
+            synthetic: lap.header.synthetic,
+            smoothness: lap.header.smoothness,
             variables: lap.variables,
+            wheel_slips: lap.wheel_slips,
+            tyres: lap.tyres,
+            events: lap.events,
+            tank: lap.tank,
+            sectors: lap.sectors,
         })
     }
 }
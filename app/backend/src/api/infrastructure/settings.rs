@@ -1,5 +1,9 @@
 use config::{Config, ConfigError, Environment, File};
 use serde::{Deserialize, Serialize};
+use shared::analysis::domain::analysis::channel::Channel;
+use shared::analysis::domain::analysis::clustering_model::Algorithm;
+use shared::analysis::domain::analysis::tag_policy::ChannelTagPolicy;
+use shared::analysis::domain::analysis::validity::Criterion;
 use std::fmt::{Display, Formatter};
 
 const BACKEND_CONFIG_PATH_ENV: &str = "BACKEND_CONFIG_PATH";
@@ -44,6 +48,7 @@ pub struct Mongo {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MongoCollections {
     pub analysis: String,
+    pub consistency: String,
     pub file: String,
     pub lap: String,
 }
@@ -54,6 +59,17 @@ pub struct FcmGrid {
     pub m: InitMaxMinF64,
     pub max_iter: InitMaxMinUsize,
     pub error: InitMaxMinF64,
+    pub joint: Option<Joint>,
+    pub algorithm: Option<Algorithm>,
+    pub criterion: Option<Criterion>,
+    pub seed: Option<u64>,
+    pub tag_policies: Option<Vec<ChannelTagPolicy>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Joint {
+    pub channels: Vec<Channel>,
+    pub window: Option<f32>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
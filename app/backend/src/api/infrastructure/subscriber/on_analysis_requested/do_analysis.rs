@@ -0,0 +1,150 @@
+use crate::api::infrastructure::event::tokio_bus::TokioBus;
+use crate::api::infrastructure::executor::tokio_blocking::TokioBlocking;
+use crate::api::infrastructure::repository::mongo::analysis::Mongo as AnalysisRepository;
+use crate::api::infrastructure::repository::mongo::lap::Mongo as LapRepository;
+
+use shared::analysis::application::analyze::service::Analyzer;
+use shared::analysis::application::find::by_id::service::Finder;
+use shared::analysis::application::update::service::Updater;
+use shared::analysis::domain::event::created::Created;
+use shared::analysis::domain::event::reanalyzed::Reanalyzed;
+use shared::common::domain::event::subscriber::{Error, Subscriber};
+use shared::common::domain::event::Event;
+
+use async_trait::async_trait;
+use shared::analysis::domain::analysis::fcm_grid::Config;
+use std::sync::Arc;
+use tokio::sync::broadcast::Receiver;
+use tokio::sync::RwLock;
+
+type EventReceiver = Receiver<Arc<dyn Event>>;
+
+pub struct DoAnalysis {
+    receiver: Arc<RwLock<EventReceiver>>,
+    finder: Arc<Finder<AnalysisRepository>>,
+    analyzer: Arc<Analyzer<AnalysisRepository, LapRepository, TokioBlocking>>,
+    updater: Arc<Updater<AnalysisRepository>>,
+    fcm_grid_config: Config,
+}
+
+impl DoAnalysis {
+    /// Subscriber running the analyses that have just been created.
+    #[must_use]
+    pub async fn new(
+        event_bus: &Arc<TokioBus>,
+        finder: &Arc<Finder<AnalysisRepository>>,
+        analyzer: &Arc<Analyzer<AnalysisRepository, LapRepository, TokioBlocking>>,
+        updater: &Arc<Updater<AnalysisRepository>>,
+        fcm_grid_config: Config,
+    ) -> Self {
+        Self::subscribe(
+            Created::event_id(),
+            event_bus,
+            finder,
+            analyzer,
+            updater,
+            fcm_grid_config,
+        )
+        .await
+    }
+
+    /// Subscriber running again the analyses whose reanalysis has been requested.
+    #[must_use]
+    pub async fn for_reanalyses(
+        event_bus: &Arc<TokioBus>,
+        finder: &Arc<Finder<AnalysisRepository>>,
+        analyzer: &Arc<Analyzer<AnalysisRepository, LapRepository, TokioBlocking>>,
+        updater: &Arc<Updater<AnalysisRepository>>,
+        fcm_grid_config: Config,
+    ) -> Self {
+        Self::subscribe(
+            Reanalyzed::event_id(),
+            event_bus,
+            finder,
+            analyzer,
+            updater,
+            fcm_grid_config,
+        )
+        .await
+    }
+
+    async fn subscribe(
+        event_id: &str,
+        event_bus: &Arc<TokioBus>,
+        finder: &Arc<Finder<AnalysisRepository>>,
+        analyzer: &Arc<Analyzer<AnalysisRepository, LapRepository, TokioBlocking>>,
+        updater: &Arc<Updater<AnalysisRepository>>,
+        fcm_grid_config: Config,
+    ) -> Self {
+        tracing::debug!("Creating subscriber");
+
+        let receiver = event_bus.receiver(event_id).await;
+        let receiver = Arc::new(RwLock::new(receiver));
+        let finder = Arc::clone(finder);
+        let analyzer = Arc::clone(analyzer);
+        let updater = Arc::clone(updater);
+
+        Self {
+            receiver,
+            finder,
+            analyzer,
+            updater,
+            fcm_grid_config,
+        }
+    }
+}
+
+#[async_trait]
+impl Subscriber for DoAnalysis {
+    async fn receive(&self) -> Result<Arc<dyn Event>, Error> {
+        tracing::trace!("Waiting for new analyses");
+        let mut receiver = self.receiver.write().await;
+        receiver
+            .recv()
+            .await
+            .map_err(|e| Error::Receive(format!("{e}")))
+    }
+
+    async fn process(&self, event: Arc<dyn Event>) {
+        tracing::debug!("Processing new file {}", event.id());
+        let id = if let Some(created) = event.as_any().downcast_ref::<Created>() {
+            created.id
+        } else if let Some(reanalyzed) = event.as_any().downcast_ref::<Reanalyzed>() {
+            reanalyzed.id
+        } else {
+            tracing::error!(
+                "Can't downcast {} to {} or {}",
+                event.id(),
+                Created::event_id(),
+                Reanalyzed::event_id()
+            );
+            return;
+        };
+
+        // The CPU bound stages run on the blocking pool, the rest stays on this task
+        let result = self.analyzer.analyze(id, &self.fcm_grid_config).await;
+
+        if let Err(msg) = result {
+            // Only the status changes, so the laps and the previous versions are kept. Failures
+            // in a stage have already been stored by the analyzer, along with the stage, and
+            // cancelled analyses are left as they are.
+            if let Ok(Some(mut analysis)) = self.finder.find(&id).await {
+                if !analysis.header.status.is_active() {
+                    tracing::error!("Analysis with id `{}` failed: {}", id, msg);
+                    return;
+                }
+                analysis.fail(None, msg);
+
+                if let Err(msg) = self.updater.updater(&analysis).await {
+                    tracing::error!(
+                        "IMPORTANT: Can't update analysis `{}` to set erroneous status {}",
+                        id,
+                        msg,
+                    );
+                };
+            }
+        } else {
+            tracing::info!("Analysis with id `{}` done!", id);
+        }
+    }
+}
@@ -3,6 +3,10 @@ use shared::ibt::domain::file::session_info::SessionInfo;
 use shared::ibt::domain::file::var_value::primitive::Primitive;
 use shared::ibt::domain::file::var_value::VarValue;
 use shared::ibt::domain::file::variables::Variables as IbtVariables;
+use shared::lap::domain::lap::events::{detect as detect_events, TrackStatus};
+use shared::lap::domain::lap::fuel::Tank;
+use shared::lap::domain::lap::synthetic::Segmentation;
+use shared::lap::domain::lap::tyres::{TyreSamples, Tyres};
 use shared::lap::domain::lap::variables::Variables;
 use shared::lap::domain::lap::Lap;
 use shared::lap::domain::laps::Laps;
@@ -70,11 +74,24 @@ pub fn ibt_variables2laps(
     let date = get_datetime_or_now(session_info);
 
     let variables_by_lap: HashMap<u16, Variables> = group_variables_by_lap(variables);
+    let tyre_samples_by_lap: HashMap<u16, Vec<TyreSamples>> = group_tyre_samples_by_lap(variables);
+    let status_by_lap: HashMap<u16, TrackStatus> = group_track_status_by_lap(variables);
+    let tires_aero = session_info
+        .car_setup
+        .as_ref()
+        .and_then(|cs| cs.tires_aero.as_ref());
+    let tank = Tank::from_session_info(session_info);
+    // A single sector would keep whole laps when building the theoretical best one
+    let sectors = session_info
+        .split_time_info
+        .as_ref()
+        .map(Segmentation::from)
+        .filter(|sectors| sectors.boundaries().len() > 2);
 
     let laps_vec: Vec<Lap> = variables_by_lap
         .iter()
         .map(|(lap_number, variables)| {
-            Lap::new(
+            let mut lap = Lap::new(
                 Uuid::new_v4(),
                 file_id.to_string(),
                 *lap_number,
@@ -84,7 +101,16 @@ pub fn ibt_variables2laps(
                 circuit.clone(),
                 date,
                 variables.clone(),
-            )
+            );
+            let samples = tyre_samples_by_lap
+                .get(lap_number)
+                .map_or(&[][..], Vec::as_slice);
+            lap.tyres = Tyres::new(tires_aero, samples);
+            let status = status_by_lap.get(lap_number).cloned().unwrap_or_default();
+            lap.events = detect_events(&lap.variables, &status);
+            lap.tank.clone_from(&tank);
+            lap.sectors.clone_from(&sectors);
+            lap
         })
         .collect();
 
@@ -158,6 +184,13 @@ fn group_variables_by_lap(variables: &IbtVariables) -> HashMap<u16, Variables> {
     let steering_wheel_angle: Vec<f32> = extract_values!(variables, "SteeringWheelAngle", Float);
     let fuel_level: Vec<f32> = extract_values!(variables, "FuelLevel", Float);
     let lap_current_lap_time: Vec<f32> = extract_values!(variables, "LapCurrentLapTime", Float);
+    let lat_accel: Vec<f32> = extract_values!(variables, "LatAccel", Float);
+    let long_accel: Vec<f32> = extract_values!(variables, "LongAccel", Float);
+    let yaw_rate: Vec<f32> = extract_values!(variables, "YawRate", Float);
+    let lf_speed: Vec<f32> = extract_values!(variables, "LFspeed", Float);
+    let rf_speed: Vec<f32> = extract_values!(variables, "RFspeed", Float);
+    let lr_speed: Vec<f32> = extract_values!(variables, "LRspeed", Float);
+    let rr_speed: Vec<f32> = extract_values!(variables, "RRspeed", Float);
 
     let mut groups = HashMap::new();
 
@@ -182,6 +215,90 @@ fn group_variables_by_lap(variables: &IbtVariables) -> HashMap<u16, Variables> {
         lap_variables
             .lap_current_lap_time
             .push(lap_current_lap_time[i]);
+        // Files recorded without some of the inertial channels keep them empty
+        lap_variables.lat_accel.extend(lat_accel.get(i));
+        lap_variables.long_accel.extend(long_accel.get(i));
+        lap_variables.yaw_rate.extend(yaw_rate.get(i));
+        // Wheel speeds are only recorded by some cars
+        if let (Some(&lf), Some(&rf), Some(&lr), Some(&rr)) = (
+            lf_speed.get(i),
+            rf_speed.get(i),
+            lr_speed.get(i),
+            rr_speed.get(i),
+        ) {
+            lap_variables.lf_speed.push(lf);
+            lap_variables.rf_speed.push(rf);
+            lap_variables.lr_speed.push(lr);
+            lap_variables.rr_speed.push(rr);
+        }
+    });
+
+    groups
+}
+
+/// Groups the live tyre channels by lap, one `TyreSamples` per wheel in the order of
+/// `Wheel::ALL`. Laps of files recorded without them have no samples.
+fn group_tyre_samples_by_lap(variables: &IbtVariables) -> HashMap<u16, Vec<TyreSamples>> {
+    let lap: Vec<u16> = extract_values!(variables, "Lap", Int, u16);
+    let channels: Vec<[Vec<f32>; 7]> = ["LF", "RF", "LR", "RR"]
+        .iter()
+        .map(|wheel| {
+            [
+                "tempCL", "tempCM", "tempCR", "press", "wearL", "wearM", "wearR",
+            ]
+            .map(|channel| {
+                let name = format!("{wheel}{channel}");
+                extract_values!(variables, name.as_str(), Float)
+            })
+        })
+        .collect();
+    if channels.iter().flatten().all(Vec::is_empty) {
+        return HashMap::new();
+    }
+
+    let mut groups: HashMap<u16, Vec<TyreSamples>> = HashMap::new();
+
+    (0..lap.len()).for_each(|i| {
+        let lap_samples = groups
+            .entry(lap[i])
+            .or_insert_with(|| vec![TyreSamples::default(); channels.len()]);
+        for (samples, wheel) in lap_samples.iter_mut().zip(&channels) {
+            let targets = [
+                &mut samples.temp_left,
+                &mut samples.temp_middle,
+                &mut samples.temp_right,
+                &mut samples.pressure,
+                &mut samples.wear_left,
+                &mut samples.wear_middle,
+                &mut samples.wear_right,
+            ];
+            for (target, values) in targets.into_iter().zip(wheel) {
+                target.extend(values.get(i));
+            }
+        }
+    });
+
+    groups
+}
+
+/// Groups the track surface and incident count channels by lap, keeping the last sample before
+/// each lap so events across the start/finish line are detected. Files recorded without them
+/// leave them empty.
+fn group_track_status_by_lap(variables: &IbtVariables) -> HashMap<u16, TrackStatus> {
+    let lap: Vec<u16> = extract_values!(variables, "Lap", Int, u16);
+    let surface: Vec<i32> = extract_values!(variables, "PlayerTrackSurface", Int);
+    let incidents: Vec<i32> = extract_values!(variables, "PlayerCarMyIncidentCount", Int);
+
+    let mut groups: HashMap<u16, TrackStatus> = HashMap::new();
+
+    (0..lap.len()).for_each(|i| {
+        let lap_status = groups.entry(lap[i]).or_insert_with(|| TrackStatus {
+            previous_surface: i.checked_sub(1).and_then(|j| surface.get(j).copied()),
+            previous_incidents: i.checked_sub(1).and_then(|j| incidents.get(j).copied()),
+            ..TrackStatus::default()
+        });
+        lap_status.surface.extend(surface.get(i));
+        lap_status.incidents.extend(incidents.get(i));
     });
 
     groups
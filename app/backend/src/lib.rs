@@ -4,6 +4,7 @@ pub mod api {
         pub mod app_assembler;
         pub mod controller {
             pub mod analysis;
+            pub mod consistency;
             pub mod file;
             pub mod ibt_extractor;
             pub mod lap;
@@ -11,11 +12,14 @@ pub mod api {
         pub mod event {
             pub mod tokio_bus;
         }
+        pub mod executor {
+            pub mod tokio_blocking;
+        }
         pub mod repository {
             pub mod mongo;
         }
         pub mod subscriber {
-            pub mod on_analysis_created {
+            pub mod on_analysis_requested {
                 pub mod do_analysis;
             }
             pub mod on_file_deleted {
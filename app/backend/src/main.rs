@@ -2,13 +2,19 @@ extern crate symracing_virtual_mentor_backend_lib as backend_lib;
 
 use backend_lib::api::infrastructure::app_assembler::AppAssembler;
 use backend_lib::api::infrastructure::controller::analysis::{
-    creator as create_analysis, delete as delete_analysis,
+    cancel as cancel_analysis, creator as create_analysis, delete as delete_analysis,
     find_by_criteria as find_analysis_by_criteria, find_by_id as find_analysis_by_id,
     find_header_by_id as find_analysis_header_by_id,
-    find_headers_by_criteria as find_analysis_headers_by_criteria,
+    find_headers_by_criteria as find_analysis_headers_by_criteria, reanalyze as reanalyze_analysis,
+};
+use backend_lib::api::infrastructure::controller::consistency::{
+    creator as create_consistency, delete as delete_consistency,
+    find_by_id as find_consistency_by_id, focus as focus_consistency,
 };
 use backend_lib::api::infrastructure::controller::file::{
-    delete as delete_file, find_by_criteria as find_file_by_criteria, find_by_id as find_file_by_id,
+    delete as delete_file, find_by_criteria as find_file_by_criteria,
+    find_by_id as find_file_by_id, fuel_consumption as file_fuel_consumption,
+    fuel_plan as file_fuel_plan, summary as file_summary, tyres as file_tyres,
 };
 use backend_lib::api::infrastructure::controller::ibt_extractor::{
     upload, ControllerState as UploadIbtState,
@@ -16,10 +22,11 @@ use backend_lib::api::infrastructure::controller::ibt_extractor::{
 use backend_lib::api::infrastructure::controller::lap::{
     delete as delete_lap, find_by_criteria as find_lap_by_criteria, find_by_id as find_lap_by_id,
     find_header_by_id as find_lap_header_by_id,
-    find_headers_by_criteria as find_lap_headers_by_criteria,
+    find_headers_by_criteria as find_lap_headers_by_criteria, grip as lap_grip,
+    synthesize as synthesize_lap,
 };
 use backend_lib::api::infrastructure::settings::Settings;
-use backend_lib::api::infrastructure::subscriber::on_analysis_created::do_analysis::DoAnalysis;
+use backend_lib::api::infrastructure::subscriber::on_analysis_requested::do_analysis::DoAnalysis;
 use backend_lib::api::infrastructure::subscriber::{
     manager::Manager as SubscriberManager, on_file_deleted::delete_laps::LapDeleter,
     on_ibt_extracted::validate_file::FileValidator,
@@ -84,6 +91,16 @@ async fn main() -> io::Result<()> {
             )
             .await,
         ))
+        .with_subscriber(Arc::new(
+            DoAnalysis::for_reanalyses(
+                &app_assembler.event_bus,
+                &app_assembler.analysis.by_id_finder,
+                &app_assembler.analysis.analyzer,
+                &app_assembler.analysis.updater,
+                app_assembler.analysis.fcm_grid_config.clone(),
+            )
+            .await,
+        ))
         .build()
         .run();
 
@@ -110,6 +127,14 @@ fn configure_router(app_assembler: &AppAssembler) -> Router {
             "/delete/:id",
             delete(delete_analysis).with_state(Arc::clone(&app_assembler.analysis.deleter)),
         )
+        .route(
+            "/reanalyze/:id",
+            put(reanalyze_analysis).with_state(Arc::clone(&app_assembler.analysis.reanalyzer)),
+        )
+        .route(
+            "/cancel/:id",
+            put(cancel_analysis).with_state(Arc::clone(&app_assembler.analysis.canceller)),
+        )
         .route(
             "/find/:id",
             get(find_analysis_by_id).with_state(Arc::clone(&app_assembler.analysis.by_id_finder)),
@@ -144,6 +169,22 @@ fn configure_router(app_assembler: &AppAssembler) -> Router {
             "/find",
             post(find_file_by_criteria)
                 .with_state(Arc::clone(&app_assembler.file.by_criteria_finder)),
+        )
+        .route(
+            "/summary/:id",
+            get(file_summary).with_state(Arc::clone(&app_assembler.lap.summarizer)),
+        )
+        .route(
+            "/fuel/:id",
+            get(file_fuel_consumption).with_state(Arc::clone(&app_assembler.lap.fuel_planner)),
+        )
+        .route(
+            "/fuel/plan/:id",
+            post(file_fuel_plan).with_state(Arc::clone(&app_assembler.lap.fuel_planner)),
+        )
+        .route(
+            "/tyres/:id",
+            get(file_tyres).with_state(Arc::clone(&app_assembler.lap.tyre_analyzer)),
         );
 
     let lap_routes = Router::new()
@@ -169,6 +210,33 @@ fn configure_router(app_assembler: &AppAssembler) -> Router {
             "/find/header",
             post(find_lap_headers_by_criteria)
                 .with_state(Arc::clone(&app_assembler.lap.by_criteria_header_finder)),
+        )
+        .route(
+            "/synthesize",
+            put(synthesize_lap).with_state(Arc::clone(&app_assembler.lap.synthesizer)),
+        )
+        .route(
+            "/grip/:id",
+            get(lap_grip).with_state(Arc::clone(&app_assembler.lap.grip_analyzer)),
+        );
+
+    let consistency_routes = Router::new()
+        .route(
+            "/create",
+            put(create_consistency).with_state(Arc::clone(&app_assembler.consistency.creator)),
+        )
+        .route(
+            "/delete/:id",
+            delete(delete_consistency).with_state(Arc::clone(&app_assembler.consistency.deleter)),
+        )
+        .route(
+            "/find/:id",
+            get(find_consistency_by_id)
+                .with_state(Arc::clone(&app_assembler.consistency.by_id_finder)),
+        )
+        .route(
+            "/focus/:id/:analysis_id",
+            get(focus_consistency).with_state(Arc::clone(&app_assembler.consistency.focuser)),
         );
 
     let ibt_extractor_routes = Router::new().route(
@@ -181,6 +249,7 @@ fn configure_router(app_assembler: &AppAssembler) -> Router {
 
     Router::new()
         .nest("/analysis", analysis_routes)
+        .nest("/consistency", consistency_routes)
         .nest("/file", file_routes)
         .nest("/lap", lap_routes)
         .nest("/ibt_extractor", ibt_extractor_routes)
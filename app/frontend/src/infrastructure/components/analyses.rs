@@ -16,6 +16,7 @@ use yew_router::prelude::Link;
 pub enum Msg {
     FetchAnalyses,
     DeleteAnalysis(Uuid),
+    CancelAnalysis(Uuid),
     SetAnalyses(DomainAnalyses),
     SetFilter(Criteria),
     Error(String),
@@ -85,6 +86,21 @@ impl Component for Analyses {
                 });
                 false
             }
+            Msg::CancelAnalysis(id) => {
+                let analyses_repo = self.analyses_repository.clone();
+                let link = ctx.link().clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    match analyses_repo.cancel(&id).await {
+                        Ok(()) => {
+                            link.send_message(Msg::FetchAnalyses);
+                        }
+                        Err(e) => {
+                            link.send_message(Msg::Error(e));
+                        }
+                    }
+                });
+                false
+            }
             Msg::SetFilter(filter) => {
                 self.filter = filter;
                 self.is_fetching = true;
@@ -106,6 +122,7 @@ impl Component for Analyses {
     fn view(&self, ctx: &Context<Self>) -> Html {
         let fetch_analyses = ctx.link().callback(|()| Msg::FetchAnalyses);
         let delete_analysis_callback = ctx.link().callback(Msg::DeleteAnalysis);
+        let cancel_analysis_callback = ctx.link().callback(Msg::CancelAnalysis);
 
         html! {
             <div class="container">
@@ -118,6 +135,7 @@ impl Component for Analyses {
                     analyses={self.analyses.clone()}
                     error={self.error.clone()}
                     {delete_analysis_callback}
+                    {cancel_analysis_callback}
                     fetch_callback={fetch_analyses.clone()}
                     fetching={self.is_fetching}
                 />
@@ -17,6 +17,8 @@ pub struct AnalysisListProps {
     pub fetch_callback: Callback<()>,
     #[prop_or_default]
     pub delete_analysis_callback: Option<Callback<Uuid>>,
+    #[prop_or_default]
+    pub cancel_analysis_callback: Option<Callback<Uuid>>,
 }
 
 pub enum Msg {
@@ -115,9 +117,11 @@ impl AnalysisListComponent {
                             <p class="image is-64x64">
                                 <h1 class="title is-1 is-center">{
                                     match analysis.status {
-                                        Status::Pending { ref_id: _, target_id: _ } => {"⏳"}
-                                        Status::Completed => {"🧬"}
-                                        Status::Error(_) => {"🚫"}
+                                        Status::Queued { .. } => {"⏳"}
+                                        Status::Running { .. } => {"⚙️"}
+                                        Status::Completed { .. } => {"🧬"}
+                                        Status::Failed { .. } => {"🚫"}
+                                        Status::Cancelled { .. } => {"⏹️"}
                                     }
                                 }</h1>
                             </p>
@@ -129,13 +133,15 @@ impl AnalysisListComponent {
                                     <small class="subtitle is-5">{analysis.circuit.clone()}</small>
                                     <br/>
                                     <small><b>{"Date: "}</b>{analysis.date.to_string()}</small>
+                                    <br/>
+                                    <small>{Self::status_description(&analysis.status)}</small>
                                 </p>
                             </div>
                         </div>
                         <div class="media-right">
                             {
                             match analysis.status {
-                                Status::Completed => html! {
+                                Status::Completed { .. } => html! {
                                     <>
                                         <Link<Route> to={Route::Dashboard { analysis_id: analysis.id }}>
                                             <button
@@ -145,10 +151,12 @@ impl AnalysisListComponent {
                                         {Self::add_delete_button(ctx, analysis, modal)}
                                     </>
                                 },
-                                Status::Error(_) => html! {
+                                Status::Failed { .. } | Status::Cancelled { .. } => html! {
                                     {Self::add_delete_button(ctx, analysis, modal)}
                                 },
-                                Status::Pending{ .. } => html! { <></> },
+                                Status::Queued { .. } | Status::Running { .. } => {
+                                    Self::add_cancel_button(ctx, analysis)
+                                }
                             }
                         }
                         </div>
@@ -158,10 +166,49 @@ impl AnalysisListComponent {
         }
     }
 
+    fn status_description(status: &Status) -> String {
+        match status {
+            Status::Queued { at, .. } => format!("Queued since {at}"),
+            Status::Running { stage, percent, .. } => format!("Running {stage} ({percent}%)"),
+            Status::Completed { at } => format!("Completed on {at}"),
+            Status::Failed {
+                stage: Some(stage),
+                reason,
+                ..
+            } => format!("Failed on {stage}: {reason}"),
+            Status::Failed {
+                stage: None,
+                reason,
+                ..
+            } => format!("Failed: {reason}"),
+            Status::Cancelled {
+                stage: Some(stage),
+                at,
+            } => format!("Cancelled on {stage} at {at}"),
+            Status::Cancelled { stage: None, at } => format!("Cancelled at {at}"),
+        }
+    }
+
     pub fn analysis_name(analysis: &Analysis) -> String {
         analysis.name.clone()
     }
 
+    fn add_cancel_button(ctx: &Context<Self>, analysis: &Analysis) -> Html {
+        ctx.props().cancel_analysis_callback.clone().map_or_else(
+            || html! {},
+            |cb| {
+                let id = analysis.id;
+                html! {
+                    <button
+                        class="button is-warning is-outlined is-large"
+                        title="Cancel"
+                        onclick={Callback::from(move |_| cb.emit(id))}
+                    >{"⏹️"}</button>
+                }
+            },
+        )
+    }
+
     fn add_delete_button(ctx: &Context<Self>, analysis: &Analysis, modal: bool) -> Html {
         ctx.props().delete_analysis_callback.clone().map_or_else(
             || html! {},
@@ -3,6 +3,8 @@ use crate::infrastructure::components::repository_context::Repositories;
 use crate::infrastructure::components::routes::switch;
 use crate::infrastructure::components::routes::Route;
 
+use shared::analysis::domain::analysis::catalogue::Language;
+
 use yew::prelude::*;
 use yew_router::BrowserRouter;
 use yew_router::Switch;
@@ -10,12 +12,15 @@ use yew_router::Switch;
 #[function_component(App)]
 pub fn app() -> Html {
     let ctx = use_state(Repositories::default);
+    let language = use_state(Language::default);
     html! {
         <ContextProvider<Repositories> context={(*ctx).clone()}>
-            <BrowserRouter>
-                <NavBar />
-                <Switch<Route> render={switch} />
-            </BrowserRouter>
+            <ContextProvider<UseStateHandle<Language>> context={language}>
+                <BrowserRouter>
+                    <NavBar />
+                    <Switch<Route> render={switch} />
+                </BrowserRouter>
+            </ContextProvider<UseStateHandle<Language>>>
         </ContextProvider<Repositories>>
     }
 }
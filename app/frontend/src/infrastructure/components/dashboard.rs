@@ -1,16 +1,22 @@
 mod circuit;
+mod consistency;
 mod hook;
 mod plot;
 mod suggestions;
 
 use crate::infrastructure::components::dashboard::circuit::Circuit;
+use crate::infrastructure::components::dashboard::consistency::ConsistencyPanel;
 use crate::infrastructure::components::dashboard::hook::use_analyses;
-use crate::infrastructure::components::dashboard::plot::{create, Type};
+use crate::infrastructure::components::dashboard::plot::{create, create_gg, Type, GG_PLOT_ID};
 use crate::infrastructure::components::dashboard::suggestions::Suggestions;
 use crate::infrastructure::repository::analysis::http::Http as AnalysisHttpRepository;
 use crate::infrastructure::settings::Settings;
 
+use shared::analysis::domain::analysis::catalogue::Language;
+use shared::analysis::domain::analysis::distances::to_reference;
 use shared::analysis::domain::analysis::Analysis;
+use shared::lap::domain::lap::events::Event as LapEvent;
+use shared::lap::domain::lap::wheel_slip::WheelSlip;
 
 use gloo_events::EventListener;
 use log::{error, info};
@@ -19,6 +25,7 @@ use uuid::Uuid;
 use wasm_bindgen::prelude::wasm_bindgen;
 use wasm_bindgen::JsValue;
 use wasm_bindgen_futures::js_sys::Object;
+use web_sys::HtmlSelectElement;
 use yew::prelude::*;
 
 // components/fx
@@ -101,6 +108,7 @@ pub enum PlotlyDrawerMsg {
     PlotlyHover,
     SyncPlotlyHover(String),
     SyncCanvas(String),
+    Drawn,
     Error(String),
 }
 
@@ -162,6 +170,16 @@ impl Component for PlotlyDrawer {
             });
         }
 
+        // The G-G diagram is not plotted against the distance, so its hover is not synced
+        if let Ok(plot) = create_gg(&analysis) {
+            ctx.link().send_future(async move {
+                match js_new_plot_(GG_PLOT_ID, &plot.to_js_object()).await {
+                    Ok(_) => Self::Message::Drawn,
+                    Err(e) => Self::Message::Error(format!("{e:?}")),
+                }
+            });
+        }
+
         html! {
             <div class="fixed-grid">
                 <div class="grid">
@@ -173,9 +191,22 @@ impl Component for PlotlyDrawer {
                             latitudes={analysis.reference.clone().map_or_else(Vec::default, |a| a.variables.latitude)}
                             longitudes={analysis.reference.clone().map_or_else(Vec::default, |a| a.variables.longitude)}
                             distances={analysis.union_distances.clone()}
+                            wheel_slips={target_slips_on_reference(&analysis)}
+                            events={target_events_on_reference(&analysis)}
+                            lateral_offset={analysis.racing_line.clone().map_or_else(Vec::default, |r| r.lateral_offset)}
                         />
+                        if analysis.grip.is_some() {
+                            <div id={GG_PLOT_ID} class="mt-4" />
+                        }
                         <Suggestions
                             memberships={analysis.clustering.clone().unwrap_or_default()}
+                            coaching={analysis.coaching.clone().unwrap_or_default()}
+                            shift_suggestions={analysis.gear_shifts.clone().map_or_else(Vec::default, |g| g.suggestions)}
+                        />
+                        <ConsistencyPanel
+                            analysis_id={analysis.header.id}
+                            target_lap_id={analysis.header.target_lap_id}
+                            driver={analysis.target.as_ref().map_or_else(String::new, |t| t.driver.clone())}
                         />
                     </div>
 
@@ -208,13 +239,62 @@ impl Component for PlotlyDrawer {
                 // TODO: Manage error
                 true
             }
-            PlotlyDrawerMsg::SyncCanvas(_) | Self::Message::PlotlyHover => false,
+            PlotlyDrawerMsg::SyncCanvas(_) | Self::Message::PlotlyHover | Self::Message::Drawn => {
+                false
+            }
         }
     }
 }
 
 impl PlotlyDrawer {}
 
+/// Wheel slips of the target lap, moved to the distances where the reference lap passes the
+/// same points, as the circuit is drawn from the reference lap.
+fn target_slips_on_reference(analysis: &Analysis) -> Vec<WheelSlip> {
+    let (Some(reference), Some(target)) = (&analysis.reference, &analysis.target) else {
+        return Vec::new();
+    };
+    target
+        .wheel_slips
+        .iter()
+        .filter_map(|slip| {
+            let distance = to_reference(
+                &analysis.union_distances,
+                &reference.variables,
+                &target.variables,
+                slip.distance,
+            )?;
+            Some(WheelSlip {
+                distance,
+                ..slip.clone()
+            })
+        })
+        .collect()
+}
+
+/// Events of the target lap, moved to the reference lap like its wheel slips.
+fn target_events_on_reference(analysis: &Analysis) -> Vec<LapEvent> {
+    let (Some(reference), Some(target)) = (&analysis.reference, &analysis.target) else {
+        return Vec::new();
+    };
+    target
+        .events
+        .iter()
+        .filter_map(|event| {
+            let distance = to_reference(
+                &analysis.union_distances,
+                &reference.variables,
+                &target.variables,
+                event.distance,
+            )?;
+            Some(LapEvent {
+                distance,
+                ..event.clone()
+            })
+        })
+        .collect()
+}
+
 #[function_component(PlotlyLoader)]
 pub fn plotly_loader(Props { analysis }: &Props) -> Html {
     info!("Entering PlotlyLoader");
@@ -233,10 +313,58 @@ pub fn dashboard_data_fetcher(AnalysisProps { analysis_id: id }: &AnalysisProps)
 
     let settings = Settings::default();
     let analysis_repo = AnalysisHttpRepository::new(&settings);
+    let language = use_context::<UseStateHandle<Language>>().map_or_else(Language::default, |l| *l);
 
-    let analysis = use_analyses(id, analysis_repo)?;
+    let analysis = use_analyses(id, language, analysis_repo)?;
 
-    Ok(html! { <PlotlyDrawer analysis={analysis} /> })
+    Ok(html! { <VersionSwitch analysis={analysis} /> })
+}
+
+/// Lets the user choose which version of the analysis is drawn, the current one by default.
+#[function_component(VersionSwitch)]
+pub fn version_switch(Props { analysis }: &Props) -> Html {
+    let current = analysis.as_ref().map_or(1, |a| a.version);
+    let selected = use_state(|| current);
+
+    let Some(analysis) = analysis else {
+        return html! { <PlotlyDrawer analysis={None::<Analysis>} /> };
+    };
+
+    let on_version_change = {
+        let selected = selected.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Ok(number) = select.value().parse() {
+                selected.set(number);
+            }
+        })
+    };
+
+    let mut numbers: Vec<u32> = analysis.versions.iter().map(|v| v.number).collect();
+    numbers.push(current);
+
+    html! {
+        <>
+            if numbers.len() > 1 {
+                <div class="field is-grouped mt-4 ml-4">
+                    <label class="label mr-2">{"Version"}</label>
+                    <div class="control">
+                        <div class="select">
+                            <select onchange={on_version_change}>
+                                { for numbers.iter().rev().map(|&number| html! {
+                                    <option value={number.to_string()} selected={number == *selected}>
+                                        { if number == current { format!("v{number} (current)") } else { format!("v{number}") } }
+                                    </option>
+                                }) }
+                            </select>
+                        </div>
+                    </div>
+                </div>
+            }
+            // Every version is drawn from scratch
+            <PlotlyDrawer key={selected.to_string()} analysis={analysis.at_version(*selected)} />
+        </>
+    }
 }
 
 #[derive(Properties, PartialEq, Eq)]
@@ -247,6 +375,7 @@ pub struct AnalysisProps {
 #[function_component(Dashboard)]
 pub fn dashboard(AnalysisProps { analysis_id: id }: &AnalysisProps) -> Html {
     info!("Entering Dashboard");
+    let language = use_context::<UseStateHandle<Language>>().map_or_else(Language::default, |l| *l);
     let fallback = html! {
         <div class="block">
             <h1 class="title">{format!("Fetching Analysis `...`")}</h1>
@@ -256,7 +385,8 @@ pub fn dashboard(AnalysisProps { analysis_id: id }: &AnalysisProps) -> Html {
 
     html! {
         <Suspense {fallback}>
-            <DashboardDataFetcher analysis_id={*id} />
+            // Fetched again when the language changes, to get the coaching in that language
+            <DashboardDataFetcher key={language.code()} analysis_id={*id} />
         </Suspense>
     }
 }
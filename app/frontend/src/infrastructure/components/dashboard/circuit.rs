@@ -3,6 +3,8 @@ use crate::utils::{f32_as_f64, f32_as_i32, f64_as_f32, usize_as_f64};
 use crate::infrastructure::components::dashboard::suggestions;
 use serde::{Deserialize, Serialize};
 use serde_wasm_bindgen::to_value;
+use shared::lap::domain::lap::events::{Event, EventKind};
+use shared::lap::domain::lap::wheel_slip::{SlipKind, WheelSlip};
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::prelude::wasm_bindgen;
 use wasm_bindgen::JsCast;
@@ -16,6 +18,8 @@ pub const CANVAS_HEIGHT: f64 = 480.;
 pub const CANVAS_WIDTH: f64 = 800.;
 pub const CANVAS_MARGIN: f64 = 50.;
 pub const UPDATE_CIRCUIT_POINTER_EVENT: &str = "update_circuit_pointer";
+/// Lateral offset (m) drawn with the most saturated colour.
+const MAX_LATERAL_OFFSET: f32 = 3.;
 
 #[wasm_bindgen(module = "/assets/scripts/plotly_interop.js")]
 extern "C" {
@@ -121,6 +125,12 @@ pub struct Props {
     pub latitudes: Vec<f64>,
     pub longitudes: Vec<f64>,
     pub distances: Vec<f32>,
+    /// Lock-ups and wheelspins marked on the circuit
+    pub wheel_slips: Vec<WheelSlip>,
+    /// Off-tracks, incidents, spins and pit-road entries marked on the circuit
+    pub events: Vec<Event>,
+    /// Lateral offset of the target line at every distance, colouring the circuit
+    pub lateral_offset: Vec<f32>,
 }
 
 #[function_component(Circuit)]
@@ -132,6 +142,9 @@ pub fn circuit(props: &Props) -> Html {
     let latitudes = props.latitudes.clone();
     let longitudes = props.longitudes.clone();
     let distances = props.distances.clone();
+    let wheel_slips = props.wheel_slips.clone();
+    let events = props.events.clone();
+    let lateral_offset = props.lateral_offset.clone();
     let plot_div_ids = vec![
         "speed_plot",
         "throttle_plot",
@@ -145,6 +158,9 @@ pub fn circuit(props: &Props) -> Html {
 
     {
         let normalized_points = normalized_points.clone();
+        let wheel_slips = wheel_slips.clone();
+        let events = events.clone();
+        let lateral_offset = lateral_offset.clone();
         let canvas_ref = canvas_ref.clone();
         use_effect_with(canvas_ref, move |canvas_ref| {
             let document = web_sys::window().unwrap().document().unwrap();
@@ -162,7 +178,13 @@ pub fn circuit(props: &Props) -> Html {
                     find_nearest_point_by_distance(&normalized_points, distance)
                 {
                     context.clear_rect(0.0, 0.0, canvas.width().into(), canvas.height().into());
-                    draw_circuit(&context, &normalized_points);
+                    draw_circuit(
+                        &context,
+                        &normalized_points,
+                        &lateral_offset,
+                        &wheel_slips,
+                        &events,
+                    );
                     context.begin_path();
                     context
                         .arc(
@@ -193,6 +215,9 @@ pub fn circuit(props: &Props) -> Html {
     let onmousemove = {
         let canvas_ref = canvas_ref.clone();
         let normalized_points = normalized_points.clone();
+        let wheel_slips = wheel_slips.clone();
+        let events = events.clone();
+        let lateral_offset = lateral_offset.clone();
         Callback::from(move |event: MouseEvent| {
             let canvas = canvas_ref.cast::<HtmlCanvasElement>().unwrap();
             let rect = canvas.get_bounding_client_rect();
@@ -211,7 +236,13 @@ pub fn circuit(props: &Props) -> Html {
             {
                 // Redibujar el canvas
                 context.clear_rect(0.0, 0.0, canvas.width().into(), canvas.height().into());
-                draw_circuit(&context, &normalized_points);
+                draw_circuit(
+                    &context,
+                    &normalized_points,
+                    &lateral_offset,
+                    &wheel_slips,
+                    &events,
+                );
                 context.begin_path();
                 context
                     .arc(
@@ -253,7 +284,13 @@ pub fn circuit(props: &Props) -> Html {
                 .dyn_into::<CanvasRenderingContext2d>()
                 .unwrap();
             context.clear_rect(0.0, 0.0, canvas.width().into(), canvas.height().into());
-            draw_circuit(&context, &normalized_points);
+            draw_circuit(
+                &context,
+                &normalized_points,
+                &lateral_offset,
+                &wheel_slips,
+                &events,
+            );
         }
         || ()
     });
@@ -263,7 +300,13 @@ pub fn circuit(props: &Props) -> Html {
     }
 }
 
-fn draw_circuit(context: &CanvasRenderingContext2d, points: &[Point]) {
+fn draw_circuit(
+    context: &CanvasRenderingContext2d,
+    points: &[Point],
+    lateral_offset: &[f32],
+    wheel_slips: &[WheelSlip],
+    events: &[Event],
+) {
     context.set_stroke_style(&JsValue::from_str("white"));
     context.set_line_width(6.0);
     context.begin_path();
@@ -275,6 +318,63 @@ fn draw_circuit(context: &CanvasRenderingContext2d, points: &[Point]) {
         }
     }
     context.stroke();
+
+    // Sections where the target line is away from the reference line are coloured over it
+    if lateral_offset.len() == points.len() {
+        context.set_line_width(4.0);
+        for (pair, &offset) in points.windows(2).zip(&lateral_offset[1..]) {
+            context.begin_path();
+            context.move_to(pair[0].x, pair[0].y);
+            context.line_to(pair[1].x, pair[1].y);
+            context.set_stroke_style_str(&offset_color(offset));
+            context.stroke();
+        }
+    }
+
+    for slip in wheel_slips {
+        let Some(point) = find_nearest_point_by_distance(points, slip.distance) else {
+            continue;
+        };
+        let color = match slip.kind {
+            SlipKind::LockUp => "orange",
+            SlipKind::Wheelspin => "deepskyblue",
+        };
+        context.begin_path();
+        context
+            .arc(point.x, point.y, 5.0, 0.0, 2.0 * std::f64::consts::PI)
+            .unwrap();
+        context.set_fill_style_str(color);
+        context.fill();
+    }
+
+    for event in events {
+        let Some(point) = find_nearest_point_by_distance(points, event.distance) else {
+            continue;
+        };
+        context.set_fill_style_str(event_color(event.kind));
+        context.fill_rect(point.x - 6.0, point.y - 6.0, 12.0, 12.0);
+    }
+}
+
+/// Colour of the circuit where the target line is `offset` metres to the left (blue) or to
+/// the right (red) of the reference line, fading to white when both lines match.
+fn offset_color(offset: f32) -> String {
+    let fade = f32_as_i32(255. * (1. - (offset.abs() / MAX_LATERAL_OFFSET).min(1.)));
+    if offset > 0. {
+        format!("rgb({fade}, {fade}, 255)")
+    } else {
+        format!("rgb(255, {fade}, {fade})")
+    }
+}
+
+/// Colour of the markers of every event type, shared by the circuit and the plots.
+pub const fn event_color(kind: EventKind) -> &'static str {
+    match kind {
+        EventKind::OffTrack => "yellow",
+        EventKind::Incident => "magenta",
+        EventKind::Spin => "violet",
+        EventKind::PitEntry => "lightgray",
+    }
 }
 
 fn gps_coord(lat: &[f64], lon: &[f64], dist: &[f32]) -> Vec<GpsCoord> {
@@ -0,0 +1,128 @@
+use crate::infrastructure::components::repository_context::Repositories;
+use crate::infrastructure::repository::consistency::http::Request;
+
+use shared::analysis::domain::analysis::catalogue::{Language, Message};
+use shared::analysis::domain::analysis::coaching::Coaching;
+use shared::consistency::domain::consistency::scope::Scope;
+use shared::consistency::domain::consistency::Consistency;
+
+use uuid::Uuid;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub analysis_id: Uuid,
+    pub target_lap_id: Option<Uuid>,
+    /// Driver of the target lap.
+    pub driver: String,
+}
+
+/// Measures the consistency of the driver of the target lap over its session, and shows the
+/// sections where it is least consistent along with the advice on them.
+#[function_component(ConsistencyPanel)]
+pub fn consistency_panel(props: &Props) -> Html {
+    let language = use_context::<UseStateHandle<Language>>().map_or_else(Language::default, |l| *l);
+    let repositories = use_context::<Repositories>().unwrap_or_default();
+    let result = use_state(|| None::<Result<(Consistency, Coaching), String>>);
+
+    let Some(target_lap_id) = props.target_lap_id else {
+        return html! {};
+    };
+
+    let onclick = {
+        let result = result.clone();
+        let analysis_id = props.analysis_id;
+        Callback::from(move |_| {
+            let result = result.clone();
+            let repositories = repositories.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let measured = measure(&repositories, analysis_id, target_lap_id, language).await;
+                result.set(Some(measured));
+            });
+        })
+    };
+
+    html! {
+        <div class="mt-4 ml-4">
+            <div class="is-size-3 has-text-centered">
+                { Message::ConsistencyTitle.text(language) }
+            </div>
+            {
+                match &*result {
+                    None => html! {
+                        <div class="has-text-centered">
+                            <button class="button is-link is-rounded" {onclick}>
+                                { Message::MeasureConsistency.format(language, &[("driver", &props.driver)]) }
+                            </button>
+                        </div>
+                    },
+                    Some(Err(e)) => html! {
+                        <div class="notification is-danger">{ e }</div>
+                    },
+                    Some(Ok((consistency, coaching))) => view_consistency(consistency, coaching, language),
+                }
+            }
+        </div>
+    }
+}
+
+/// Measures the consistency over the session of the target lap and focuses the coaching of the
+/// analysis on it.
+async fn measure(
+    repositories: &Repositories,
+    analysis_id: Uuid,
+    target_lap_id: Uuid,
+    language: Language,
+) -> Result<(Consistency, Coaching), String> {
+    let lap = repositories
+        .lap
+        .find_by_id(&target_lap_id)
+        .await?
+        .ok_or(format!("Target lap `{target_lap_id}` not found"))?;
+    let scope = Scope::Session {
+        circuit: lap.header.circuit,
+        date: lap.header.date,
+    };
+    let request = Request::new(scope, lap.header.driver);
+
+    repositories.consistency.create(&request).await?;
+    let consistency = repositories
+        .consistency
+        .find_by_id(&request.id)
+        .await?
+        .ok_or(format!("Consistency `{}` not found", request.id))?;
+    let coaching = repositories
+        .consistency
+        .focus(&request.id, &analysis_id, language)
+        .await?;
+
+    Ok((consistency, coaching))
+}
+
+fn view_consistency(consistency: &Consistency, coaching: &Coaching, language: Language) -> Html {
+    let sections: Vec<String> = consistency
+        .least_consistent()
+        .map(|section| {
+            Message::LeastConsistent.format(
+                language,
+                &[
+                    ("segment", &section.segment.name()),
+                    ("spread", &format!("{:.1}", section.spread)),
+                    ("time", &format!("{:.2}", section.time_std_dev)),
+                ],
+            )
+        })
+        .collect();
+
+    html! {
+        <div class="console">
+            if sections.is_empty() {
+                <pre class="stay">{ Message::Consistent.text(language) }</pre>
+            }
+            { for sections.iter().map(|section| html! { <pre class="stay_to_reduce">{ section }</pre> }) }
+            { for coaching.iter().filter(|advice| advice.inconsistent).map(|advice| html! {
+                <pre class="reduce">{ &advice.message }</pre>
+            }) }
+        </div>
+    }
+}
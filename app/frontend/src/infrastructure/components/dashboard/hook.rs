@@ -1,5 +1,6 @@
 use crate::infrastructure::repository::analysis::http::Http as AnalysisHttpRepository;
 
+use shared::analysis::domain::analysis::catalogue::Language;
 use shared::analysis::domain::analysis::Analysis;
 
 use log::{error, info};
@@ -10,14 +11,18 @@ use yew::prelude::*;
 use yew::suspense::{Suspension, SuspensionResult};
 
 #[hook]
-pub fn use_analyses(id: &Uuid, repo: AnalysisHttpRepository) -> SuspensionResult<Option<Analysis>> {
+pub fn use_analyses(
+    id: &Uuid,
+    language: Language,
+    repo: AnalysisHttpRepository,
+) -> SuspensionResult<Option<Analysis>> {
     let result_handle = use_state(|| None);
     let result = (*result_handle).clone();
 
     let suspension_hanlde = use_state(|| {
         let id = id.to_owned();
         Suspension::from_future(async move {
-            match repo.find_by_id(&id).await {
+            match repo.find_by_id(&id, language).await {
                 Ok(Some(found_analysis)) => result_handle.set(Some(found_analysis)),
                 Ok(None) => {
                     error!("No analysis found");
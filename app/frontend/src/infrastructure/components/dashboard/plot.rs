@@ -1,11 +1,14 @@
+use crate::infrastructure::components::dashboard::circuit::event_color;
+
 use shared::analysis::domain::analysis::reference_lap::ReferenceLap;
 use shared::analysis::domain::analysis::Analysis;
+use shared::lap::domain::lap::events::Event;
 use shared::lap::domain::lap::variables::Variables;
 
 use plotly::color::{Color, NamedColor, Rgb};
-use plotly::common::{AxisSide, Font, Label, Line};
-use plotly::layout::Margin;
+use plotly::common::{AxisSide, DashType, Font, Label, Line, Marker, Mode, Title};
 use plotly::layout::{Axis, HoverMode};
+use plotly::layout::{Margin, Shape, ShapeLine, ShapeType};
 use plotly::{Layout, Plot, Scatter};
 use serde::Serialize;
 use std::fmt::{Display, Formatter};
@@ -46,7 +49,10 @@ pub fn create(plot_type: &Type, analysis: &Analysis) -> Result<Plot, String> {
     let distances = analysis.union_distances.clone();
 
     let mut plot = Plot::new();
-    let layout = base_layout();
+    let mut layout = base_layout();
+    for event in &target.events {
+        layout.add_shape(event_marker(event));
+    }
     traces(
         &mut plot,
         plot_type,
@@ -59,6 +65,55 @@ pub fn create(plot_type: &Type, analysis: &Analysis) -> Result<Plot, String> {
     Ok(plot)
 }
 
+/// Id of the div where the G-G diagram is drawn.
+pub const GG_PLOT_ID: &str = "gg_plot";
+
+/// G-G diagram of both laps: lateral against longitudinal acceleration at every sample.
+pub fn create_gg(analysis: &Analysis) -> Result<Plot, String> {
+    let grip = analysis
+        .grip
+        .as_ref()
+        .ok_or_else(|| "No grip found".to_string())?;
+
+    let mut plot = Plot::new();
+    for (name, utilisation, color) in [
+        ("reference", &grip.reference, NamedColor::Red),
+        ("target", &grip.target, NamedColor::Green),
+    ] {
+        let (lat, long): (Vec<f32>, Vec<f32>) = utilisation
+            .scatter
+            .iter()
+            .map(|point| (point.lat, point.long))
+            .unzip();
+        plot.add_trace(
+            Scatter::new(lat, long)
+                .name(name)
+                .mode(Mode::Markers)
+                .marker(Marker::new().size(2).color(color))
+                .web_gl_mode(true)
+                .show_legend(false)
+                .hover_template("%{x:.1f}, %{y:.1f} m/s²"),
+        );
+    }
+    plot.set_layout(
+        base_layout()
+            .x_axis(
+                Axis::new()
+                    .title(Title::new("lateral (m/s²)"))
+                    .show_spikes(false),
+            )
+            .y_axis(
+                Axis::new()
+                    .title(Title::new("longitudinal (m/s²)"))
+                    .show_spikes(false),
+            )
+            .hover_mode(HoverMode::Closest)
+            .height(300)
+            .margin(Margin::new().top(10).bottom(40).left(50).right(10)),
+    );
+    Ok(plot)
+}
+
 fn base_layout() -> Layout {
     let bulma_background = Rgb::new(20, 22, 26);
     Layout::new()
@@ -94,6 +149,24 @@ fn base_layout() -> Layout {
         .margin(Margin::new().top(10).bottom(10).left(10).right(10))
 }
 
+/// Vertical line marking an event of the target lap across the whole plot.
+fn event_marker(event: &Event) -> Shape {
+    Shape::new()
+        .shape_type(ShapeType::Line)
+        .x_ref("x")
+        .y_ref("paper")
+        .x0(event.distance)
+        .x1(event.distance)
+        .y0(0)
+        .y1(1)
+        .line(
+            ShapeLine::new()
+                .color(event_color(event.kind))
+                .width(1.0)
+                .dash(DashType::Dot),
+        )
+}
+
 fn select_metrics(
     plot_type: &Type,
     reference: &ReferenceLap,
@@ -1,6 +1,10 @@
 use crate::utils::f64_as_usize;
 
+use shared::analysis::domain::analysis::catalogue::{Language, Message};
+use shared::analysis::domain::analysis::channel::Channel;
 use shared::analysis::domain::analysis::clusters_memberships::ClustersMemberships;
+use shared::analysis::domain::analysis::coaching::{describe_tag, Coaching};
+use shared::analysis::domain::analysis::gear_shifts::ShiftSuggestion;
 use shared::analysis::domain::analysis::tag::Base;
 use shared::analysis::domain::analysis::tag::Tag;
 
@@ -14,14 +18,18 @@ pub const UPDATE_SUGGESTION_EVENT: &str = "update_suggestion_event";
 #[derive(Properties, PartialEq)]
 pub struct Props {
     pub memberships: ClustersMemberships,
+    pub coaching: Coaching,
+    /// Early and late gear changes of the target lap.
+    pub shift_suggestions: Vec<ShiftSuggestion>,
 }
 
 #[function_component(Suggestions)]
 pub fn suggestions(props: &Props) -> Html {
+    let language = use_context::<UseStateHandle<Language>>().map_or_else(Language::default, |l| *l);
+
     let suggestion_html = use_state(|| {
-        let mut suggestions = vec![
-            html! { <pre class="stay">{ "To start receiving suggestions, select a point on the map or hover over a graph." }</pre> },
-        ];
+        let mut suggestions =
+            vec![html! { <pre class="stay">{ Message::SuggestionsHint.text(language) }</pre> }];
         suggestions.extend((0..4).map(|_| html! { <pre class="stay">{ " " }</pre> }));
         suggestions
     });
@@ -35,7 +43,7 @@ pub fn suggestions(props: &Props) -> Html {
                 if let Some(index) = event.detail().as_f64() {
                     let index = f64_as_usize(index);
 
-                    let suggestions = generate_suggestion(index, &memberships);
+                    let suggestions = generate_suggestion(index, &memberships, language);
                     suggestion_html.set(suggestions);
                 }
             });
@@ -56,134 +64,67 @@ pub fn suggestions(props: &Props) -> Html {
     html! {
         <div class="mt-4 ml-4">
             <div class="is-size-3 has-text-centered	">
-                {"Suggestions"}
+                { Message::SuggestionsTitle.text(language) }
             </div>
             // Unix-style console for messages
             <div class="console">
                 { for (*suggestion_html).clone() }
             </div>
+            if !props.coaching.is_empty() {
+                <div class="is-size-3 has-text-centered mt-4">
+                    { Message::CoachingTitle.text(language) }
+                </div>
+                <div class="console">
+                    { for props.coaching.iter().map(|advice| html! {
+                        <pre class="reduce">{ &advice.message }</pre>
+                    }) }
+                </div>
+            }
+            if !props.shift_suggestions.is_empty() {
+                <div class="is-size-3 has-text-centered mt-4">
+                    { Message::GearShiftsTitle.text(language) }
+                </div>
+                <div class="console">
+                    { for props.shift_suggestions.iter().map(|suggestion| html! {
+                        <pre class="reduce">{ &suggestion.message }</pre>
+                    }) }
+                </div>
+            }
         </div>
     }
 }
-fn generate_suggestion(index: usize, memberships: &ClustersMemberships) -> Vec<Html> {
-    let mut suggestions = Vec::new();
-
-    if let Some(tag) = memberships.speed_tags.get(index) {
-        let (message, css_class) = interpret_tag("speed", *tag);
-        suggestions.push(html! {
-            <pre class={css_class}>{ message }</pre>
-        });
-    }
-
-    if let Some(tag) = memberships.throttle_tags.get(index) {
-        let (message, css_class) = interpret_tag("throttle", *tag);
-        suggestions.push(html! {
-            <pre class={css_class}>{ message }</pre>
-        });
-    }
-
-    if let Some(tag) = memberships.brake_tags.get(index) {
-        let (message, css_class) = interpret_tag("brake", *tag);
-        suggestions.push(html! {
-            <pre class={css_class}>{ message }</pre>
-        });
-    }
-
-    if let Some(tag) = memberships.gear_tags.get(index) {
-        let (message, css_class) = interpret_tag("gear", *tag);
-        suggestions.push(html! {
-            <pre class={css_class}>{ message }</pre>
-        });
-    }
-
-    if let Some(tag) = memberships.steering_wheel_angle_tags.get(index) {
-        let (message, css_class) = interpret_tag("steering wheel angle", *tag);
-        suggestions.push(html! {
-            <pre class={css_class}>{ message }</pre>
-        });
-    }
-
-    suggestions
+fn generate_suggestion(
+    index: usize,
+    memberships: &ClustersMemberships,
+    language: Language,
+) -> Vec<Html> {
+    Channel::ALL
+        .into_iter()
+        .filter_map(|channel| {
+            let tag = memberships.tags(channel).get(index)?;
+            let (message, css_class) = interpret_tag(channel, *tag, language);
+            Some(html! {
+                <pre class={css_class}>{ message }</pre>
+            })
+        })
+        .collect()
 }
 
-fn interpret_tag(variable: &str, tag: Tag) -> (String, String) {
-    match tag {
-        Tag::Single(base) => match base {
-            Base::Stay => (
-                format!("You can maintain the current {variable}."),
-                "stay".to_string(),
-            ),
-            Base::Increase(_) => (
-                format!("You should increase the {variable}."),
-                "increase".to_string(),
-            ),
-            Base::Reduce(_) => (
-                format!("You should reduce the {variable}."),
-                "reduce".to_string(),
-            ),
+fn interpret_tag(channel: Channel, tag: Tag, language: Language) -> (String, String) {
+    let message = describe_tag(channel, tag, language);
+
+    let css_class = match tag {
+        Tag::Single(Base::Stay) => "stay",
+        Tag::Single(Base::Increase(_)) => "increase",
+        Tag::Single(Base::Reduce(_)) => "reduce",
+        Tag::Tendency(base1, base2) => match (base1, base2) {
+            (Base::Stay, Base::Increase(_)) | (Base::Increase(_), Base::Stay) => "stay_to_increase",
+            (Base::Stay, Base::Reduce(_)) | (Base::Reduce(_), Base::Stay) => "stay_to_reduce",
+            (Base::Increase(_), Base::Increase(_)) => "increase",
+            (Base::Reduce(_), Base::Reduce(_)) => "reduce",
+            _ => "stay",
         },
-        Tag::Tendency(base1, base2) => {
-            let action1 = match base1 {
-                Base::Stay => format!("maintain the current {variable}"),
-                Base::Increase(level) => format!(
-                    "increase the {}{}",
-                    variable,
-                    match level {
-                        0 => "",
-                        1 => " significantly",
-                        2 => " greatly",
-                        _ => " a lot",
-                    }
-                ),
-                Base::Reduce(level) => format!(
-                    "reduce the {}{}",
-                    variable,
-                    match level {
-                        0 => "",
-                        1 => " significantly",
-                        2 => " greatly",
-                        _ => " a lot",
-                    }
-                ),
-            };
-
-            let action2 = match base2 {
-                Base::Stay => "maintain it",
-                Base::Increase(level) => &format!(
-                    "start increasing it{}",
-                    match level {
-                        0 => "",
-                        1 => " significantly",
-                        2 => " greatly",
-                        _ => " a lot",
-                    }
-                ),
-                Base::Reduce(level) => &format!(
-                    "start reducing it{}",
-                    match level {
-                        0 => "",
-                        1 => " significantly",
-                        2 => " greatly",
-                        _ => " a lot",
-                    }
-                ),
-            };
+    };
 
-            let message = format!("You should {action1}, but it would be good to {action2}.");
-
-            let css_class = match (base1, base2) {
-                (Base::Stay, Base::Increase(_)) | (Base::Increase(_), Base::Stay) => {
-                    "stay_to_increase".to_string()
-                }
-                (Base::Stay, Base::Reduce(_)) | (Base::Reduce(_), Base::Stay) => {
-                    "stay_to_reduce".to_string()
-                }
-                (Base::Increase(_), Base::Increase(_)) => "increase".to_string(),
-                (Base::Reduce(_), Base::Reduce(_)) => "reduce".to_string(),
-                _ => "stay".to_string(),
-            };
-
-            (message, css_class)
-        }
-    }
+    (message, css_class.to_string())
 }
@@ -1,12 +1,27 @@
 use crate::infrastructure::components::routes::Route;
 
-use yew::function_component;
-use yew::html;
-use yew::Html;
+use shared::analysis::domain::analysis::catalogue::Language;
+
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
 use yew_router::prelude::*;
 
 #[function_component(NavBar)]
 pub fn header() -> Html {
+    let language_ctx = use_context::<UseStateHandle<Language>>();
+    let language = language_ctx
+        .as_ref()
+        .map_or_else(Language::default, |l| **l);
+
+    let on_language_change = Callback::from(move |e: Event| {
+        let select: HtmlSelectElement = e.target_unchecked_into();
+        if let (Some(language_ctx), Some(selected)) =
+            (&language_ctx, Language::from_code(&select.value()))
+        {
+            language_ctx.set(selected);
+        }
+    });
+
     html! {
     <nav class="navbar" role="navigation" aria-label="main navigation">
         <input id="menu-switch" name="menu-switch" type="checkbox"/>
@@ -41,6 +56,15 @@ pub fn header() -> Html {
             </div>
 
             <div class="navbar-end">
+                <div class="navbar-item">
+                    <div class="select">
+                        <select onchange={on_language_change}>
+                            { for Language::ALL.into_iter().map(|l| html! {
+                                <option value={l.code()} selected={l == language}>{ l.name() }</option>
+                            }) }
+                        </select>
+                    </div>
+                </div>
                 <div class="navbar-item">
                     <div class="buttons">
                         //<a class="button is-primary">
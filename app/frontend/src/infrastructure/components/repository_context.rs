@@ -1,4 +1,5 @@
 use crate::infrastructure::repository::analysis::http::Http as AnalysisRepository;
+use crate::infrastructure::repository::consistency::http::Http as ConsistencyRepository;
 use crate::infrastructure::repository::file::http::Http as FileRepository;
 use crate::infrastructure::repository::ibt::http::Http as IbtRepository;
 use crate::infrastructure::repository::lap::http::Http as LapRepository;
@@ -7,6 +8,7 @@ use crate::infrastructure::settings::Settings;
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Repositories {
     pub analysis: AnalysisRepository,
+    pub consistency: ConsistencyRepository,
     pub file: FileRepository,
     pub ibt: IbtRepository,
     pub lap: LapRepository,
@@ -17,6 +19,7 @@ impl Default for Repositories {
         let settings = Settings::default();
         Self {
             analysis: AnalysisRepository::new(&settings),
+            consistency: ConsistencyRepository::new(&settings),
             file: FileRepository::new(&settings),
             ibt: IbtRepository::new(&settings),
             lap: LapRepository::new(&settings),
@@ -29,6 +32,7 @@ impl Repositories {
     pub fn new(settings: &Settings) -> Self {
         Self {
             analysis: AnalysisRepository::new(settings),
+            consistency: ConsistencyRepository::new(settings),
             file: FileRepository::new(settings),
             ibt: IbtRepository::new(settings),
             lap: LapRepository::new(settings),
@@ -1,6 +1,7 @@
 use crate::infrastructure::settings::Settings;
 
 use shared::analysis::domain::analyses::Analyses;
+use shared::analysis::domain::analysis::catalogue::Language;
 use shared::analysis::domain::analysis::headers::Headers;
 use shared::analysis::domain::analysis::Analysis;
 use shared::common::domain::criteria::Criteria;
@@ -14,12 +15,13 @@ use uuid::Uuid;
 
 /// Struct representing the HTTP client for performing various analysis-related operations.
 ///
-/// This struct holds the endpoints for creating, deleting, and retrieving analyses,
+/// This struct holds the endpoints for creating, deleting, cancelling and retrieving analyses,
 /// as well as retrieving headers and searching based on criteria.
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct Http {
     pub create: String,
     pub delete: String,
+    pub cancel: String,
     pub find_by_id: String,
     pub find_by_criteria: String,
     pub find_header_by_criteria: String,
@@ -49,6 +51,10 @@ impl Http {
                 "{}{}",
                 settings.endpoints.analysis.server, settings.endpoints.analysis.delete
             ),
+            cancel: format!(
+                "{}{}",
+                settings.endpoints.analysis.server, settings.endpoints.analysis.cancel
+            ),
             find_by_id: format!(
                 "{}{}",
                 settings.endpoints.analysis.server, settings.endpoints.analysis.find_by_id
@@ -173,6 +179,40 @@ impl Http {
         }
     }
 
+    /// Cancels a queued or running analysis by its ID.
+    ///
+    /// This function sends a PUT request to stop an analysis based on its ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - A reference to a `Uuid` representing the ID of the analysis to be cancelled.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the analysis was cancelled.
+    /// * `Err(String)` - If the cancellation failed, an error message with the response status is returned.
+    ///
+    /// # Errors
+    ///
+    /// This function can return various types of errors:
+    ///
+    /// * If the HTTP request fails, it returns an error with the failure message.
+    /// * If the server responds with a non-success status, the function returns the response status.
+    pub async fn cancel(&self, id: &Uuid) -> Result<(), String> {
+        let endpoint = format!("{}/{id}", self.cancel);
+        let response = Client::new()
+            .put(&endpoint)
+            .send()
+            .await
+            .map_err(|e| format!("{e}"))?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(response.status().to_string())
+        }
+    }
+
     /// Finds an analysis by its ID.
     ///
     /// This function sends a GET request to retrieve an analysis based on its ID.
@@ -180,6 +220,7 @@ impl Http {
     /// # Arguments
     ///
     /// * `id` - A reference to a `Uuid` representing the ID of the analysis.
+    /// * `language` - Language of the coaching messages.
     ///
     /// # Returns
     ///
@@ -193,8 +234,12 @@ impl Http {
     ///
     /// * If the HTTP request fails, it returns an error with the failure message.
     /// * If the server responds with a non-success status, the function returns the response status.
-    pub async fn find_by_id(&self, id: &Uuid) -> Result<Option<Analysis>, String> {
-        let endpoint = format!("{}/{id}", self.find_by_id);
+    pub async fn find_by_id(
+        &self,
+        id: &Uuid,
+        language: Language,
+    ) -> Result<Option<Analysis>, String> {
+        let endpoint = format!("{}/{id}?language={}", self.find_by_id, language.code());
         let response = Client::new()
             .get(&endpoint)
             .send()
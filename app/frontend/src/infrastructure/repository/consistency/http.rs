@@ -0,0 +1,159 @@
+use crate::infrastructure::settings::Settings;
+
+use shared::analysis::domain::analysis::catalogue::Language;
+use shared::analysis::domain::analysis::coaching::Coaching;
+use shared::consistency::domain::consistency::scope::Scope;
+use shared::consistency::domain::consistency::Consistency;
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Struct representing the HTTP client for the consistency of the drivers over a stint.
+///
+/// This struct holds the endpoints for measuring and retrieving consistencies, and for
+/// focusing the coaching of an analysis on the least consistent sections.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct Http {
+    pub create: String,
+    pub find_by_id: String,
+    pub focus: String,
+}
+
+impl Http {
+    /// Creates a new `Http` instance using the provided settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `settings` - A reference to `Settings` containing the server and endpoint configuration.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `Http` instance with the respective endpoints initialized.
+    #[must_use]
+    pub fn new(settings: &Settings) -> Self {
+        let endpoints = &settings.endpoints.consistency;
+        Self {
+            create: format!("{}{}", endpoints.server, endpoints.create),
+            find_by_id: format!("{}{}", endpoints.server, endpoints.find_by_id),
+            focus: format!("{}{}", endpoints.server, endpoints.focus),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Request {
+    pub id: Uuid,
+    pub scope: Scope,
+    pub driver: String,
+}
+
+impl Request {
+    /// Creates a new `Request` to measure the consistency of `driver` over `scope`, with a newly
+    /// generated `Uuid`.
+    #[must_use]
+    pub fn new(scope: Scope, driver: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            scope,
+            driver,
+        }
+    }
+}
+
+impl Http {
+    /// Measures the consistency of a driver on the server.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - A `Request` with the laps and the driver to be compared.
+    ///
+    /// # Errors
+    ///
+    /// * If the HTTP request fails, it returns an error with the failure message.
+    /// * If the server responds with a non-success status, the function returns the response
+    ///   body, which explains why the consistency could not be measured.
+    pub async fn create(&self, request: &Request) -> Result<(), String> {
+        let response = Client::new()
+            .put(&self.create)
+            .json(request)
+            .send()
+            .await
+            .map_err(|e| format!("{e}"))?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Cannot measure the consistency".to_string()))
+        }
+    }
+
+    /// Finds a consistency by its ID.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Consistency))` - If the consistency was found.
+    /// * `Ok(None)` - If the consistency was not found (404 status).
+    ///
+    /// # Errors
+    ///
+    /// * If the HTTP request fails, it returns an error with the failure message.
+    /// * If the server responds with a non-success status, the function returns the response status.
+    pub async fn find_by_id(&self, id: &Uuid) -> Result<Option<Consistency>, String> {
+        let endpoint = format!("{}/{id}", self.find_by_id);
+        let response = Client::new()
+            .get(&endpoint)
+            .send()
+            .await
+            .map_err(|e| format!("{e}"))?;
+
+        if response.status().is_success() {
+            let consistency = response.json().await.map_err(|e| format!("{e}"))?;
+            Ok(Some(consistency))
+        } else if response.status() == 404 {
+            Ok(None)
+        } else {
+            Err(response.status().to_string())
+        }
+    }
+
+    /// Finds the coaching of an analysis, with the advice on the sections where the driver is
+    /// least consistent first.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the consistency.
+    /// * `analysis_id` - The ID of the analysis.
+    /// * `language` - Language of the coaching messages.
+    ///
+    /// # Errors
+    ///
+    /// * If the HTTP request fails, it returns an error with the failure message.
+    /// * If the server responds with a non-success status, the function returns the response status.
+    pub async fn focus(
+        &self,
+        id: &Uuid,
+        analysis_id: &Uuid,
+        language: Language,
+    ) -> Result<Coaching, String> {
+        let endpoint = format!(
+            "{}/{id}/{analysis_id}?language={}",
+            self.focus,
+            language.code()
+        );
+        let response = Client::new()
+            .get(&endpoint)
+            .send()
+            .await
+            .map_err(|e| format!("{e}"))?;
+
+        if response.status().is_success() {
+            response.json().await.map_err(|e| format!("{e}"))
+        } else {
+            Err(response.status().to_string())
+        }
+    }
+}
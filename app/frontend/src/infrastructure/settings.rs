@@ -11,6 +11,7 @@ pub struct Settings {
 #[derive(Debug, Deserialize)]
 pub struct Endpoints {
     pub analysis: Analysis,
+    pub consistency: Consistency,
     pub file: File,
     pub lap: Lap,
     pub ibt_extractor: IbtExtractor,
@@ -21,11 +22,20 @@ pub struct Analysis {
     pub server: String,
     pub create: String,
     pub delete: String,
+    pub cancel: String,
     pub find_by_id: String,
     pub find_by_criteria: String,
     pub find_header_by_criteria: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct Consistency {
+    pub server: String,
+    pub create: String,
+    pub find_by_id: String,
+    pub focus: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct File {
     pub server: String,
@@ -58,10 +68,17 @@ impl Default for Settings {
                     server: "http://localhost:16666".to_string(),
                     create: "/analysis/create".to_string(),
                     delete: "/analysis/delete".to_string(),
+                    cancel: "/analysis/cancel".to_string(),
                     find_by_id: "/analysis/find".to_string(),
                     find_by_criteria: "/analysis/find".to_string(),
                     find_header_by_criteria: "/analysis/find/header".to_string(),
                 },
+                consistency: Consistency {
+                    server: "http://localhost:16666".to_string(),
+                    create: "/consistency/create".to_string(),
+                    find_by_id: "/consistency/find".to_string(),
+                    focus: "/consistency/focus".to_string(),
+                },
                 file: File {
                     server: "http://localhost:16666".to_string(),
                     delete: "/file/delete".to_string(),
@@ -16,6 +16,9 @@ pub mod infrastructure {
         pub mod analysis {
             pub mod http;
         }
+        pub mod consistency {
+            pub mod http;
+        }
         pub mod file {
             pub mod http;
         }
@@ -1,82 +1,223 @@
-use crate::analysis::domain::analysis::status::Status;
+use crate::analysis::domain::analysis::header::Header;
+use crate::analysis::domain::analysis::segments::Segments;
+use crate::analysis::domain::analysis::status::{Stage, Status};
 use crate::analysis::domain::repository::Repository;
+use crate::common::domain::criteria::filter::condition::Condition;
+use crate::common::domain::criteria::filter::field::Field;
+use crate::common::domain::criteria::filter::value::Value;
+use crate::common::domain::criteria::filter::Filter;
+use crate::common::domain::criteria::filters::Filters;
+use crate::common::domain::criteria::Criteria;
+use crate::common::domain::executor::Executor;
+use crate::lap::domain::lap::Lap;
+use crate::lap::domain::laps::Laps;
 use crate::lap::domain::repository::Repository as LapRepository;
 
 use crate::analysis::domain::analysis::fcm_grid::Config;
+use chrono::Utc;
 use std::sync::Arc;
 use uuid::Uuid;
 
 /// A struct responsible for asynchronously perform an analysis.
-pub struct Analyzer<R: Repository, LR: LapRepository> {
+pub struct Analyzer<R: Repository, LR: LapRepository, E: Executor> {
     repository: Arc<R>,
     lap_repository: Arc<LR>,
+    executor: Arc<E>,
 }
 
-impl<R: Repository, LR: LapRepository> Analyzer<R, LR> {
+impl<R: Repository, LR: LapRepository, E: Executor> Analyzer<R, LR, E> {
     /// Creates a new `Analyzer` instance.
     ///
     /// # Parameters
     ///
     /// - `repository`: An asynchronous repository for analysis operations.
     /// - `lap_repository`: An asynchronous repository for lap operations.
+    /// - `executor`: Executor of the CPU bound stages of the analyses.
     ///
     /// # Returns
     ///
     /// A new `Analyzer` instance.
-    pub fn new(repository: Arc<R>, lap_repository: Arc<LR>) -> Self {
+    pub fn new(repository: Arc<R>, lap_repository: Arc<LR>, executor: Arc<E>) -> Self {
         Self {
             repository,
             lap_repository,
+            executor,
         }
     }
 
-    /// Asynchronously creates analysis data using the repositories.
+    /// Asynchronously runs every stage of an analysis, persisting its progress.
+    ///
+    /// Before each stage the analysis is marked as `Status::Running`, so clients can follow it.
+    /// Every status change, and the final results, are only stored while the stored status is
+    /// still the one last written here: if the analysis has been cancelled meanwhile, it is left
+    /// as it is and no further stage is run. Only the stages themselves are run on the executor,
+    /// the repositories are accessed from the calling task.
+    ///
+    /// Corners are numbered once per circuit: the segments of the earliest analysis of the same
+    /// circuit are reused, and only detected on the reference lap for the first one.
     ///
     /// # Parameters
     ///
     /// - `id`: The UUID identifier for the analysis.
-    /// - `name`: The name of the analysis.
-    /// - `ref_lap_id`: The UUID identifier of the reference lap.
-    /// - `target_lap_id`: The UUID identifier of the target lap.
+    /// - `fcm_grid_config`: Configuration of the clustering, replaced by the overrides of the
+    ///   analysis if it is run again.
     ///
     /// # Errors
     ///
-    /// Returns an `Err` if either the reference lap or target lap is not found in the repository,
-    /// or if the repository fails during the creation process.
+    /// Returns an `Err` if the analysis is not queued, if either the reference lap or target
+    /// lap is not found in the repository, if a stage fails (the analysis is then stored as
+    /// `Status::Failed` on that stage), if its status is changed meanwhile other than by a
+    /// cancellation or if the repository fails.
     ///
     /// # Returns
     ///
-    /// Returns `Ok` if the analysis data was successfully created and stored in the repository.
+    /// Returns `Ok` if the analysis was completed or cancelled.
     pub async fn analyze(&self, id: Uuid, fcm_grid_config: &Config) -> Result<(), String> {
         let mut analysis = self.repository.find_by_id(&id).await?.ok_or(format!(
             "Cannot found analysis with id `{id}` to perform an analysis"
         ))?;
 
-        if let Status::Pending { ref_id, target_id } = analysis.header.status {
-            let ref_lap = self
-                .lap_repository
-                .find_by_id(&ref_id)
-                .await?
-                .ok_or(format!("Reference Lap with id {ref_id} not found"))?;
+        let Status::Queued {
+            ref_id, target_id, ..
+        } = analysis.header.status
+        else {
+            return Err(format!(
+                "Analysis `{}` ({}) cannot be analyzed in `{:?}` status",
+                analysis.header.name, analysis.header.id, analysis.header.status
+            ));
+        };
+
+        let ref_lap = self
+            .lap_repository
+            .find_by_id(&ref_id)
+            .await?
+            .ok_or(format!("Reference Lap with id {ref_id} not found"))?;
+
+        let target_lap = self
+            .lap_repository
+            .find_by_id(&target_id)
+            .await?
+            .ok_or(format!("Target Lap with id {target_id} not found"))?;
+
+        // Analyses run again may replace some of the configured parameters
+        let fcm_grid_config = Arc::new(analysis.overrides.as_ref().map_or_else(
+            || fcm_grid_config.clone(),
+            |overrides| overrides.apply(fcm_grid_config.clone()),
+        ));
+        let driver_laps = Arc::new(self.driver_laps(&target_lap).await?);
+        let (ref_lap, target_lap) = (Arc::new(ref_lap), Arc::new(target_lap));
+
+        if analysis.segments.is_none() {
+            analysis.segments = self.circuit_segments(&analysis.header).await?;
+        }
+
+        // Status last stored by this analyzer, the analysis is only written while it is kept
+        let mut stored = analysis.header.status.clone();
 
-            let target_lap = self
-                .lap_repository
-                .find_by_id(&target_id)
+        for stage in Stage::ALL {
+            let running = Status::Running {
+                stage,
+                percent: stage.percent(),
+                at: Utc::now(),
+            };
+            if !self
+                .repository
+                .update_status(&id, &stored, &running)
                 .await?
-                .ok_or(format!("Target Lap with id {target_id} not found"))?;
+            {
+                return self.stopped(&id).await;
+            }
+            analysis.header.status = running.clone();
+            stored = running;
 
-            analysis
-                .analyze(ref_lap, target_lap, fcm_grid_config)
-                .map_err(|e| format!("{e}"))?;
+            let (ref_lap, target_lap, driver_laps, fcm_grid_config) = (
+                Arc::clone(&ref_lap),
+                Arc::clone(&target_lap),
+                Arc::clone(&driver_laps),
+                Arc::clone(&fcm_grid_config),
+            );
+            let (stage_analysis, result) = self
+                .executor
+                .run(
+                    &format!("Stage {stage:?} of analysis `{id}`"),
+                    Box::new(move || {
+                        let result = analysis.run_stage(
+                            stage,
+                            &ref_lap,
+                            &target_lap,
+                            &driver_laps,
+                            &fcm_grid_config,
+                        );
+                        (analysis, result)
+                    }),
+                )
+                .await?;
+            analysis = stage_analysis;
 
-            self.repository.update(&analysis).await
-        } else {
-            Err(format!(
-                "Analysis `{}` ({}) cannot be analyzed in `{:?}` status",
-                analysis.header.name, analysis.header.id, analysis.header.status
-            ))
+            if let Err(e) = result {
+                analysis.fail(Some(stage), format!("{e}"));
+                let status = &analysis.header.status;
+                if !self.repository.update_status(&id, &stored, status).await? {
+                    return self.stopped(&id).await;
+                }
+                return Err(format!("{e}"));
+            }
+        }
+
+        analysis.header.status = Status::Completed { at: Utc::now() };
+        if !self.repository.update_if_status(&analysis, &stored).await? {
+            return self.stopped(&id).await;
         }
+        Ok(())
+    }
+
+    /// Driven laps of the driver of `target_lap` with the same car on the same circuit.
+    async fn driver_laps(&self, target_lap: &Lap) -> Result<Laps, String> {
+        let header = &target_lap.header;
+        let criteria = Criteria::new(
+            Some(Filters::from(vec![
+                Filter::new(
+                    Field::new("driver"),
+                    Condition::Equal,
+                    Value::new(&header.driver),
+                ),
+                Filter::new(Field::new("car"), Condition::Equal, Value::new(&header.car)),
+                Filter::new(
+                    Field::new("circuit"),
+                    Condition::Equal,
+                    Value::new(&header.circuit),
+                ),
+            ])),
+            None,
+            None,
+            None,
+        );
 
-        // Optionally, you might want to trigger domain events here.
+        let mut laps = self
+            .lap_repository
+            .find_by_criteria(&criteria)
+            .await?
+            .unwrap_or_default();
+        laps.retain(|lap| !lap.header.synthetic);
+        Ok(laps)
+    }
+
+    /// Segments of the earliest other analysis of the circuit of `header`, if any.
+    async fn circuit_segments(&self, header: &Header) -> Result<Option<Segments>, String> {
+        self.repository
+            .find_circuit_segments(&header.circuit, &header.id)
+            .await
+    }
+
+    /// Outcome of an analysis whose status was changed by someone else while running it.
+    async fn stopped(&self, id: &Uuid) -> Result<(), String> {
+        match self.repository.find_header_by_id(id).await? {
+            Some(header) if header.status.is_cancelled() => Ok(()),
+            Some(header) => Err(format!(
+                "Status of analysis `{id}` changed to `{:?}` while analyzing it",
+                header.status
+            )),
+            None => Err(format!("Analysis `{id}` deleted while analyzing it")),
+        }
     }
 }
@@ -0,0 +1,62 @@
+use crate::analysis::domain::repository::Repository;
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// A struct responsible for asynchronously cancelling a queued or running analysis.
+pub struct Canceller<R: Repository> {
+    repository: Arc<R>,
+}
+
+impl<R: Repository> Canceller<R> {
+    /// Creates a new `Canceller` instance.
+    ///
+    /// # Parameters
+    ///
+    /// - `repository`: An asynchronous repository for analysis operations.
+    ///
+    /// # Returns
+    ///
+    /// A new `Canceller` instance.
+    pub fn new(repository: Arc<R>) -> Self {
+        Self { repository }
+    }
+
+    /// Asynchronously cancels an analysis.
+    ///
+    /// The analysis is marked as cancelled on the stage it was running, only if its status has
+    /// not changed since it was read. The analyzer notices it when storing its next status and
+    /// stops there.
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: The UUID identifier for the analysis.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the analysis is not found, if it is neither queued nor running, if
+    /// its status changed while cancelling it or if the repository fails.
+    pub async fn cancel(&self, id: Uuid) -> Result<(), String> {
+        let mut analysis = self
+            .repository
+            .find_by_id(&id)
+            .await?
+            .ok_or(format!("Cannot find analysis with id `{id}` to cancel it"))?;
+
+        let current = analysis.header.status.clone();
+        analysis.cancel().map_err(|e| format!("{e}"))?;
+
+        let cancelled = &analysis.header.status;
+        if self
+            .repository
+            .update_status(&id, &current, cancelled)
+            .await?
+        {
+            Ok(())
+        } else {
+            Err(format!(
+                "Status of analysis `{id}` changed while cancelling it, try again"
+            ))
+        }
+    }
+}
@@ -46,7 +46,8 @@ impl<R: Repository, LR: LapRepository, E: EventBus> Creator<R, LR, E> {
     /// # Errors
     ///
     /// Returns an `Err` if either the reference lap or target lap is not found in the repository,
-    /// or if the repository fails during the creation process.
+    /// if the reference lap had off-track, incident, spin or pit-road events, or if the
+    /// repository fails during the creation process.
     ///
     /// # Returns
     ///
@@ -75,6 +76,12 @@ impl<R: Repository, LR: LapRepository, E: EventBus> Creator<R, LR, E> {
             return Err("the laps in an analysis have to belong to the same circuit".to_owned());
         }
 
+        if !ref_lap.events.is_empty() {
+            return Err(format!(
+                "Reference Lap with id {ref_lap_id} is invalid: it has off-track, incident, spin or pit-road events"
+            ));
+        }
+
         let analysis = Analysis::new(
             id,
             name,
@@ -93,3 +100,108 @@ impl<R: Repository, LR: LapRepository, E: EventBus> Creator<R, LR, E> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::infrastructure::repository::in_memory::InMemory;
+    use crate::common::domain::event::Event as DomainEvent;
+    use crate::lap::domain::lap::events::{Event, EventKind};
+    use crate::lap::domain::lap::test_util;
+    use crate::lap::domain::lap::variables::Variables;
+    use crate::lap::domain::laps::Laps;
+    use crate::lap::infrastructure::repository::in_memory::InMemory as LapInMemory;
+
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Event bus counting the dispatched events.
+    #[derive(Default)]
+    struct CountingBus {
+        dispatched: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl EventBus for CountingBus {
+        async fn dispatch(&self, _event: Arc<dyn DomainEvent>) -> Result<(), String> {
+            self.dispatched.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    /// Builds a creator over a reference lap with `events` and a clean target lap.
+    async fn setup(
+        events: Vec<Event>,
+    ) -> (
+        Creator<InMemory, LapInMemory, CountingBus>,
+        Arc<InMemory>,
+        Arc<CountingBus>,
+        Uuid,
+        Uuid,
+    ) {
+        let mut ref_lap = test_util::test_lap(1, "Driver", Variables::default());
+        ref_lap.events = events;
+        let target_lap = test_util::test_lap(2, "Driver", Variables::default());
+        let (ref_lap_id, target_lap_id) = (ref_lap.header.id, target_lap.header.id);
+
+        let lap_repository = Arc::new(LapInMemory::default());
+        lap_repository
+            .create(Laps::from(vec![ref_lap, target_lap]))
+            .await
+            .unwrap();
+        let repository = Arc::new(InMemory::default());
+        let event_bus = Arc::new(CountingBus::default());
+        let creator = Creator::new(
+            Arc::clone(&repository),
+            lap_repository,
+            Arc::clone(&event_bus),
+        );
+
+        (creator, repository, event_bus, ref_lap_id, target_lap_id)
+    }
+
+    #[tokio::test]
+    async fn creates_the_analysis() {
+        let (creator, repository, event_bus, ref_lap_id, target_lap_id) = setup(Vec::new()).await;
+        let id = Uuid::new_v4();
+
+        creator
+            .create(
+                id,
+                "name".to_string(),
+                Utc::now(),
+                ref_lap_id,
+                target_lap_id,
+            )
+            .await
+            .unwrap();
+
+        assert!(repository.find_by_id(&id).await.unwrap().is_some());
+        assert_eq!(event_bus.dispatched.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn rejects_a_reference_lap_with_events() {
+        let (creator, repository, event_bus, ref_lap_id, target_lap_id) = setup(vec![Event {
+            kind: EventKind::OffTrack,
+            distance: 500.,
+            time: 10.,
+        }])
+        .await;
+        let id = Uuid::new_v4();
+
+        let result = creator
+            .create(
+                id,
+                "name".to_string(),
+                Utc::now(),
+                ref_lap_id,
+                target_lap_id,
+            )
+            .await;
+
+        assert!(result.unwrap_err().contains("is invalid"));
+        assert!(repository.find_by_id(&id).await.unwrap().is_none());
+        assert_eq!(event_bus.dispatched.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,58 @@
+use crate::analysis::domain::analysis::overrides::Overrides;
+use crate::analysis::domain::event::reanalyzed::Reanalyzed;
+use crate::analysis::domain::repository::Repository;
+use crate::common::domain::event::bus::Bus as EventBus;
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// A struct responsible for asynchronously running an existing analysis again.
+pub struct Reanalyzer<R: Repository, E: EventBus> {
+    repository: Arc<R>,
+    event_bus: Arc<E>,
+}
+
+impl<R: Repository, E: EventBus> Reanalyzer<R, E> {
+    /// Creates a new `Reanalyzer` instance.
+    ///
+    /// # Parameters
+    ///
+    /// - `repository`: An asynchronous repository for analysis operations.
+    /// - `event_bus`: The bus where the `Reanalyzed` event is dispatched.
+    ///
+    /// # Returns
+    ///
+    /// A new `Reanalyzer` instance.
+    pub fn new(repository: Arc<R>, event_bus: Arc<E>) -> Self {
+        Self {
+            repository,
+            event_bus,
+        }
+    }
+
+    /// Asynchronously requests to run an analysis again.
+    ///
+    /// The current results are kept as a previous version and the analysis goes back to the
+    /// queued status until the `Reanalyzed` event is processed.
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: The UUID identifier for the analysis.
+    /// - `overrides`: Parameters replacing the configured ones in the new run.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the analysis is not found, if it cannot be run again or if the
+    /// repository or the event bus fail.
+    pub async fn reanalyze(&self, id: Uuid, overrides: Option<Overrides>) -> Result<(), String> {
+        let mut analysis = self.repository.find_by_id(&id).await?.ok_or(format!(
+            "Cannot find analysis with id `{id}` to run it again"
+        ))?;
+
+        analysis.reanalyze(overrides).map_err(|e| format!("{e}"))?;
+
+        self.repository.update(&analysis).await?;
+        let event = Arc::new(Reanalyzed::new(&id));
+        self.event_bus.dispatch(event).await
+    }
+}
@@ -1,26 +1,69 @@
+pub mod braking_zones;
+pub mod catalogue;
+pub mod channel;
+pub mod clustering_model;
 pub mod clusters_memberships;
+pub mod coaching;
+pub mod corners;
+pub mod dbscan;
 pub mod differences;
 pub mod distances;
 pub mod fcm_grid;
 pub mod fuzzy_c_means;
+pub mod gaussian_mixture;
+pub mod gear_shifts;
+pub mod geo;
+pub mod grip;
 pub mod header;
 pub mod headers;
 pub mod interpolation;
+pub mod joint_clusters;
+pub mod k_means;
+pub mod overrides;
+pub mod racing_line;
 pub mod reference_lap;
+pub mod segment;
+pub mod segmentation;
+pub mod segments;
+pub mod smoothness;
 pub mod status;
 pub mod tag;
 pub mod tag_assigner;
+pub mod tag_policy;
 pub mod tags;
+pub mod technique;
+pub mod thresholds;
+pub mod validity;
+pub mod version;
 
+use crate::analysis::domain::analysis::braking_zones::{
+    compare as compare_braking_zones, BrakingZones,
+};
+use crate::analysis::domain::analysis::catalogue::Language;
 use crate::analysis::domain::analysis::clusters_memberships::ClustersMemberships;
+use crate::analysis::domain::analysis::coaching::{coach, Coaching};
+use crate::analysis::domain::analysis::corners::{calculate as calculate_corners, Corners};
 use crate::analysis::domain::analysis::differences::calculate as calculate_differences;
 use crate::analysis::domain::analysis::distances::generate_union as generate_union_distances;
 use crate::analysis::domain::analysis::fcm_grid::Config;
+use crate::analysis::domain::analysis::gear_shifts::{compare as compare_gear_shifts, GearShifts};
+use crate::analysis::domain::analysis::grip::{
+    compare as compare_grip, envelope_laps, GripComparison,
+};
 use crate::analysis::domain::analysis::header::Header;
 use crate::analysis::domain::analysis::interpolation::interpolate_variables;
+use crate::analysis::domain::analysis::overrides::Overrides;
+use crate::analysis::domain::analysis::racing_line::{
+    calculate as calculate_racing_line, RacingLine,
+};
 use crate::analysis::domain::analysis::reference_lap::ReferenceLap;
-use crate::analysis::domain::analysis::status::Status;
+use crate::analysis::domain::analysis::segmentation::detect as detect_segments;
+use crate::analysis::domain::analysis::segments::Segments;
+use crate::analysis::domain::analysis::smoothness::{compare as compare_smoothness, Smoothness};
+use crate::analysis::domain::analysis::status::{Stage, Status};
 use crate::analysis::domain::analysis::tags::Tags;
+use crate::analysis::domain::analysis::technique::{calculate as calculate_technique, Technique};
+use crate::analysis::domain::analysis::version::{Version, Versions};
 use crate::lap::domain::lap::variables::Variables;
 use crate::lap::domain::lap::Lap;
 
@@ -44,8 +87,55 @@ pub struct Analysis {
     /// Difference metrics: reference - target
     pub differences: Option<Variables>,
 
+    /// Corners and straights of the circuit, detected on the reference lap of the first
+    /// analysis of the circuit so every analysis numbers them the same
+    pub segments: Option<Segments>,
+
+    /// Per corner metrics of both laps
+    pub corners: Option<Corners>,
+
+    /// Braking and throttle points of both laps in every braking zone
+    pub braking_zones: Option<BrakingZones>,
+
+    /// Gear changes of both laps, with the optimal upshift RPM of every gear
+    pub gear_shifts: Option<GearShifts>,
+
+    /// Coasting, trail braking and pedal overlap of both laps, per lap and per corner
+    pub technique: Option<Technique>,
+
+    /// Smoothness scores and steering corrections of both laps, per lap and per corner
+    pub smoothness: Option<Smoothness>,
+
+    /// Lateral offset, radius and length of both racing lines, from their GPS traces
+    pub racing_line: Option<RacingLine>,
+
+    /// Grip utilisation and G-G diagram of both laps, against the envelope of the car
+    #[serde(default)]
+    pub grip: Option<GripComparison>,
+
     /// Clustering results
     pub clustering: Option<ClustersMemberships>,
+
+    /// Seed used to initialise the clustering, to reproduce it
+    pub seed: Option<u64>,
+
+    /// Advice per section of the circuit, from the most to the least time lost
+    pub coaching: Option<Coaching>,
+
+    /// Number of the current version, increased every time the analysis is run again
+    #[serde(default = "first_version")]
+    pub version: u32,
+
+    /// Parameters replaced in the current version, if any
+    pub overrides: Option<Overrides>,
+
+    /// Results of the previous versions
+    #[serde(default)]
+    pub versions: Versions,
+}
+
+const fn first_version() -> u32 {
+    1
 }
 
 impl Analysis {
@@ -64,7 +154,20 @@ impl Analysis {
             target: None,
             union_distances: vec![],
             differences: None,
+            segments: None,
+            corners: None,
+            braking_zones: None,
+            gear_shifts: None,
+            technique: None,
+            smoothness: None,
+            racing_line: None,
+            grip: None,
             clustering: None,
+            seed: None,
+            coaching: None,
+            version: first_version(),
+            overrides: None,
+            versions: Versions::default(),
         }
     }
 
@@ -82,10 +185,29 @@ impl Analysis {
             target: None,
             union_distances: vec![],
             differences: None,
+            segments: None,
+            corners: None,
+            braking_zones: None,
+            gear_shifts: None,
+            technique: None,
+            smoothness: None,
+            racing_line: None,
+            grip: None,
             clustering: None,
+            seed: None,
+            coaching: None,
+            version: first_version(),
+            overrides: None,
+            versions: Versions::default(),
         }
     }
 
+    /// Runs every stage of the analysis, in order, and marks it as completed.
+    ///
+    /// `driver_laps` are the laps of the driver of the target lap with the same car, used to
+    /// estimate the optimal upshift RPM of every gear and, the fastest valid ones, the grip
+    /// envelope of the car.
+    ///
     /// # Errors
     ///
     /// This function returns an error if:
@@ -94,6 +216,9 @@ impl Analysis {
     ///   an `Error::DifferentCircuits` is returned with the circuit information of both laps.
     /// * Any error occurs during the interpolation of metrics. This might be due to issues
     ///   with the data points not aligning properly between the reference and target laps.
+    /// * The clustering fails.
+    ///
+    /// The status is only set to `Status::Completed` once every stage has succeeded.
     ///
     /// # Note
     ///
@@ -101,50 +226,248 @@ impl Analysis {
     ///
     pub fn analyze(
         &mut self,
-        ref_lap: Lap,
-        target_lap: Lap,
+        ref_lap: &Lap,
+        target_lap: &Lap,
+        driver_laps: &[Lap],
+        fcm_grid_config: &Config,
+    ) -> Result<(), Error> {
+        for stage in Stage::ALL {
+            self.run_stage(stage, ref_lap, target_lap, driver_laps, fcm_grid_config)?;
+        }
+        self.header.status = Status::Completed { at: Utc::now() };
+
+        Ok(())
+    }
+
+    /// Runs a single stage of the analysis, storing its results.
+    ///
+    /// Every stage relies on the results of the previous ones, so they must be run in the order
+    /// of `Stage::ALL`. The status is left untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the stage, or `Error::StageOrder` if the previous stages have not
+    /// been run yet.
+    pub fn run_stage(
+        &mut self,
+        stage: Stage,
+        ref_lap: &Lap,
+        target_lap: &Lap,
+        driver_laps: &[Lap],
         fcm_grid_config: &Config,
     ) -> Result<(), Error> {
+        match stage {
+            Stage::Interpolation => self.interpolate(ref_lap, target_lap),
+            Stage::Differences => {
+                let (reference, target) = self.laps(stage)?;
+                self.differences = Some(calculate_differences(reference, target));
+                Ok(())
+            }
+            Stage::Segmentation => {
+                let (reference, target) = self.laps(stage)?;
+                // Segments already known for the circuit are kept, to keep the numbering
+                let segments = self
+                    .segments
+                    .clone()
+                    .unwrap_or_else(|| detect_segments(reference));
+                let corners = calculate_corners(&segments, reference, target);
+                let braking_zones = compare_braking_zones(&segments, reference, target);
+                let grip = compare_grip(&segments, reference, target, &envelope_laps(driver_laps));
+                let driver_laps: Vec<&Variables> =
+                    driver_laps.iter().map(|lap| &lap.variables).collect();
+                let gear_shifts = compare_gear_shifts(&segments, reference, target, &driver_laps);
+                let technique = calculate_technique(&segments, reference, target);
+                let smoothness = compare_smoothness(&segments, reference, target);
+                let racing_line = calculate_racing_line(&segments, reference, target);
+                self.segments = Some(segments);
+                self.corners = Some(corners);
+                self.braking_zones = Some(braking_zones);
+                self.gear_shifts = Some(gear_shifts);
+                self.technique = Some(technique);
+                self.smoothness = smoothness;
+                self.racing_line = racing_line;
+                self.grip = grip;
+                Ok(())
+            }
+            Stage::Clustering => {
+                let differences = self.differences.as_ref().ok_or(Error::StageOrder(stage))?;
+                // Seeded so it can be reproduced even when no seed is configured
+                let seed = fcm_grid_config.seed.unwrap_or_else(rand::random);
+                let clustering = ClustersMemberships::try_transform_and_fit(
+                    differences,
+                    &self.union_distances,
+                    &fcm_grid_config.clone().with_seed(seed),
+                )
+                .map_err(Error::Clustering)?;
+                self.clustering = Some(clustering);
+                self.seed = Some(seed);
+                Ok(())
+            }
+            Stage::Coaching => {
+                let (reference, target) = self.laps(stage)?;
+                let (Some(segments), Some(braking_zones), Some(clustering)) =
+                    (&self.segments, &self.braking_zones, &self.clustering)
+                else {
+                    return Err(Error::StageOrder(stage));
+                };
+                let coaching = coach(segments, braking_zones, reference, target, clustering);
+                self.coaching = Some(coaching);
+                Ok(())
+            }
+        }
+    }
+
+    fn interpolate(&mut self, ref_lap: &Lap, target_lap: &Lap) -> Result<(), Error> {
         if ref_lap.header.circuit != target_lap.header.circuit {
             return Err(Error::DifferentCircuits(
-                ref_lap.header.circuit,
-                target_lap.header.circuit,
+                ref_lap.header.circuit.clone(),
+                target_lap.header.circuit.clone(),
             ));
         }
 
-        let union_distances = generate_union_distances(&ref_lap, &target_lap);
+        let union_distances = generate_union_distances(ref_lap, target_lap);
         let ref_variables = interpolate_variables(&ref_lap.variables, &union_distances)?;
         let target_variables = interpolate_variables(&target_lap.variables, &union_distances)?;
-        let differences = calculate_differences(&ref_variables, &target_variables);
 
         self.reference = Some(ReferenceLap::new(
             ref_lap.header.number,
             ref_lap.header.driver.clone(),
             ref_lap.header.category.clone(),
-            ref_lap.header.car,
+            ref_lap.header.car.clone(),
             ref_variables,
+            ref_lap.wheel_slips.clone(),
+            ref_lap.events.clone(),
         ));
-
         self.target = Some(ReferenceLap::new(
             target_lap.header.number,
             target_lap.header.driver.clone(),
             target_lap.header.category.clone(),
-            target_lap.header.car,
+            target_lap.header.car.clone(),
             target_variables,
+            target_lap.wheel_slips.clone(),
+            target_lap.events.clone(),
         ));
-
         self.union_distances = union_distances;
-        self.differences = Some(differences.clone());
 
-        self.header.status = Status::Completed;
+        Ok(())
+    }
 
-        // Clustering
-        let clustering = ClustersMemberships::try_transform_and_fit(&differences, fcm_grid_config)
-            .map_err(Error::Clustering)?;
-        self.clustering = Some(clustering);
+    /// Interpolated variables of both laps, needed by every stage after the interpolation.
+    fn laps(&self, stage: Stage) -> Result<(&Variables, &Variables), Error> {
+        match (&self.reference, &self.target) {
+            (Some(reference), Some(target)) => Ok((&reference.variables, &target.variables)),
+            _ => Err(Error::StageOrder(stage)),
+        }
+    }
+
+    /// Writes the messages of the coaching of every version, and the gear shift suggestions,
+    /// in `language`.
+    pub fn localise(&mut self, language: Language) {
+        if let Some(gear_shifts) = &mut self.gear_shifts {
+            gear_shifts.localise(language);
+        }
+        let previous = self.versions.iter_mut().map(|v| &mut v.coaching);
+        for coaching in std::iter::once(&mut self.coaching)
+            .chain(previous)
+            .flatten()
+        {
+            coaching.localise(language);
+        }
+    }
+
+    /// Prepares the analysis to be run again with the given overrides.
+    ///
+    /// The results of the current version are kept as a previous version, if there are any,
+    /// and the analysis goes back to `Status::Queued` with a new version number.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Reanalysis` if the analysis is already queued or running, or if it does
+    /// not know which laps it compares.
+    pub fn reanalyze(&mut self, overrides: Option<Overrides>) -> Result<(), Error> {
+        if self.header.status.is_active() {
+            return Err(Error::Reanalysis(
+                "the analysis is already queued or running".to_string(),
+            ));
+        }
+        let (Some(ref_id), Some(target_id)) = (self.header.ref_lap_id, self.header.target_lap_id)
+        else {
+            return Err(Error::Reanalysis(
+                "the laps of the analysis are unknown".to_string(),
+            ));
+        };
+
+        if self.clustering.is_some() {
+            self.versions.push(Version {
+                number: self.version,
+                overrides: self.overrides.take(),
+                seed: self.seed.take(),
+                clustering: self.clustering.take(),
+                coaching: self.coaching.take(),
+            });
+        }
+
+        self.version += 1;
+        self.overrides = overrides;
+        self.header.status = Status::Queued {
+            ref_id,
+            target_id,
+            at: Utc::now(),
+        };
 
         Ok(())
     }
+
+    /// Marks the analysis as failed with `error_msg`, on the given stage if known.
+    ///
+    /// Unlike `with_error`, the laps and the previous versions are kept, so the analysis can be
+    /// run again.
+    pub fn fail(&mut self, stage: Option<Stage>, error_msg: String) {
+        self.header.status = Status::Failed {
+            stage,
+            reason: error_msg,
+            at: Utc::now(),
+        };
+    }
+
+    /// Cancels the analysis while it is queued or running, keeping the stage it was running.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Cancellation` if the analysis is neither queued nor running.
+    pub fn cancel(&mut self) -> Result<(), Error> {
+        if !self.header.status.is_active() {
+            return Err(Error::Cancellation(format!(
+                "the analysis is not queued nor running: {:?}",
+                self.header.status
+            )));
+        }
+
+        self.header.status = Status::Cancelled {
+            stage: self.header.status.stage(),
+            at: Utc::now(),
+        };
+
+        Ok(())
+    }
+
+    /// The analysis as it was in the given version, or `None` if there is no such version.
+    #[must_use]
+    pub fn at_version(&self, number: u32) -> Option<Self> {
+        if number == self.version {
+            return Some(self.clone());
+        }
+
+        let version = self.versions.iter().find(|v| v.number == number)?.clone();
+        Some(Self {
+            overrides: version.overrides,
+            seed: version.seed,
+            clustering: version.clustering,
+            coaching: version.coaching,
+            version: version.number,
+            ..self.clone()
+        })
+    }
 }
 
 #[derive(PartialEq, Eq, Debug, thiserror::Error)]
@@ -157,4 +480,88 @@ pub enum Error {
     FromHeader(String),
     #[error("cannot perform clusters_memberships: {0}")]
     Clustering(String),
+    #[error("cannot run the analysis again: {0}")]
+    Reanalysis(String),
+    #[error("cannot cancel the analysis: {0}")]
+    Cancellation(String),
+    #[error("cannot run the {0} stage before the previous ones")]
+    StageOrder(Stage),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lap::domain::lap::test_util;
+
+    fn completed_analysis() -> Analysis {
+        let mut analysis = Analysis::new(
+            Uuid::new_v4(),
+            "Test".to_string(),
+            Utc::now(),
+            "Spa".to_string(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        analysis.header.status = Status::Completed { at: Utc::now() };
+        analysis.clustering = Some(ClustersMemberships::default());
+        analysis.seed = Some(42);
+        analysis
+    }
+
+    #[test]
+    fn reanalyzing_keeps_previous_version() {
+        let mut analysis = completed_analysis();
+        let overrides = Overrides {
+            seed: Some(7),
+            ..Overrides::default()
+        };
+
+        analysis.reanalyze(Some(overrides.clone())).unwrap();
+
+        assert!(matches!(analysis.header.status, Status::Queued { .. }));
+        assert_eq!(analysis.version, 2);
+        assert_eq!(analysis.overrides, Some(overrides));
+        assert!(analysis.clustering.is_none());
+        assert_eq!(analysis.versions.len(), 1);
+        let previous = analysis.at_version(1).unwrap();
+        assert_eq!(previous.seed, Some(42));
+        assert!(previous.clustering.is_some());
+        assert!(analysis.at_version(3).is_none());
+        assert_eq!(
+            analysis.reanalyze(None),
+            Err(Error::Reanalysis(
+                "the analysis is already queued or running".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn stages_need_the_previous_ones() {
+        let mut analysis = completed_analysis();
+        analysis.header.status = Status::Running {
+            stage: Stage::Clustering,
+            percent: Stage::Clustering.percent(),
+            at: Utc::now(),
+        };
+        let lap = test_util::test_lap(1, "Driver", Variables::default());
+        let config = Config::new(
+            (2, None, None),
+            (2.0, None, None),
+            (10, None, None),
+            (0.01, None, None),
+        );
+
+        assert_eq!(
+            analysis.run_stage(Stage::Differences, &lap, &lap, &[], &config),
+            Err(Error::StageOrder(Stage::Differences))
+        );
+        assert!(analysis.differences.is_none());
+        assert!(analysis.reanalyze(None).is_err());
+        assert_eq!(analysis.version, 1);
+
+        analysis.cancel().unwrap();
+        assert!(analysis.header.status.is_cancelled());
+        assert_eq!(analysis.header.status.stage(), Some(Stage::Clustering));
+        assert!(analysis.cancel().is_err());
+    }
 }
@@ -0,0 +1,378 @@
+use crate::analysis::domain::analysis::segments::Segments;
+use crate::analysis::domain::analysis::thresholds::{BRAKE_ON, THROTTLE_ON};
+use crate::lap::domain::lap::variables::Variables;
+
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, Range};
+
+/// Throttle position from which the driver is considered to be at full throttle.
+const FULL_THROTTLE: f32 = 0.98;
+/// Brake applications separated by less than this distance (m) belong to the same zone.
+const MIN_GAP: f32 = 10.;
+/// Maximum distance (m) between the zones of both laps to consider them the same zone.
+const MATCH_MARGIN: f32 = 50.;
+/// Distance deltas (m) below this value are reported as "at the same point".
+const NEGLIGIBLE_DELTA: f32 = 1.;
+
+/// Key points of a lap through a braking zone. Distances in metres, times in seconds.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct BrakingPoints {
+    /// Distance where the brake is first applied.
+    pub onset: f32,
+    /// Maximum brake pressure reached within the zone.
+    pub peak_pressure: f32,
+    /// Distance where the maximum brake pressure is reached.
+    pub peak_distance: f32,
+    /// Distance where the brake is released.
+    pub release: f32,
+    /// Distance where the throttle is applied again after releasing the brake.
+    pub throttle_application: Option<f32>,
+    /// Distance where full throttle is reached after releasing the brake.
+    pub full_throttle: Option<f32>,
+}
+
+/// Differences of the target lap with respect to the reference lap through a braking zone.
+///
+/// Positive distances mean that the target lap does it later than the reference lap.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Deltas {
+    pub onset: f32,
+    pub peak_pressure: f32,
+    pub release: f32,
+    pub throttle_application: Option<f32>,
+    pub full_throttle: Option<f32>,
+    /// Time lost (positive) or gained (negative) by the target lap through the whole zone.
+    pub time: f32,
+}
+
+/// Comparison of both laps of an analysis through one braking zone.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct BrakingZone {
+    /// Number of the corner the driver brakes for, if known.
+    pub corner: Option<u16>,
+    pub reference: BrakingPoints,
+    pub target: BrakingPoints,
+    pub deltas: Deltas,
+}
+
+/// Represents the braking zones of an analysis, in track order.
+#[derive(Serialize, Deserialize, PartialEq, Default, Clone, Debug)]
+pub struct BrakingZones {
+    zones: Vec<BrakingZone>,
+}
+
+impl From<Vec<BrakingZone>> for BrakingZones {
+    /// Converts a vector of braking zones into `BrakingZones`.
+    fn from(zones: Vec<BrakingZone>) -> Self {
+        Self { zones }
+    }
+}
+
+impl Deref for BrakingZones {
+    type Target = Vec<BrakingZone>;
+
+    /// Implements dereferencing for `BrakingZones`.
+    fn deref(&self) -> &Self::Target {
+        &self.zones
+    }
+}
+
+/// Detects the braking zones of both laps and compares them.
+///
+/// Both `reference` and `target` must be sampled on the same distances. Zones are detected
+/// independently on each lap and matched when they are close enough, so zones where only one
+/// of the laps brakes are not reported.
+#[must_use]
+pub fn compare(segments: &Segments, reference: &Variables, target: &Variables) -> BrakingZones {
+    let reference_zones = detect(reference);
+    let target_zones = detect(target);
+
+    reference_zones
+        .iter()
+        .filter_map(|reference_points| {
+            let target_points = target_zones
+                .iter()
+                .filter(|t| {
+                    t.onset <= reference_points.release + MATCH_MARGIN
+                        && t.release >= reference_points.onset - MATCH_MARGIN
+                })
+                .min_by(|a, b| {
+                    (a.onset - reference_points.onset)
+                        .abs()
+                        .total_cmp(&(b.onset - reference_points.onset).abs())
+                })?;
+
+            let corner = segments
+                .corners()
+                .find(|c| c.exit >= reference_points.release)
+                .map(|c| c.number);
+
+            Some(BrakingZone {
+                corner,
+                deltas: Deltas::calculate(reference_points, target_points, reference, target),
+                reference: reference_points.clone(),
+                target: target_points.clone(),
+            })
+        })
+        .collect::<Vec<BrakingZone>>()
+        .into()
+}
+
+/// Detects the braking zones of a lap.
+#[must_use]
+pub fn detect(variables: &Variables) -> Vec<BrakingPoints> {
+    let distances = &variables.distance;
+    let brakes = &variables.brake;
+    let len = distances.len().min(brakes.len());
+
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    let mut close_run = |start: usize, end: usize| match runs.last_mut() {
+        Some(last) if distances[start] - distances[last.1] < MIN_GAP => last.1 = end,
+        _ => runs.push((start, end)),
+    };
+    let mut start: Option<usize> = None;
+    for (i, &brake) in brakes.iter().enumerate().take(len) {
+        match (brake > BRAKE_ON, start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                close_run(s, i - 1);
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    // The lap may end while braking
+    if let Some(s) = start {
+        close_run(s, len - 1);
+    }
+
+    runs.iter()
+        .enumerate()
+        .map(|(i, &(onset, release))| {
+            let (peak_idx, peak_pressure) = (onset..=release)
+                .map(|j| (j, brakes[j]))
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                .unwrap_or((onset, brakes[onset]));
+
+            // Throttle points are searched until the next braking zone
+            let until = runs.get(i + 1).map_or(len, |next| next.0);
+            let throttle_after =
+                |threshold: f32| throttle_reached(variables, release..until, threshold);
+
+            BrakingPoints {
+                onset: distances[onset],
+                peak_pressure,
+                peak_distance: distances[peak_idx],
+                release: distances[release],
+                throttle_application: throttle_after(THROTTLE_ON),
+                full_throttle: throttle_after(FULL_THROTTLE),
+            }
+        })
+        .collect()
+}
+
+/// Distance of the first sample within `range` where the throttle reaches `threshold`.
+pub(crate) fn throttle_reached(
+    variables: &Variables,
+    range: Range<usize>,
+    threshold: f32,
+) -> Option<f32> {
+    range
+        .take_while(|&j| j < variables.distance.len())
+        .find(|&j| variables.throttle.get(j).is_some_and(|&t| t >= threshold))
+        .map(|j| variables.distance[j])
+}
+
+impl Deltas {
+    #[must_use]
+    pub fn calculate(
+        reference: &BrakingPoints,
+        target: &BrakingPoints,
+        reference_variables: &Variables,
+        target_variables: &Variables,
+    ) -> Self {
+        let optional_delta = |r: Option<f32>, t: Option<f32>| Some(t? - r?);
+
+        // The zone spans from the earliest onset to the latest point back on full throttle
+        let start = reference.onset.min(target.onset);
+        let end = [
+            reference.full_throttle.unwrap_or(reference.release),
+            target.full_throttle.unwrap_or(target.release),
+        ]
+        .into_iter()
+        .fold(f32::NEG_INFINITY, f32::max);
+
+        let time = elapsed(target_variables, start, end) - elapsed(reference_variables, start, end);
+
+        Self {
+            onset: target.onset - reference.onset,
+            peak_pressure: target.peak_pressure - reference.peak_pressure,
+            release: target.release - reference.release,
+            throttle_application: optional_delta(
+                reference.throttle_application,
+                target.throttle_application,
+            ),
+            full_throttle: optional_delta(reference.full_throttle, target.full_throttle),
+            time,
+        }
+    }
+}
+
+/// Time (s) spent by a lap between two distances.
+pub(crate) fn elapsed(variables: &Variables, from: f32, to: f32) -> f32 {
+    let time_at = |distance: f32| {
+        let idx = variables
+            .distance
+            .partition_point(|&d| d < distance)
+            .min(variables.distance.len().saturating_sub(1));
+        variables
+            .lap_current_lap_time
+            .get(idx)
+            .copied()
+            .unwrap_or_default()
+    };
+    time_at(to) - time_at(from)
+}
+
+impl BrakingZone {
+    /// Summarises the zone from the point of view of the target lap driver, e.g.
+    /// "You brake 12 m earlier into T1 and reach full throttle 20 m later (+0.15 s)".
+    #[must_use]
+    pub fn description(&self) -> String {
+        let corner = self
+            .corner
+            .map_or_else(String::new, |number| format!(" into T{number}"));
+
+        let mut actions = vec![format!(
+            "brake {}{corner}",
+            describe_delta(self.deltas.onset)
+        )];
+        if self.deltas.release.abs() >= NEGLIGIBLE_DELTA {
+            actions.push(format!(
+                "release the brake {}",
+                describe_delta(self.deltas.release)
+            ));
+        }
+        if let Some(full_throttle) = self.deltas.full_throttle {
+            actions.push(format!(
+                "reach full throttle {}",
+                describe_delta(full_throttle)
+            ));
+        }
+
+        let last = actions.pop().unwrap_or_default();
+        let actions = if actions.is_empty() {
+            last
+        } else {
+            format!("{} and {last}", actions.join(", "))
+        };
+
+        format!("You {actions} ({:+.2} s).", self.deltas.time)
+    }
+}
+
+fn describe_delta(delta: f32) -> String {
+    if delta.abs() < NEGLIGIBLE_DELTA {
+        "at the same point".to_string()
+    } else if delta < 0. {
+        format!("{:.0} m earlier", delta.abs())
+    } else {
+        format!("{delta:.0} m later")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::domain::analysis::segment::Segment;
+
+    /// A 400 m lap sampled every metre at 40 m/s except in the braking zone, with the brake
+    /// applied between `onset` and `release` and full throttle from `full_throttle`.
+    fn test_variables(onset: f32, release: f32, full_throttle: f32) -> Variables {
+        let mut variables = Variables::default();
+        let mut time = 0.;
+        for i in 0..400 {
+            #[allow(clippy::cast_precision_loss)]
+            let d = i as f32;
+            let braking = d >= onset && d <= release;
+            let speed = if d >= onset && d < full_throttle {
+                20.
+            } else {
+                40.
+            };
+            time += 1. / speed;
+
+            variables.distance.push(d);
+            variables.speed.push(speed);
+            variables.brake.push(if braking { 0.9 } else { 0. });
+            variables.throttle.push(if d >= full_throttle || d < onset {
+                1.
+            } else if d > release {
+                0.5
+            } else {
+                0.
+            });
+            variables.lap_current_lap_time.push(time);
+        }
+        variables
+    }
+
+    #[test]
+    fn detects_single_braking_zone() {
+        let zones = detect(&test_variables(100., 150., 200.));
+
+        assert_eq!(zones.len(), 1);
+        assert!((zones[0].onset - 100.).abs() < f32::EPSILON);
+        assert!((zones[0].release - 150.).abs() < f32::EPSILON);
+        assert_eq!(zones[0].throttle_application, Some(151.));
+        assert_eq!(zones[0].full_throttle, Some(200.));
+    }
+
+    #[test]
+    fn merges_brake_applications_at_the_end_of_the_lap() {
+        let mut variables = test_variables(100., 150., 200.);
+        for (i, brake) in variables.brake.iter_mut().enumerate() {
+            if (390..395).contains(&i) || i >= 398 {
+                *brake = 0.9;
+            }
+        }
+
+        let zones = detect(&variables);
+
+        assert_eq!(zones.len(), 2);
+        assert!((zones[1].onset - 390.).abs() < f32::EPSILON);
+        assert!((zones[1].release - 399.).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn compares_braking_zones_of_both_laps() {
+        let segments = Segments::from(vec![Segment::corner(1, 140., 160., 190.)]);
+        let reference = test_variables(100., 150., 200.);
+        let target = test_variables(88., 150., 220.);
+
+        let zones = compare(&segments, &reference, &target);
+
+        assert_eq!(zones.len(), 1);
+        let zone = &zones[0];
+        assert_eq!(zone.corner, Some(1));
+        assert!((zone.deltas.onset + 12.).abs() < f32::EPSILON);
+        assert_eq!(zone.deltas.full_throttle, Some(20.));
+        assert!(zone.deltas.time > 0.);
+        assert_eq!(
+            zone.description(),
+            format!(
+                "You brake 12 m earlier into T1 and reach full throttle 20 m later ({:+.2} s).",
+                zone.deltas.time
+            )
+        );
+    }
+
+    #[test]
+    fn unmatched_zones_are_not_reported() {
+        let reference = test_variables(100., 150., 200.);
+        let mut target = test_variables(100., 150., 200.);
+        target.brake.iter_mut().for_each(|b| *b = 0.);
+
+        assert!(compare(&Segments::default(), &reference, &target).is_empty());
+    }
+}
@@ -0,0 +1,506 @@
+use serde::{Deserialize, Serialize};
+
+/// Language of the coaching messages.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    En,
+    Es,
+    It,
+}
+
+impl Language {
+    pub const ALL: [Self; 3] = [Self::En, Self::Es, Self::It];
+
+    /// ISO 639-1 code of the language, as used in the `language` parameter of the API.
+    #[must_use]
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Es => "es",
+            Self::It => "it",
+        }
+    }
+
+    /// Name of the language in the language itself.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::En => "English",
+            Self::Es => "Español",
+            Self::It => "Italiano",
+        }
+    }
+
+    /// Language with the given ISO 639-1 code, if supported.
+    #[must_use]
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code() == code)
+    }
+}
+
+/// Grammatical gender of the channel nouns, which some messages agree with.
+///
+/// Messages that do not refer to any channel use the masculine form.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum Gender {
+    #[default]
+    Masculine,
+    Feminine,
+}
+
+/// Every message of the catalogues. Placeholders are written between braces, e.g. `{channel}`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Message {
+    /// Placeholders: `from`, `to`, `segment`, `actions`, `time`.
+    Advice,
+    /// Placeholder: `name`.
+    Segment,
+    And,
+    /// Placeholder: `distance`.
+    BrakeEarlier,
+    /// Placeholder: `distance`.
+    BrakeLater,
+    /// Placeholder: `intensity`.
+    MoreSpeed,
+    /// Placeholder: `intensity`.
+    LessSpeed,
+    /// Placeholder: `intensity`.
+    MoreThrottle,
+    /// Placeholder: `intensity`.
+    LessThrottle,
+    /// Placeholder: `intensity`.
+    BrakeHarder,
+    /// Placeholder: `intensity`.
+    BrakeGentler,
+    HigherGear,
+    LowerGear,
+    /// Placeholder: `intensity`.
+    SteerLeft,
+    /// Placeholder: `intensity`.
+    SteerRight,
+    /// Intensities placed before a comparative, by level. They agree with the gender of the
+    /// channel.
+    SignificantlyBefore,
+    MuchBefore,
+    FarBefore,
+    /// Intensities placed after a verb, by level.
+    SignificantlyAfter,
+    MuchAfter,
+    FarAfter,
+    Speed,
+    Throttle,
+    Brake,
+    Gear,
+    SteeringWheelAngle,
+    /// Placeholder: `channel`.
+    TagStay,
+    /// Placeholder: `channel`.
+    TagIncrease,
+    /// Placeholder: `channel`.
+    TagReduce,
+    /// Placeholders: `action`, `tendency`.
+    TagTendency,
+    /// Placeholder: `channel`.
+    ActionStay,
+    /// Placeholders: `channel`, `intensity`.
+    ActionIncrease,
+    /// Placeholders: `channel`, `intensity`.
+    ActionReduce,
+    /// Refers to the channel with a pronoun, which agrees with its gender.
+    TendencyStay,
+    /// Placeholder: `intensity`.
+    TendencyIncrease,
+    /// Placeholder: `intensity`.
+    TendencyReduce,
+    SuggestionsTitle,
+    SuggestionsHint,
+    CoachingTitle,
+    /// Placeholders: `gear`, `rpm`, `segment`, `optimal`.
+    EarlyUpshift,
+    /// Placeholders: `gear`, `rpm`, `segment`, `optimal`.
+    LateUpshift,
+    /// Placeholders: `gear`, `rpm`, `segment`, `optimal`.
+    EarlyDownshift,
+    /// Placeholders: `gear`, `rpm`, `segment`, `optimal`.
+    LateDownshift,
+    GearShiftsTitle,
+    ConsistencyTitle,
+    /// Placeholder: `driver`.
+    MeasureConsistency,
+    /// Placeholders: `segment`, `spread`, `time`.
+    LeastConsistent,
+    Consistent,
+}
+
+impl Message {
+    /// Template of the message in `language`.
+    #[must_use]
+    #[allow(clippy::too_many_lines)]
+    pub const fn template(self, language: Language) -> &'static str {
+        use Language::{En, Es, It};
+
+        match (self, language) {
+            (Self::Advice, En) => "Between {from} m and {to} m{segment}, {actions} ({time} s).",
+            (Self::Advice, Es) => "Entre {from} m y {to} m{segment}, {actions} ({time} s).",
+            (Self::Advice, It) => "Tra {from} m e {to} m{segment}, {actions} ({time} s).",
+            (Self::Segment, _) => " ({name})",
+            (Self::And, En) => "and",
+            (Self::And, Es) => "y",
+            (Self::And, It) => "e",
+            (Self::BrakeEarlier, En) => "brake {distance} m earlier",
+            (Self::BrakeEarlier, Es) => "frena {distance} m antes",
+            (Self::BrakeEarlier, It) => "frena {distance} m prima",
+            (Self::BrakeLater, En) => "brake {distance} m later",
+            (Self::BrakeLater, Es) => "frena {distance} m más tarde",
+            (Self::BrakeLater, It) => "frena {distance} m più tardi",
+            (Self::MoreSpeed, En) => "carry {intensity}more speed",
+            (Self::MoreSpeed, Es) => "lleva {intensity}más velocidad",
+            (Self::MoreSpeed, It) => "porta {intensity}più velocità",
+            (Self::LessSpeed, En) => "carry {intensity}less speed",
+            (Self::LessSpeed, Es) => "lleva {intensity}menos velocidad",
+            (Self::LessSpeed, It) => "porta {intensity}meno velocità",
+            (Self::MoreThrottle, En) => "apply {intensity}more throttle",
+            (Self::MoreThrottle, Es) => "acelera {intensity}más",
+            (Self::MoreThrottle, It) => "accelera {intensity}di più",
+            (Self::LessThrottle, En) => "apply {intensity}less throttle",
+            (Self::LessThrottle, Es) => "acelera {intensity}menos",
+            (Self::LessThrottle, It) => "accelera {intensity}di meno",
+            (Self::BrakeHarder, En) => "brake {intensity}harder",
+            (Self::BrakeHarder, Es) => "frena {intensity}más fuerte",
+            (Self::BrakeHarder, It) => "frena {intensity}più forte",
+            (Self::BrakeGentler, En) => "brake {intensity}more gently",
+            (Self::BrakeGentler, Es) => "frena {intensity}más suave",
+            (Self::BrakeGentler, It) => "frena {intensity}più dolcemente",
+            (Self::HigherGear, En) => "use a higher gear",
+            (Self::HigherGear, Es) => "usa una marcha más larga",
+            (Self::HigherGear, It) => "usa una marcia più alta",
+            (Self::LowerGear, En) => "use a lower gear",
+            (Self::LowerGear, Es) => "usa una marcha más corta",
+            (Self::LowerGear, It) => "usa una marcia più bassa",
+            (Self::SteerLeft, En) => "steer {intensity}more to the left",
+            (Self::SteerLeft, Es) => "gira {intensity}más a la izquierda",
+            (Self::SteerLeft, It) => "sterza {intensity}più a sinistra",
+            (Self::SteerRight, En) => "steer {intensity}more to the right",
+            (Self::SteerRight, Es) => "gira {intensity}más a la derecha",
+            (Self::SteerRight, It) => "sterza {intensity}più a destra",
+            (Self::SignificantlyBefore, En) => "significantly ",
+            (Self::SignificantlyBefore, Es) => "bastante ",
+            (Self::SignificantlyBefore, It) => "decisamente ",
+            (Self::MuchBefore, En) => "much ",
+            (Self::MuchBefore, Es) => "mucho ",
+            (Self::MuchBefore, It) => "molto ",
+            (Self::FarBefore, En) => "far ",
+            (Self::FarBefore, Es) => "muchísimo ",
+            (Self::FarBefore, It) => "moltissimo ",
+            (Self::SignificantlyAfter, En) => " significantly",
+            (Self::SignificantlyAfter, Es) => " considerablemente",
+            (Self::SignificantlyAfter, It) => " notevolmente",
+            (Self::MuchAfter, En) => " greatly",
+            // Adverbs after a verb do not agree with the channel
+            (Self::MuchAfter, Es) => " mucho",
+            (Self::MuchAfter, It) => " molto",
+            (Self::FarAfter, En) => " a lot",
+            (Self::FarAfter, Es) => " muchísimo",
+            (Self::FarAfter, It) => " moltissimo",
+            (Self::Speed, En) => "the speed",
+            (Self::Speed, Es) => "la velocidad",
+            (Self::Speed, It) => "la velocità",
+            (Self::Throttle, En) => "the throttle",
+            (Self::Throttle, Es) => "el acelerador",
+            (Self::Throttle, It) => "l'acceleratore",
+            (Self::Brake, En) => "the brake",
+            (Self::Brake, Es) => "el freno",
+            (Self::Brake, It) => "il freno",
+            (Self::Gear, En) => "the gear",
+            (Self::Gear, Es) => "la marcha",
+            (Self::Gear, It) => "la marcia",
+            (Self::SteeringWheelAngle, En) => "the steering wheel angle",
+            (Self::SteeringWheelAngle, Es) => "el ángulo de volante",
+            (Self::SteeringWheelAngle, It) => "l'angolo di sterzo",
+            (Self::TagStay, En) => "You can keep {channel} as it is.",
+            (Self::TagStay, Es) => "Puedes mantener {channel} como está.",
+            (Self::TagStay, It) => "Puoi mantenere {channel} così com'è.",
+            (Self::TagIncrease, En) => "You should increase {channel}.",
+            (Self::TagIncrease, Es) => "Deberías aumentar {channel}.",
+            (Self::TagIncrease, It) => "Dovresti aumentare {channel}.",
+            (Self::TagReduce, En) => "You should reduce {channel}.",
+            (Self::TagReduce, Es) => "Deberías reducir {channel}.",
+            (Self::TagReduce, It) => "Dovresti ridurre {channel}.",
+            (Self::TagTendency, En) => "You should {action}, but it would be good to {tendency}.",
+            (Self::TagTendency, Es) => "Deberías {action}, pero sería bueno {tendency}.",
+            (Self::TagTendency, It) => "Dovresti {action}, ma sarebbe bene {tendency}.",
+            (Self::ActionStay, En) => "keep {channel} as it is",
+            (Self::ActionStay, Es) => "mantener {channel} como está",
+            (Self::ActionStay, It) => "mantenere {channel} così com'è",
+            (Self::ActionIncrease, En) => "increase {channel}{intensity}",
+            (Self::ActionIncrease, Es) => "aumentar {channel}{intensity}",
+            (Self::ActionIncrease, It) => "aumentare {channel}{intensity}",
+            (Self::ActionReduce, En) => "reduce {channel}{intensity}",
+            (Self::ActionReduce, Es) => "reducir {channel}{intensity}",
+            (Self::ActionReduce, It) => "ridurre {channel}{intensity}",
+            (Self::TendencyStay, En) => "maintain it",
+            (Self::TendencyStay, Es) => "mantenerlo así",
+            (Self::TendencyStay, It) => "mantenerlo così",
+            (Self::TendencyIncrease, En) => "start increasing it{intensity}",
+            (Self::TendencyIncrease, Es) => "empezar a aumentarlo{intensity}",
+            (Self::TendencyIncrease, It) => "iniziare ad aumentarlo{intensity}",
+            (Self::TendencyReduce, En) => "start reducing it{intensity}",
+            (Self::TendencyReduce, Es) => "empezar a reducirlo{intensity}",
+            (Self::TendencyReduce, It) => "iniziare a ridurlo{intensity}",
+            (Self::SuggestionsTitle, En) => "Suggestions",
+            (Self::SuggestionsTitle, Es) => "Sugerencias",
+            (Self::SuggestionsTitle, It) => "Suggerimenti",
+            (Self::SuggestionsHint, En) => {
+                "To start receiving suggestions, select a point on the map or hover over a graph."
+            }
+            (Self::SuggestionsHint, Es) => {
+                "Para empezar a recibir sugerencias, selecciona un punto del mapa o pasa el ratón por un gráfico."
+            }
+            (Self::SuggestionsHint, It) => {
+                "Per ricevere suggerimenti, seleziona un punto sulla mappa o passa il mouse su un grafico."
+            }
+            (Self::CoachingTitle, En) => "Where you lose time",
+            (Self::CoachingTitle, Es) => "Dónde pierdes tiempo",
+            (Self::CoachingTitle, It) => "Dove perdi tempo",
+            (Self::EarlyUpshift, En) => {
+                "You shift up from gear {gear} at {rpm} rpm{segment}; hold it until about {optimal} rpm."
+            }
+            (Self::EarlyUpshift, Es) => {
+                "Subes desde la marcha {gear} a {rpm} rpm{segment}; aguántala hasta unas {optimal} rpm."
+            }
+            (Self::EarlyUpshift, It) => {
+                "Sali dalla marcia {gear} a {rpm} rpm{segment}; tienila fino a circa {optimal} rpm."
+            }
+            (Self::LateUpshift, En) => {
+                "You shift up from gear {gear} at {rpm} rpm{segment}; shift earlier, at about {optimal} rpm."
+            }
+            (Self::LateUpshift, Es) => {
+                "Subes desde la marcha {gear} a {rpm} rpm{segment}; cambia antes, a unas {optimal} rpm."
+            }
+            (Self::LateUpshift, It) => {
+                "Sali dalla marcia {gear} a {rpm} rpm{segment}; cambia prima, a circa {optimal} rpm."
+            }
+            (Self::EarlyDownshift, En) => {
+                "You shift down from gear {gear} at {rpm} rpm{segment}; wait until about {optimal} rpm, like the reference lap."
+            }
+            (Self::EarlyDownshift, Es) => {
+                "Reduces desde la marcha {gear} a {rpm} rpm{segment}; espera hasta unas {optimal} rpm, como la vuelta de referencia."
+            }
+            (Self::EarlyDownshift, It) => {
+                "Scali dalla marcia {gear} a {rpm} rpm{segment}; aspetta fino a circa {optimal} rpm, come il giro di riferimento."
+            }
+            (Self::LateDownshift, En) => {
+                "You shift down from gear {gear} at {rpm} rpm{segment}; shift earlier, at about {optimal} rpm, like the reference lap."
+            }
+            (Self::LateDownshift, Es) => {
+                "Reduces desde la marcha {gear} a {rpm} rpm{segment}; reduce antes, a unas {optimal} rpm, como la vuelta de referencia."
+            }
+            (Self::LateDownshift, It) => {
+                "Scali dalla marcia {gear} a {rpm} rpm{segment}; scala prima, a circa {optimal} rpm, come il giro di riferimento."
+            }
+            (Self::GearShiftsTitle, En) => "Gear changes",
+            (Self::GearShiftsTitle, Es) => "Cambios de marcha",
+            (Self::GearShiftsTitle, It) => "Cambi di marcia",
+            (Self::ConsistencyTitle, En) => "Where you are least consistent",
+            (Self::ConsistencyTitle, Es) => "Dónde eres menos regular",
+            (Self::ConsistencyTitle, It) => "Dove sei meno costante",
+            (Self::MeasureConsistency, En) => "Measure the consistency of {driver} in this session",
+            (Self::MeasureConsistency, Es) => "Medir la regularidad de {driver} en esta sesión",
+            (Self::MeasureConsistency, It) => "Misura la costanza di {driver} in questa sessione",
+            (Self::LeastConsistent, En) => "{segment}: {spread} times the usual spread, ±{time} s",
+            (Self::LeastConsistent, Es) => {
+                "{segment}: {spread} veces la dispersión habitual, ±{time} s"
+            }
+            (Self::LeastConsistent, It) => {
+                "{segment}: {spread} volte la dispersione abituale, ±{time} s"
+            }
+            (Self::Consistent, En) => "No section stands out: you are equally consistent everywhere.",
+            (Self::Consistent, Es) => "Ninguna sección destaca: eres igual de regular en todas.",
+            (Self::Consistent, It) => "Nessuna sezione spicca: sei ugualmente costante ovunque.",
+        }
+    }
+
+    /// Template of the message in `language`, agreeing with a channel of the given gender.
+    #[must_use]
+    pub const fn template_for(self, language: Language, gender: Gender) -> &'static str {
+        use Gender::Feminine;
+        use Language::{Es, It};
+
+        match (self, language, gender) {
+            (Self::MuchBefore, Es, Feminine) => "mucha ",
+            (Self::MuchBefore, It, Feminine) => "molta ",
+            (Self::FarBefore, Es, Feminine) => "muchísima ",
+            (Self::FarBefore, It, Feminine) => "moltissima ",
+            (Self::TendencyStay, Es, Feminine) => "mantenerla así",
+            (Self::TendencyStay, It, Feminine) => "mantenerla così",
+            (Self::TendencyIncrease, Es, Feminine) => "empezar a aumentarla{intensity}",
+            (Self::TendencyIncrease, It, Feminine) => "iniziare ad aumentarla{intensity}",
+            (Self::TendencyReduce, Es, Feminine) => "empezar a reducirla{intensity}",
+            (Self::TendencyReduce, It, Feminine) => "iniziare a ridurla{intensity}",
+            _ => self.template(language),
+        }
+    }
+
+    /// Gender of the noun of a channel message, e.g. `Message::Speed`, in `language`.
+    #[must_use]
+    pub const fn gender(self, language: Language) -> Gender {
+        match (self, language) {
+            (Self::Speed | Self::Gear, Language::Es | Language::It) => Gender::Feminine,
+            _ => Gender::Masculine,
+        }
+    }
+
+    /// Message in `language` without placeholders.
+    #[must_use]
+    pub fn text(self, language: Language) -> String {
+        self.text_for(language, Gender::default())
+    }
+
+    /// Message in `language` without placeholders, agreeing with a channel of the given gender.
+    #[must_use]
+    pub fn text_for(self, language: Language, gender: Gender) -> String {
+        self.template_for(language, gender).to_string()
+    }
+
+    /// Message in `language` with every `{name}` placeholder replaced by its value in `args`.
+    #[must_use]
+    pub fn format(self, language: Language, args: &[(&str, &str)]) -> String {
+        self.format_for(language, Gender::default(), args)
+    }
+
+    /// Message in `language`, agreeing with a channel of the given gender, with every `{name}`
+    /// placeholder replaced by its value in `args`.
+    #[must_use]
+    pub fn format_for(self, language: Language, gender: Gender, args: &[(&str, &str)]) -> String {
+        args.iter().fold(
+            self.template_for(language, gender).to_string(),
+            |text, (name, value)| text.replace(&format!("{{{name}}}"), value),
+        )
+    }
+
+    /// Intensity of a change of the given level, placed before a comparative, e.g. "much more",
+    /// agreeing with a channel of the given gender.
+    #[must_use]
+    pub fn intensity_before(level: u8, language: Language, gender: Gender) -> String {
+        match level {
+            0 => String::new(),
+            1 => Self::SignificantlyBefore.text_for(language, gender),
+            2 => Self::MuchBefore.text_for(language, gender),
+            _ => Self::FarBefore.text_for(language, gender),
+        }
+    }
+
+    /// Intensity of a change of the given level, placed after a verb, e.g. "increase greatly".
+    #[must_use]
+    pub fn intensity_after(level: u8, language: Language) -> String {
+        match level {
+            0 => String::new(),
+            1 => Self::SignificantlyAfter.text(language),
+            2 => Self::MuchAfter.text(language),
+            _ => Self::FarAfter.text(language),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGES: [Message; 48] = [
+        Message::Advice,
+        Message::Segment,
+        Message::And,
+        Message::BrakeEarlier,
+        Message::BrakeLater,
+        Message::MoreSpeed,
+        Message::LessSpeed,
+        Message::MoreThrottle,
+        Message::LessThrottle,
+        Message::BrakeHarder,
+        Message::BrakeGentler,
+        Message::HigherGear,
+        Message::LowerGear,
+        Message::SteerLeft,
+        Message::SteerRight,
+        Message::SignificantlyBefore,
+        Message::MuchBefore,
+        Message::FarBefore,
+        Message::SignificantlyAfter,
+        Message::MuchAfter,
+        Message::FarAfter,
+        Message::Speed,
+        Message::Throttle,
+        Message::Brake,
+        Message::Gear,
+        Message::SteeringWheelAngle,
+        Message::TagStay,
+        Message::TagIncrease,
+        Message::TagReduce,
+        Message::TagTendency,
+        Message::ActionStay,
+        Message::ActionIncrease,
+        Message::ActionReduce,
+        Message::TendencyStay,
+        Message::TendencyIncrease,
+        Message::TendencyReduce,
+        Message::SuggestionsTitle,
+        Message::SuggestionsHint,
+        Message::CoachingTitle,
+        Message::EarlyUpshift,
+        Message::LateUpshift,
+        Message::EarlyDownshift,
+        Message::LateDownshift,
+        Message::GearShiftsTitle,
+        Message::ConsistencyTitle,
+        Message::MeasureConsistency,
+        Message::LeastConsistent,
+        Message::Consistent,
+    ];
+
+    fn placeholders(template: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = template
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn every_language_has_the_same_placeholders() {
+        for message in MESSAGES {
+            let english = placeholders(message.template(Language::En));
+            for language in Language::ALL {
+                for gender in [Gender::Masculine, Gender::Feminine] {
+                    assert_eq!(
+                        placeholders(message.template_for(language, gender)),
+                        english,
+                        "{message:?} in {language:?} ({gender:?})"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fills_placeholders() {
+        let text = Message::BrakeLater.format(Language::Es, &[("distance", "12")]);
+
+        assert_eq!(text, "frena 12 m más tarde");
+        assert_eq!(
+            Message::intensity_before(2, Language::Es, Message::Speed.gender(Language::Es)),
+            "mucha "
+        );
+        assert_eq!(
+            Message::intensity_before(2, Language::Es, Message::Brake.gender(Language::Es)),
+            "mucho "
+        );
+        assert_eq!(Language::from_code("it"), Some(Language::It));
+        assert_eq!(Language::from_code("fr"), None);
+    }
+}
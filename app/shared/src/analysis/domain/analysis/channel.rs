@@ -0,0 +1,67 @@
+use crate::lap::domain::lap::variables::Variables;
+
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Kilometres per hour in a metre per second.
+const KMH_PER_MS: f64 = 3.6;
+
+/// Telemetry channels used to cluster the differences between two laps.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Channel {
+    Speed,
+    Throttle,
+    Brake,
+    Gear,
+    SteeringWheelAngle,
+}
+
+impl Channel {
+    /// Every clustered channel, in the order they are reported.
+    pub const ALL: [Self; 5] = [
+        Self::Speed,
+        Self::Throttle,
+        Self::Brake,
+        Self::Gear,
+        Self::SteeringWheelAngle,
+    ];
+
+    /// Converts `value`, given in the unit the channel is configured in, to the unit of its
+    /// telemetry. Speeds are configured in km/h, the other channels in their telemetry units.
+    #[must_use]
+    pub fn to_telemetry_unit(self, value: f64) -> f64 {
+        match self {
+            Self::Speed => value / KMH_PER_MS,
+            Self::Throttle | Self::Brake | Self::Gear | Self::SteeringWheelAngle => value,
+        }
+    }
+
+    /// Returns the values of this channel in `variables`.
+    #[must_use]
+    pub fn values(self, variables: &Variables) -> Vec<f64> {
+        match self {
+            Self::Speed => variables.speed.iter().map(|&v| f64::from(v)).collect(),
+            Self::Throttle => variables.throttle.iter().map(|&v| f64::from(v)).collect(),
+            Self::Brake => variables.brake.iter().map(|&v| f64::from(v)).collect(),
+            Self::Gear => variables.gear.iter().map(|&v| f64::from(v)).collect(),
+            Self::SteeringWheelAngle => variables
+                .steering_wheel_angle
+                .iter()
+                .map(|&v| f64::from(v))
+                .collect(),
+        }
+    }
+}
+
+impl Display for Channel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Speed => write!(f, "speed"),
+            Self::Throttle => write!(f, "throttle"),
+            Self::Brake => write!(f, "brake"),
+            Self::Gear => write!(f, "gear"),
+            Self::SteeringWheelAngle => write!(f, "steering wheel angle"),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::analysis::domain::analysis::fuzzy_c_means::FittedModel;
+use crate::analysis::domain::analysis::validity::Criterion;
+
+use ndarray::Array2;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+/// A clustering algorithm whose result can be consumed by `TagAssigner`.
+///
+/// Every implementation returns one centroid per cluster and a membership matrix with one row
+/// per sample and one column per cluster, each row adding up to 1. Crisp algorithms return
+/// memberships of either 0 or 1.
+pub trait ClusteringModel: Send + Sync {
+    /// Fits the model to `data`, where each row is a sample and each column a feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model cannot be fitted to `data`.
+    fn try_fit(&self, data: &Array2<f64>) -> Result<FittedModel, Error>;
+}
+
+/// Clustering algorithm used by the grid search.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Algorithm {
+    /// Fuzzy c-means, searching over `c`, `m`, `max_iter` and `error`.
+    #[default]
+    FuzzyCMeans,
+    /// K-means with k-means++ initialisation, searching over `c`, `max_iter` and `error`.
+    KMeans,
+    /// Gaussian mixture with diagonal covariances fitted with EM, searching over `c`,
+    /// `max_iter` and `error`.
+    GaussianMixture,
+    /// DBSCAN with the given neighbourhood radius and minimum number of neighbours.
+    Dbscan { eps: f64, min_samples: usize },
+}
+
+impl Algorithm {
+    /// Validity index used to choose the best model when none is configured.
+    ///
+    /// The fuzzy partition coefficient only makes sense for fuzzy memberships: it is always 1
+    /// for the crisp partitions of k-means and DBSCAN, and close to 1 for Gaussian mixtures.
+    /// Any other algorithm is compared by its silhouette.
+    #[must_use]
+    pub const fn default_criterion(&self) -> Criterion {
+        match self {
+            Self::FuzzyCMeans => Criterion::Fpc,
+            Self::KMeans | Self::GaussianMixture | Self::Dbscan { .. } => Criterion::Silhouette,
+        }
+    }
+}
+
+/// Random number generator for the initialisation of a model, seeded when `seed` is given.
+pub(crate) fn seeded_rng(seed: Option<u64>) -> StdRng {
+    seed.map_or_else(StdRng::from_entropy, StdRng::seed_from_u64)
+}
+
+#[derive(PartialEq, Eq, Debug, thiserror::Error)]
+pub enum Error {
+    #[error("invalid parameter: {0}")]
+    InvalidParameter(String),
+    #[error("{0} samples are not enough to find {1} clusters")]
+    NotEnoughSamples(usize, usize),
+    #[error("no cluster found")]
+    NoClusterFound,
+    #[error("{0}")]
+    Fitting(String),
+}
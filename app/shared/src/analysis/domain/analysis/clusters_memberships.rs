@@ -1,9 +1,15 @@
+use crate::analysis::domain::analysis::channel::Channel;
 use crate::analysis::domain::analysis::fcm_grid::{Config, FcmGrid};
+use crate::analysis::domain::analysis::joint_clusters::JointClusters;
 use crate::analysis::domain::analysis::tag_assigner::TagAssigner;
+use crate::analysis::domain::analysis::tag_policy::{self, ChannelTagPolicy};
+use crate::analysis::domain::analysis::validity::{ChannelValidity, Criterion};
 use crate::analysis::domain::analysis::Tags;
 use crate::lap::domain::lap::variables::Variables;
 
 use ndarray::Array2;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, PartialEq, Clone, Default)]
@@ -18,9 +24,32 @@ pub struct ClustersMemberships {
     pub gear_tags: Tags,
     pub steering_wheel_angle: Vec<Vec<f64>>,
     pub steering_wheel_angle_tags: Tags,
+    /// Clusters of several channels fitted together, when enabled in the configuration
+    pub joint: Option<JointClusters>,
+    /// Criterion used to choose the model of every channel
+    #[serde(default)]
+    pub criterion: Criterion,
+    /// Validity indices of the model chosen for every channel
+    #[serde(default)]
+    pub validity: Vec<ChannelValidity>,
+    /// Policies effectively used to tag every channel
+    #[serde(default)]
+    pub tag_policies: Vec<ChannelTagPolicy>,
 }
 
 impl ClustersMemberships {
+    /// Tags of the given channel, one per sample.
+    #[must_use]
+    pub const fn tags(&self, channel: Channel) -> &Tags {
+        match channel {
+            Channel::Speed => &self.speed_tags,
+            Channel::Throttle => &self.throttle_tags,
+            Channel::Brake => &self.brake_tags,
+            Channel::Gear => &self.gear_tags,
+            Channel::SteeringWheelAngle => &self.steering_wheel_angle_tags,
+        }
+    }
+
     /// Transforms and fits the Fuzzy C-Means (FCM) model to various variables from `diff_variables`.
     ///
     /// This function performs the following steps:
@@ -28,10 +57,15 @@ impl ClustersMemberships {
     /// 2. Converts each variable from `diff_variables` into a 2D `Array2<f64>`.
     /// 3. Performs grid search to find the best FCM model for each variable.
     /// 4. Extracts membership values from the best model and stores them in the result struct.
+    /// 5. If enabled, fits the joint clustering of several channels (see `JointClusters`).
+    ///
+    /// With the `parallel` feature, channels are fitted on the rayon thread pool.
     ///
     /// # Arguments
     ///
     /// * `diff_variables` - A reference to a `Variables` struct containing different variables to be fitted.
+    /// * `distances` - Distances of the samples, used by the joint clustering window.
+    /// * `fcm_grid_config` - Grid search configuration, optionally enabling the joint clustering.
     ///
     /// # Returns
     ///
@@ -46,77 +80,75 @@ impl ClustersMemberships {
     ///   which could be due to invalid model parameters or fitting issues.
     pub fn try_transform_and_fit(
         diff_variables: &Variables,
+        distances: &[f32],
         fcm_grid_config: &Config,
     ) -> Result<Self, String> {
         let fcm_grid = FcmGrid::new(fcm_grid_config.clone());
 
-        let variables: Vec<(&str, Vec<f64>)> = vec![
-            (
-                "speed",
-                diff_variables.speed.iter().map(|&g| f64::from(g)).collect(),
-            ),
-            (
-                "throttle",
-                diff_variables
-                    .throttle
-                    .iter()
-                    .map(|&g| f64::from(g))
-                    .collect(),
-            ),
-            (
-                "brake",
-                diff_variables.brake.iter().map(|&g| f64::from(g)).collect(),
-            ),
-            (
-                "gear",
-                diff_variables.gear.iter().map(|&g| f64::from(g)).collect(),
-            ),
-            (
-                "steering_wheel_angle",
-                diff_variables
-                    .steering_wheel_angle
-                    .iter()
-                    .map(|&g| f64::from(g))
-                    .collect(),
-            ),
-        ];
-
-        let mut result = Self::default();
-
-        for (name, data) in variables {
+        let fit = |channel: Channel| {
+            let data = channel.values(diff_variables);
             let data = Array2::from_shape_vec((data.len(), 1), data).map_err(|e| format!("{e}"))?;
             let best_model = fcm_grid
                 .get_best_fitted_model(&data)
                 .map_err(|e| format!("{e}"))?;
-            let memberships = best_model.memberships();
+            let memberships: Vec<Vec<f64>> = best_model
+                .memberships()
+                .outer_iter()
+                .map(|row| row.to_vec())
+                .collect();
+            let validity = best_model.validity().copied();
+            let policy = tag_policy::for_channel(&fcm_grid_config.tag_policies, channel)
+                .in_telemetry_units(channel);
+            let tags = TagAssigner::with_policy(data, &best_model, &policy).assign();
+            Ok::<_, String>((channel, memberships, tags, validity))
+        };
 
-            match name {
-                "speed" => {
-                    result.speed = memberships.outer_iter().map(|row| row.to_vec()).collect();
-                    result.speed_tags = TagAssigner::new(data, &best_model, 0.2).assign();
-                }
-                "throttle" => {
-                    result.throttle = memberships.outer_iter().map(|row| row.to_vec()).collect();
-                    result.throttle_tags = TagAssigner::new(data, &best_model, 0.2).assign();
-                }
-                "brake" => {
-                    result.brake = memberships.outer_iter().map(|row| row.to_vec()).collect();
-                    result.brake_tags = TagAssigner::new(data, &best_model, 0.2).assign();
-                }
-                "gear" => {
-                    result.gear = memberships.outer_iter().map(|row| row.to_vec()).collect();
-                    result.gear_tags = TagAssigner::new(data, &best_model, 0.2).assign();
-                }
-                "steering_wheel_angle" => {
-                    result.steering_wheel_angle =
-                        memberships.outer_iter().map(|row| row.to_vec()).collect();
-                    result.steering_wheel_angle_tags =
-                        TagAssigner::new(data, &best_model, 0.2).assign();
+        #[cfg(feature = "parallel")]
+        let fitted: Vec<_> = Channel::ALL.into_par_iter().map(fit).collect();
+        #[cfg(not(feature = "parallel"))]
+        let fitted: Vec<_> = Channel::ALL.into_iter().map(fit).collect();
+
+        let mut result = Self {
+            criterion: fcm_grid_config.criterion(),
+            tag_policies: Channel::ALL
+                .into_iter()
+                .map(|channel| ChannelTagPolicy {
+                    channel,
+                    policy: tag_policy::for_channel(&fcm_grid_config.tag_policies, channel),
+                })
+                .collect(),
+            ..Self::default()
+        };
+
+        for channel_result in fitted {
+            let (channel, memberships, tags, validity) = channel_result?;
+            if let Some(indices) = validity {
+                result.validity.push(ChannelValidity { channel, indices });
+            }
+
+            match channel {
+                Channel::Speed => (result.speed, result.speed_tags) = (memberships, tags),
+                Channel::Throttle => (result.throttle, result.throttle_tags) = (memberships, tags),
+                Channel::Brake => (result.brake, result.brake_tags) = (memberships, tags),
+                Channel::Gear => (result.gear, result.gear_tags) = (memberships, tags),
+                Channel::SteeringWheelAngle => {
+                    (
+                        result.steering_wheel_angle,
+                        result.steering_wheel_angle_tags,
+                    ) = (memberships, tags);
                 }
-                _ => (),
             }
         }
 
+        if let Some(joint_config) = &fcm_grid_config.joint {
+            result.joint = Some(JointClusters::try_fit(
+                diff_variables,
+                distances,
+                fcm_grid_config,
+                joint_config,
+            )?);
+        }
+
         Ok(result)
     }
 }
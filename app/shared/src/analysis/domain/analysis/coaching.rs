@@ -0,0 +1,620 @@
+use crate::analysis::domain::analysis::braking_zones::{elapsed, BrakingZones};
+use crate::analysis::domain::analysis::catalogue::{Language, Message};
+use crate::analysis::domain::analysis::channel::Channel;
+use crate::analysis::domain::analysis::clusters_memberships::ClustersMemberships;
+use crate::analysis::domain::analysis::segment::Segment;
+use crate::analysis::domain::analysis::segments::Segments;
+use crate::analysis::domain::analysis::tag::{Base, Tag};
+use crate::lap::domain::lap::variables::Variables;
+
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+
+/// Minimum share of the samples of a segment tagged in the same direction to advise it.
+const MIN_SHARE: f32 = 0.4;
+/// Brake point deltas (m) below this value are not advised.
+const MIN_BRAKE_POINT_DELTA: f32 = 5.;
+/// Advice on sections where less time (s) than this is lost is not reported.
+const MIN_TIME_LOST: f32 = 0.01;
+
+/// Something the driver of the target lap should do differently.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    /// Increase or reduce a channel, with the level of the change.
+    Channel { channel: Channel, base: Base },
+    /// Move the brake point this distance (m), later when positive.
+    BrakePoint { distance: f32 },
+}
+
+/// Advice for a section of the circuit, e.g.
+/// "Between 820 m and 910 m (T3), brake 12 m later and carry more speed (+0.24 s)."
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Advice {
+    /// Name of the segment the section belongs to, e.g. `T3`.
+    pub segment: Option<String>,
+    /// Distance where the section starts (m).
+    pub from: f32,
+    /// Distance where the section ends (m).
+    pub to: f32,
+    pub actions: Vec<Action>,
+    /// Time lost by the target lap through the section (s).
+    pub time_lost: f32,
+    /// The advice in plain words, in English unless localised.
+    pub message: String,
+    /// Whether the section overlaps one where the driver is least consistent over a stint.
+    #[serde(default)]
+    pub inconsistent: bool,
+}
+
+/// Represents the advice of an analysis, from the most to the least time lost.
+#[derive(Serialize, Deserialize, PartialEq, Default, Clone, Debug)]
+pub struct Coaching {
+    advice: Vec<Advice>,
+}
+
+impl From<Vec<Advice>> for Coaching {
+    /// Converts a vector of advice into `Coaching`.
+    fn from(advice: Vec<Advice>) -> Self {
+        Self { advice }
+    }
+}
+
+impl Deref for Coaching {
+    type Target = Vec<Advice>;
+
+    /// Implements dereferencing for `Coaching`.
+    fn deref(&self) -> &Self::Target {
+        &self.advice
+    }
+}
+
+impl Advice {
+    #[must_use]
+    pub fn new(
+        segment: Option<String>,
+        from: f32,
+        to: f32,
+        actions: Vec<Action>,
+        time_lost: f32,
+    ) -> Self {
+        let message = describe(
+            segment.as_deref(),
+            from,
+            to,
+            &actions,
+            time_lost,
+            Language::default(),
+        );
+        Self {
+            segment,
+            from,
+            to,
+            actions,
+            time_lost,
+            message,
+            inconsistent: false,
+        }
+    }
+
+    /// Writes the message of the advice in `language`.
+    pub fn localise(&mut self, language: Language) {
+        self.message = describe(
+            self.segment.as_deref(),
+            self.from,
+            self.to,
+            &self.actions,
+            self.time_lost,
+            language,
+        );
+    }
+}
+
+impl Coaching {
+    /// Writes the message of every advice in `language`.
+    pub fn localise(&mut self, language: Language) {
+        for advice in &mut self.advice {
+            advice.localise(language);
+        }
+    }
+
+    /// Marks the advice overlapping any of the `sections` (from, to) where the driver is least
+    /// consistent and moves it first, keeping the order by time lost within each group.
+    pub fn focus(&mut self, sections: &[(f32, f32)]) {
+        for advice in &mut self.advice {
+            advice.inconsistent = sections
+                .iter()
+                .any(|&(from, to)| advice.from <= to && from <= advice.to);
+        }
+        self.advice.sort_by_key(|advice| !advice.inconsistent);
+    }
+}
+
+/// Merges the tags of every channel and the braking zones into advice per segment.
+///
+/// Within each segment, a channel is advised when at least `MIN_SHARE` of its samples are
+/// tagged in the same direction, and the section is narrowed to the samples tagged that way.
+/// Corners also get the brake point of their braking zone. Without segments, the whole lap is
+/// a single section. Sections where the target lap does not lose time are left out.
+///
+/// Both `reference` and `target` must be sampled on the same distances as the tags.
+#[must_use]
+pub fn coach(
+    segments: &Segments,
+    braking_zones: &BrakingZones,
+    reference: &Variables,
+    target: &Variables,
+    clustering: &ClustersMemberships,
+) -> Coaching {
+    let distances = &reference.distance;
+    let sections: Vec<(Option<&Segment>, f32, f32)> = if segments.is_empty() {
+        distances
+            .first()
+            .zip(distances.last())
+            .map(|(&first, &last)| (None, first, last))
+            .into_iter()
+            .collect()
+    } else {
+        segments
+            .iter()
+            .map(|segment| (Some(segment), segment.entry, segment.exit))
+            .collect()
+    };
+
+    let mut advice: Vec<Advice> = sections
+        .into_iter()
+        .filter_map(|(segment, entry, exit)| {
+            let start = distances.partition_point(|&d| d < entry);
+            let end = distances.partition_point(|&d| d <= exit);
+            let mut actions = Vec::new();
+            let (mut from, mut to) = (f32::INFINITY, f32::NEG_INFINITY);
+
+            let zone = segment
+                .filter(|s| s.is_corner())
+                .and_then(|s| braking_zones.iter().find(|z| z.corner == Some(s.number)));
+            if let Some(zone) = zone {
+                let distance = -zone.deltas.onset;
+                if distance.abs() >= MIN_BRAKE_POINT_DELTA {
+                    actions.push(Action::BrakePoint { distance });
+                    from = zone.reference.onset.min(zone.target.onset);
+                    to = zone.reference.onset.max(zone.target.onset);
+                }
+            }
+
+            for channel in Channel::ALL {
+                let Some(tags) = clustering.tags(channel).get(start..end) else {
+                    continue;
+                };
+                let bases: Vec<Base> = tags.iter().map(|tag| tag.base()).collect();
+                let Some(base) = dominant(&bases) else {
+                    continue;
+                };
+                actions.push(Action::Channel { channel, base });
+
+                let tagged = || {
+                    bases
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, b)| same_direction(**b, base))
+                        .map(|(i, _)| distances[start + i])
+                };
+                from = tagged().fold(from, f32::min);
+                to = tagged().fold(to, f32::max);
+            }
+
+            if actions.is_empty() {
+                return None;
+            }
+
+            let time_lost = elapsed(target, from, to) - elapsed(reference, from, to);
+            (time_lost >= MIN_TIME_LOST)
+                .then(|| Advice::new(segment.map(Segment::name), from, to, actions, time_lost))
+        })
+        .collect();
+
+    advice.sort_by(|a, b| b.time_lost.total_cmp(&a.time_lost));
+    advice.into()
+}
+
+/// Direction shared by at least `MIN_SHARE` of `bases`, with the mean level of those samples.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn dominant(bases: &[Base]) -> Option<Base> {
+    let increases: Vec<u8> = bases
+        .iter()
+        .filter_map(|b| match b {
+            Base::Increase(level) => Some(*level),
+            _ => None,
+        })
+        .collect();
+    let reduces: Vec<u8> = bases
+        .iter()
+        .filter_map(|b| match b {
+            Base::Reduce(level) => Some(*level),
+            _ => None,
+        })
+        .collect();
+
+    let (levels, base): (_, fn(u8) -> Base) = if increases.len() > reduces.len() {
+        (increases, Base::Increase)
+    } else {
+        (reduces, Base::Reduce)
+    };
+    if levels.is_empty() || (levels.len() as f32) < MIN_SHARE * bases.len() as f32 {
+        return None;
+    }
+
+    let mean = levels.iter().map(|&l| f32::from(l)).sum::<f32>() / levels.len() as f32;
+    Some(base(mean.round() as u8))
+}
+
+const fn same_direction(a: Base, b: Base) -> bool {
+    matches!(
+        (a, b),
+        (Base::Increase(_), Base::Increase(_)) | (Base::Reduce(_), Base::Reduce(_))
+    )
+}
+
+fn describe(
+    segment: Option<&str>,
+    from: f32,
+    to: f32,
+    actions: &[Action],
+    time_lost: f32,
+    language: Language,
+) -> String {
+    let segment = segment.map_or_else(String::new, |name| {
+        Message::Segment.format(language, &[("name", name)])
+    });
+
+    let mut phrases: Vec<String> = actions
+        .iter()
+        .map(|action| describe_action(action, language))
+        .collect();
+    let last = phrases.pop().unwrap_or_default();
+    let phrases = if phrases.is_empty() {
+        last
+    } else {
+        format!(
+            "{} {} {last}",
+            phrases.join(", "),
+            Message::And.text(language)
+        )
+    };
+
+    Message::Advice.format(
+        language,
+        &[
+            ("from", &format!("{from:.0}")),
+            ("to", &format!("{to:.0}")),
+            ("segment", &segment),
+            ("actions", &phrases),
+            ("time", &format!("{time_lost:+.2}")),
+        ],
+    )
+}
+
+fn describe_action(action: &Action, language: Language) -> String {
+    match *action {
+        Action::BrakePoint { distance } => {
+            let message = if distance < 0. {
+                Message::BrakeEarlier
+            } else {
+                Message::BrakeLater
+            };
+            message.format(language, &[("distance", &format!("{:.0}", distance.abs()))])
+        }
+        Action::Channel { channel, base } => {
+            let (more, level) = match base {
+                Base::Increase(level) => (true, level),
+                Base::Reduce(level) => (false, level),
+                Base::Stay => {
+                    return Message::ActionStay
+                        .format(language, &[("channel", &channel_name(channel, language))])
+                }
+            };
+            let message = match (channel, more) {
+                (Channel::Speed, true) => Message::MoreSpeed,
+                (Channel::Speed, false) => Message::LessSpeed,
+                (Channel::Throttle, true) => Message::MoreThrottle,
+                (Channel::Throttle, false) => Message::LessThrottle,
+                (Channel::Brake, true) => Message::BrakeHarder,
+                (Channel::Brake, false) => Message::BrakeGentler,
+                (Channel::Gear, true) => Message::HigherGear,
+                (Channel::Gear, false) => Message::LowerGear,
+                (Channel::SteeringWheelAngle, true) => Message::SteerLeft,
+                (Channel::SteeringWheelAngle, false) => Message::SteerRight,
+            };
+            let gender = channel_message(channel).gender(language);
+            message.format(
+                language,
+                &[(
+                    "intensity",
+                    &Message::intensity_before(level, language, gender),
+                )],
+            )
+        }
+    }
+}
+
+/// Name of `channel` in `language`, with its article, e.g. "the speed".
+#[must_use]
+pub fn channel_name(channel: Channel, language: Language) -> String {
+    channel_message(channel).text(language)
+}
+
+const fn channel_message(channel: Channel) -> Message {
+    match channel {
+        Channel::Speed => Message::Speed,
+        Channel::Throttle => Message::Throttle,
+        Channel::Brake => Message::Brake,
+        Channel::Gear => Message::Gear,
+        Channel::SteeringWheelAngle => Message::SteeringWheelAngle,
+    }
+}
+
+/// Suggestion for a single sample of `channel` from its tag, e.g.
+/// "You should increase the brake significantly, but it would be good to maintain it."
+#[must_use]
+pub fn describe_tag(channel: Channel, tag: Tag, language: Language) -> String {
+    let name = channel_name(channel, language);
+    let gender = channel_message(channel).gender(language);
+    match tag {
+        Tag::Single(Base::Stay) => Message::TagStay.format(language, &[("channel", &name)]),
+        Tag::Single(Base::Increase(_)) => {
+            Message::TagIncrease.format(language, &[("channel", &name)])
+        }
+        Tag::Single(Base::Reduce(_)) => Message::TagReduce.format(language, &[("channel", &name)]),
+        Tag::Tendency(base, tendency) => {
+            let action = match base {
+                Base::Stay => Message::ActionStay.format(language, &[("channel", &name)]),
+                Base::Increase(level) | Base::Reduce(level) => {
+                    let message = if matches!(base, Base::Increase(_)) {
+                        Message::ActionIncrease
+                    } else {
+                        Message::ActionReduce
+                    };
+                    message.format(
+                        language,
+                        &[
+                            ("channel", &name),
+                            ("intensity", &Message::intensity_after(level, language)),
+                        ],
+                    )
+                }
+            };
+            let tendency = match tendency {
+                Base::Stay => Message::TendencyStay.text_for(language, gender),
+                Base::Increase(level) => Message::TendencyIncrease.format_for(
+                    language,
+                    gender,
+                    &[("intensity", &Message::intensity_after(level, language))],
+                ),
+                Base::Reduce(level) => Message::TendencyReduce.format_for(
+                    language,
+                    gender,
+                    &[("intensity", &Message::intensity_after(level, language))],
+                ),
+            };
+            Message::TagTendency.format(language, &[("action", &action), ("tendency", &tendency)])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::domain::analysis::braking_zones::{BrakingPoints, BrakingZone, Deltas};
+    use crate::analysis::domain::analysis::tags::Tags;
+
+    /// A 400 m lap sampled every metre, `slower` times slower than 40 m/s between 100 m and
+    /// 200 m and between 300 m and 350 m.
+    fn test_variables(slower: f32) -> Variables {
+        let mut variables = Variables::default();
+        let mut time = 0.;
+        for i in 0..400 {
+            #[allow(clippy::cast_precision_loss)]
+            let d = i as f32;
+            let factor = if (100. ..200.).contains(&d) || (300. ..350.).contains(&d) {
+                slower
+            } else {
+                1.
+            };
+            time += factor / 40.;
+            variables.distance.push(d);
+            variables.lap_current_lap_time.push(time);
+        }
+        variables
+    }
+
+    fn test_segments() -> Segments {
+        Segments::from(vec![
+            Segment::straight(1, 0., 99.),
+            Segment::corner(1, 100., 150., 199.),
+            Segment::straight(2, 200., 299.),
+            Segment::corner(2, 300., 320., 399.),
+        ])
+    }
+
+    fn tags_between(from: usize, to: usize, tag: Tag) -> Tags {
+        (0..400)
+            .map(|i| {
+                if (from..to).contains(&i) {
+                    tag
+                } else {
+                    Tag::stay()
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn merges_channels_into_segment_advice() {
+        let clustering = ClustersMemberships {
+            speed_tags: tags_between(120, 180, Tag::increase()),
+            brake_tags: tags_between(110, 190, Tag::Tendency(Base::Reduce(1), Base::Stay)),
+            throttle_tags: tags_between(110, 115, Tag::increase()),
+            ..ClustersMemberships::default()
+        };
+
+        let coaching = coach(
+            &test_segments(),
+            &BrakingZones::default(),
+            &test_variables(1.),
+            &test_variables(1.5),
+            &clustering,
+        );
+
+        assert_eq!(coaching.len(), 1);
+        let advice = &coaching[0];
+        assert_eq!(advice.segment.as_deref(), Some("T1"));
+        assert!((advice.from - 110.).abs() < f32::EPSILON);
+        assert!((advice.to - 189.).abs() < f32::EPSILON);
+        assert_eq!(
+            advice.actions,
+            vec![
+                Action::Channel {
+                    channel: Channel::Speed,
+                    base: Base::Increase(0)
+                },
+                Action::Channel {
+                    channel: Channel::Brake,
+                    base: Base::Reduce(1)
+                },
+            ]
+        );
+        assert_eq!(
+            advice.message,
+            format!(
+                "Between 110 m and 189 m (T1), carry more speed and brake significantly more gently ({:+.2} s).",
+                advice.time_lost
+            )
+        );
+    }
+
+    #[test]
+    fn ranks_advice_by_time_lost() {
+        let mut speed_tags = tags_between(100, 200, Tag::increase());
+        speed_tags[300..350].fill(Tag::reduce());
+        let clustering = ClustersMemberships {
+            speed_tags,
+            ..ClustersMemberships::default()
+        };
+        let reference = test_variables(1.);
+        let target = test_variables(1.5);
+        let zone = BrakingZone {
+            corner: Some(2),
+            reference: BrakingPoints {
+                onset: 290.,
+                peak_pressure: 1.,
+                peak_distance: 292.,
+                release: 310.,
+                throttle_application: None,
+                full_throttle: None,
+            },
+            target: BrakingPoints {
+                onset: 270.,
+                peak_pressure: 1.,
+                peak_distance: 272.,
+                release: 310.,
+                throttle_application: None,
+                full_throttle: None,
+            },
+            deltas: Deltas {
+                onset: -20.,
+                peak_pressure: 0.,
+                release: 0.,
+                throttle_application: None,
+                full_throttle: None,
+                time: 0.1,
+            },
+        };
+
+        let coaching = coach(
+            &test_segments(),
+            &BrakingZones::from(vec![zone]),
+            &reference,
+            &target,
+            &clustering,
+        );
+
+        assert_eq!(coaching.len(), 2);
+        assert_eq!(coaching[0].segment.as_deref(), Some("T1"));
+        assert_eq!(coaching[1].segment.as_deref(), Some("T2"));
+        assert!(coaching[0].time_lost > coaching[1].time_lost);
+        assert!((coaching[1].from - 270.).abs() < f32::EPSILON);
+        assert!(coaching[1]
+            .message
+            .starts_with("Between 270 m and 349 m (T2), brake 20 m later and carry less speed"));
+    }
+
+    #[test]
+    fn focuses_on_inconsistent_sections() {
+        let mut coaching = Coaching::from(vec![
+            Advice::new(Some("T1".to_string()), 100., 199., vec![], 0.3),
+            Advice::new(Some("T2".to_string()), 300., 399., vec![], 0.2),
+            Advice::new(Some("T3".to_string()), 500., 599., vec![], 0.1),
+        ]);
+
+        coaching.focus(&[(350., 420.), (590., 650.)]);
+
+        let segments: Vec<&str> = coaching
+            .iter()
+            .filter_map(|a| a.segment.as_deref())
+            .collect();
+        assert_eq!(segments, vec!["T2", "T3", "T1"]);
+        assert!(coaching[0].inconsistent && coaching[1].inconsistent);
+        assert!(!coaching[2].inconsistent);
+    }
+
+    #[test]
+    fn localises_advice() {
+        let mut advice = Advice::new(
+            Some("T3".to_string()),
+            820.,
+            910.,
+            vec![
+                Action::BrakePoint { distance: 12. },
+                Action::Channel {
+                    channel: Channel::Speed,
+                    base: Base::Increase(2),
+                },
+            ],
+            0.24,
+        );
+
+        advice.localise(Language::Es);
+
+        assert_eq!(
+            advice.message,
+            "Entre 820 m y 910 m (T3), frena 12 m más tarde y lleva mucha más velocidad (+0.24 s)."
+        );
+    }
+
+    #[test]
+    fn describes_tags() {
+        assert_eq!(
+            describe_tag(Channel::Brake, Tag::reduce(), Language::En),
+            "You should reduce the brake."
+        );
+        assert_eq!(
+            describe_tag(
+                Channel::Speed,
+                Tag::Tendency(Base::Increase(1), Base::Stay),
+                Language::It
+            ),
+            "Dovresti aumentare la velocità notevolmente, ma sarebbe bene mantenerla così."
+        );
+        assert_eq!(
+            describe_tag(
+                Channel::Brake,
+                Tag::Tendency(Base::Stay, Base::Reduce(0)),
+                Language::Es
+            ),
+            "Deberías mantener el freno como está, pero sería bueno empezar a reducirlo."
+        );
+    }
+}
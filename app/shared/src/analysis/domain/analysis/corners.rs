@@ -0,0 +1,217 @@
+use crate::analysis::domain::analysis::braking_zones::{
+    detect as detect_braking_zones, throttle_reached, BrakingPoints,
+};
+use crate::analysis::domain::analysis::segment::Segment;
+use crate::analysis::domain::analysis::segments::Segments;
+use crate::analysis::domain::analysis::thresholds::THROTTLE_ON;
+use crate::lap::domain::lap::variables::Variables;
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::ops::Deref;
+
+/// Key points of a lap through one corner.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct CornerMetrics {
+    /// Distance where the driver starts braking for the corner (m), if braking at all.
+    pub braking_point: Option<f32>,
+    /// Minimum speed within the corner (m/s).
+    pub min_speed: f32,
+    /// Distance where the minimum speed is reached (m).
+    pub min_speed_distance: f32,
+    /// Distance where the driver gets back on the throttle after the minimum speed (m).
+    pub throttle_pickup: Option<f32>,
+    /// Speed at the corner exit (m/s).
+    pub exit_speed: f32,
+}
+
+/// Comparison of both laps of an analysis through one corner.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Corner {
+    pub segment: Segment,
+    pub reference: CornerMetrics,
+    pub target: CornerMetrics,
+}
+
+/// Represents the per corner comparison of an analysis, in track order.
+#[derive(Serialize, Deserialize, PartialEq, Default, Clone, Debug)]
+pub struct Corners {
+    corners: Vec<Corner>,
+}
+
+impl From<Vec<Corner>> for Corners {
+    /// Converts a vector of corners into `Corners`.
+    fn from(corners: Vec<Corner>) -> Self {
+        Self { corners }
+    }
+}
+
+impl Deref for Corners {
+    type Target = Vec<Corner>;
+
+    /// Implements dereferencing for `Corners`.
+    fn deref(&self) -> &Self::Target {
+        &self.corners
+    }
+}
+
+/// Computes the metrics of both laps through every corner of `segments`.
+///
+/// Both `reference` and `target` must be sampled on the same distances.
+#[must_use]
+pub fn calculate(segments: &Segments, reference: &Variables, target: &Variables) -> Corners {
+    let corners: Vec<&Segment> = segments.corners().collect();
+    let (reference_zones, target_zones) = (
+        detect_braking_zones(reference),
+        detect_braking_zones(target),
+    );
+
+    corners
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &segment)| {
+            // Braking for a corner can only start after the previous one
+            let search_from = i
+                .checked_sub(1)
+                .and_then(|previous| corners[previous].apex)
+                .unwrap_or(f32::NEG_INFINITY);
+            // Throttle pickup has to happen before the next corner
+            let search_to = corners.get(i + 1).map_or(f32::INFINITY, |next| next.entry);
+
+            Some(Corner {
+                segment: segment.clone(),
+                reference: CornerMetrics::calculate(
+                    reference,
+                    &reference_zones,
+                    segment,
+                    search_from,
+                    search_to,
+                )?,
+                target: CornerMetrics::calculate(
+                    target,
+                    &target_zones,
+                    segment,
+                    search_from,
+                    search_to,
+                )?,
+            })
+        })
+        .collect::<Vec<Corner>>()
+        .into()
+}
+
+impl CornerMetrics {
+    /// Computes the metrics of a lap through `corner`, given the braking zones of the lap.
+    ///
+    /// Returns `None` if the lap has no samples within the corner.
+    #[must_use]
+    pub fn calculate(
+        variables: &Variables,
+        braking_zones: &[BrakingPoints],
+        corner: &Segment,
+        search_from: f32,
+        search_to: f32,
+    ) -> Option<Self> {
+        let distances = &variables.distance;
+        let entry = distances.partition_point(|&d| d < corner.entry);
+        let exit = distances
+            .partition_point(|&d| d <= corner.exit)
+            .checked_sub(1)?;
+
+        let (min_idx, &min_speed) = variables
+            .speed
+            .get(entry..=exit)?
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+            .map(|(i, speed)| (entry + i, speed))?;
+
+        let to = distances.partition_point(|&d| d < search_to);
+
+        // The last braking zone starting between the previous apex and the minimum speed
+        let braking_point = braking_zones
+            .iter()
+            .rev()
+            .map(|zone| zone.onset)
+            .find(|&onset| onset >= search_from && onset <= distances[min_idx]);
+
+        let throttle_pickup =
+            throttle_reached(variables, min_idx..to.max(min_idx + 1), THROTTLE_ON);
+
+        Some(Self {
+            braking_point,
+            min_speed,
+            min_speed_distance: distances[min_idx],
+            throttle_pickup,
+            exit_speed: *variables.speed.get(exit)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 300 m lap sampled every metre, braking at `brake_at`, reaching `min_speed` at 150 m
+    /// and getting back on throttle at `throttle_at`.
+    fn test_variables(brake_at: f32, min_speed: f32, throttle_at: f32) -> Variables {
+        let mut variables = Variables::default();
+        for i in 0..300 {
+            #[allow(clippy::cast_precision_loss)]
+            let d = i as f32;
+            variables.distance.push(d);
+            variables.speed.push(min_speed + (d - 150.).abs() / 5.);
+            variables
+                .brake
+                .push(if d >= brake_at && d < 150. { 0.8 } else { 0. });
+            variables
+                .throttle
+                .push(if d >= throttle_at || d < brake_at {
+                    1.
+                } else {
+                    0.
+                });
+        }
+        variables
+    }
+
+    #[test]
+    fn calculates_corner_metrics_for_both_laps() {
+        let segments = Segments::from(vec![
+            Segment::straight(1, 0., 120.),
+            Segment::corner(1, 120., 150., 180.),
+            Segment::straight(2, 180., 299.),
+        ]);
+        let reference = test_variables(90., 20., 160.);
+        let target = test_variables(80., 18., 175.);
+
+        let corners = calculate(&segments, &reference, &target);
+
+        assert_eq!(corners.len(), 1);
+        let corner = &corners[0];
+        assert_eq!(corner.reference.braking_point, Some(90.));
+        assert_eq!(corner.target.braking_point, Some(80.));
+        assert!((corner.reference.min_speed - 20.).abs() < f32::EPSILON);
+        assert!((corner.target.min_speed_distance - 150.).abs() < f32::EPSILON);
+        assert_eq!(corner.reference.throttle_pickup, Some(160.));
+        assert_eq!(corner.target.throttle_pickup, Some(175.));
+        assert!((corner.reference.exit_speed - 26.).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn no_braking_point_when_brake_is_not_used() {
+        let mut variables = test_variables(90., 20., 160.);
+        variables.brake.iter_mut().for_each(|b| *b = 0.);
+
+        let metrics = CornerMetrics::calculate(
+            &variables,
+            &detect_braking_zones(&variables),
+            &Segment::corner(1, 120., 150., 180.),
+            f32::NEG_INFINITY,
+            f32::INFINITY,
+        )
+        .unwrap();
+
+        assert_eq!(metrics.braking_point, None);
+    }
+}
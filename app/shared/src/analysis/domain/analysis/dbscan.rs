@@ -0,0 +1,193 @@
+use crate::analysis::domain::analysis::clustering_model::{ClusteringModel, Error};
+use crate::analysis::domain::analysis::fuzzy_c_means::{FittedModel, FuzzyCMeans};
+use crate::analysis::domain::analysis::k_means::{nearest, one_hot};
+
+use ndarray::Array2;
+
+/// Density-based clustering (DBSCAN).
+///
+/// The number of clusters is not fixed in advance. Since `TagAssigner` needs every sample to
+/// belong to a cluster, noise samples are assigned to the cluster with the closest centroid.
+/// Neighbourhoods are searched on the samples sorted by their first feature, so the cost only
+/// grows with the square of the number of samples when they all lie within `eps` on it.
+pub struct Dbscan {
+    eps: f64,
+    min_samples: usize,
+}
+
+impl Dbscan {
+    /// Creates a new DBSCAN model.
+    ///
+    /// # Arguments
+    ///
+    /// * `eps` - Maximum distance between two samples to consider them neighbours.
+    /// * `min_samples` - Minimum number of neighbours, including itself, of a core sample.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidParameter` if `eps` is not positive or `min_samples` is 0.
+    pub fn try_new(eps: f64, min_samples: usize) -> Result<Self, Error> {
+        if eps <= 0. || eps.is_nan() {
+            return Err(Error::InvalidParameter(
+                "eps must be greater than 0".to_string(),
+            ));
+        }
+        if min_samples == 0 {
+            return Err(Error::InvalidParameter(
+                "min_samples must be greater than 0".to_string(),
+            ));
+        }
+
+        Ok(Self { eps, min_samples })
+    }
+
+    fn neighbours(&self, data: &Array2<f64>, index: &SortedSamples, i: usize) -> Vec<usize> {
+        let sample = data.row(i);
+        // Samples further than `eps` on the first feature are further than `eps` overall
+        let key = SortedSamples::key(data, i);
+        let from = index.keys.partition_point(|&k| k < key - self.eps);
+        let to = index.keys.partition_point(|&k| k <= key + self.eps);
+
+        index.order[from..to]
+            .iter()
+            .copied()
+            .filter(|&j| FuzzyCMeans::distance(&sample, &data.row(j)) <= self.eps)
+            .collect()
+    }
+
+    /// Cluster of each sample, `None` for noise.
+    fn labels(&self, data: &Array2<f64>) -> (Vec<Option<usize>>, usize) {
+        let num_samples = data.nrows();
+        let index = SortedSamples::new(data);
+        let mut labels: Vec<Option<usize>> = vec![None; num_samples];
+        let mut visited = vec![false; num_samples];
+        let mut clusters = 0;
+
+        for i in 0..num_samples {
+            if visited[i] {
+                continue;
+            }
+            visited[i] = true;
+
+            let neighbours = self.neighbours(data, &index, i);
+            if neighbours.len() < self.min_samples {
+                continue;
+            }
+
+            labels[i] = Some(clusters);
+            let mut queue = neighbours;
+            while let Some(j) = queue.pop() {
+                if labels[j].is_none() {
+                    labels[j] = Some(clusters);
+                }
+                if visited[j] {
+                    continue;
+                }
+                visited[j] = true;
+
+                let neighbours = self.neighbours(data, &index, j);
+                if neighbours.len() >= self.min_samples {
+                    queue.extend(neighbours);
+                }
+            }
+            clusters += 1;
+        }
+
+        (labels, clusters)
+    }
+}
+
+/// Indices of the samples sorted by their first feature.
+struct SortedSamples {
+    order: Vec<usize>,
+    keys: Vec<f64>,
+}
+
+impl SortedSamples {
+    fn new(data: &Array2<f64>) -> Self {
+        let mut order: Vec<usize> = (0..data.nrows()).collect();
+        order.sort_by(|&a, &b| Self::key(data, a).total_cmp(&Self::key(data, b)));
+        let keys = order.iter().map(|&i| Self::key(data, i)).collect();
+        Self { order, keys }
+    }
+
+    /// First feature of the `i`th sample, or 0 for samples without features.
+    fn key(data: &Array2<f64>, i: usize) -> f64 {
+        data.get((i, 0)).copied().unwrap_or_default()
+    }
+}
+
+impl ClusteringModel for Dbscan {
+    fn try_fit(&self, data: &Array2<f64>) -> Result<FittedModel, Error> {
+        let (labels, clusters) = self.labels(data);
+        if clusters == 0 {
+            return Err(Error::NoClusterFound);
+        }
+
+        let mut centroids = Array2::<f64>::zeros((clusters, data.ncols()));
+        let mut counts = vec![0usize; clusters];
+        for (label, sample) in labels.iter().zip(data.outer_iter()) {
+            if let Some(label) = *label {
+                let mut centroid = centroids.row_mut(label);
+                centroid += &sample;
+                counts[label] += 1;
+            }
+        }
+        for (mut centroid, &count) in centroids.outer_iter_mut().zip(&counts) {
+            #[allow(clippy::cast_precision_loss)]
+            let count = count as f64;
+            centroid /= count;
+        }
+
+        let labels: Vec<usize> = labels
+            .iter()
+            .zip(data.outer_iter())
+            .map(|(label, sample)| label.unwrap_or_else(|| nearest(&sample, &centroids)))
+            .collect();
+
+        Ok(FittedModel::new(centroids, one_hot(&labels, clusters)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn finds_dense_groups_and_assigns_noise() {
+        let data = array![[0.0], [0.1], [0.2], [3.0], [10.0], [10.1], [10.2]];
+
+        let model = Dbscan::try_new(0.15, 2).unwrap().try_fit(&data).unwrap();
+
+        assert_eq!(model.centroids().nrows(), 2);
+        let memberships = model.memberships();
+        assert_eq!(memberships.row(0), memberships.row(2));
+        // Noise at 3.0 belongs to the closest cluster
+        assert_eq!(memberships.row(3), memberships.row(0));
+        assert_ne!(memberships.row(0), memberships.row(6));
+    }
+
+    #[test]
+    fn neighbours_are_close_on_every_feature() {
+        // Close on the first feature but not on the second one
+        let data = array![[0.1, 5.0], [0.0, 0.0], [0.1, 5.1], [0.05, 0.05]];
+
+        let model = Dbscan::try_new(0.2, 2).unwrap().try_fit(&data).unwrap();
+
+        assert_eq!(model.centroids().nrows(), 2);
+        let memberships = model.memberships();
+        assert_eq!(memberships.row(1), memberships.row(3));
+        assert_eq!(memberships.row(0), memberships.row(2));
+        assert_ne!(memberships.row(0), memberships.row(1));
+    }
+
+    #[test]
+    fn fails_when_everything_is_noise() {
+        let data = array![[0.0], [5.0], [10.0]];
+
+        let result = Dbscan::try_new(1., 2).unwrap().try_fit(&data);
+
+        assert_eq!(result.unwrap_err(), Error::NoClusterFound);
+    }
+}
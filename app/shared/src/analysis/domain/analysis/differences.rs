@@ -1,6 +1,10 @@
 use crate::lap::domain::lap::variables::Variables;
 use std::ops::Sub;
 
+/// Computes the difference of every channel of both laps, sample by sample.
+///
+/// Latitude and longitude are left empty, as differences in degrees are not comparable along
+/// the circuit. The offsets between both lines are measured in metres by the racing line.
 #[must_use]
 pub fn calculate(ref_variables: &Variables, target_variables: &Variables) -> Variables {
     Variables {
@@ -16,8 +20,8 @@ pub fn calculate(ref_variables: &Variables, target_variables: &Variables) -> Var
             &target_variables.distance_pct,
         ),
         track_temp: calculate_difference(&ref_variables.track_temp, &target_variables.track_temp),
-        latitude: calculate_difference(&ref_variables.latitude, &target_variables.latitude),
-        longitude: calculate_difference(&ref_variables.longitude, &target_variables.longitude),
+        latitude: vec![],
+        longitude: vec![],
         altitude: calculate_difference(&ref_variables.altitude, &target_variables.altitude),
         steering_wheel_angle: calculate_difference(
             &ref_variables.steering_wheel_angle,
@@ -28,6 +32,13 @@ pub fn calculate(ref_variables: &Variables, target_variables: &Variables) -> Var
             &ref_variables.lap_current_lap_time,
             &target_variables.lap_current_lap_time,
         ),
+        lat_accel: calculate_difference(&ref_variables.lat_accel, &target_variables.lat_accel),
+        long_accel: calculate_difference(&ref_variables.long_accel, &target_variables.long_accel),
+        yaw_rate: calculate_difference(&ref_variables.yaw_rate, &target_variables.yaw_rate),
+        lf_speed: calculate_difference(&ref_variables.lf_speed, &target_variables.lf_speed),
+        rf_speed: calculate_difference(&ref_variables.rf_speed, &target_variables.rf_speed),
+        lr_speed: calculate_difference(&ref_variables.lr_speed, &target_variables.lr_speed),
+        rr_speed: calculate_difference(&ref_variables.rr_speed, &target_variables.rr_speed),
     }
 }
 
@@ -37,3 +48,28 @@ where
 {
     vec1.iter().zip(vec2).map(|(&v1, &v2)| v1 - v2).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions_are_not_subtracted() {
+        let reference = Variables {
+            latitude: vec![45., 45.],
+            longitude: vec![7., 7.],
+            ..Variables::default()
+        };
+        // One thousandth of a degree to the north and to the east
+        let target = Variables {
+            latitude: vec![45., 45.001],
+            longitude: vec![7., 7.001],
+            ..Variables::default()
+        };
+
+        let differences = calculate(&reference, &target);
+
+        assert!(differences.latitude.is_empty());
+        assert!(differences.longitude.is_empty());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::lap::domain::lap::variables::Variables;
 use crate::lap::domain::lap::Lap;
 
 #[must_use]
@@ -16,3 +17,62 @@ pub fn generate_union(lap1: &Lap, lap2: &Lap) -> Vec<f32> {
     distances.dedup();
     distances
 }
+
+/// Union distance where the reference lap passes the point of the circuit that the target lap
+/// passes at `distance`, matching the lap fraction of both laps.
+///
+/// Both laps cover the same circuit with lines of different length, so the same distance of
+/// both laps is not the same point. Both `reference` and `target` must be interpolated on
+/// `union_distances`. Returns `None` if they have no lap fraction.
+#[must_use]
+pub fn to_reference(
+    union_distances: &[f32],
+    reference: &Variables,
+    target: &Variables,
+    distance: f32,
+) -> Option<f32> {
+    let last = union_distances.len().checked_sub(1)?;
+    let i = union_distances.partition_point(|&d| d < distance).min(last);
+    let pct = *target.distance_pct.get(i)?;
+
+    let reference_pct = reference.distance_pct.get(..=last)?;
+    let j = reference_pct.partition_point(|&p| p < pct).min(last);
+    let nearest = if j > 0 && (reference_pct[j - 1] - pct).abs() < (reference_pct[j] - pct).abs() {
+        j - 1
+    } else {
+        j
+    };
+    union_distances.get(nearest).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_target_distances_by_lap_fraction() {
+        let union_distances: Vec<f32> = (0..=10u8).map(|d| f32::from(d) * 100.).collect();
+        // The reference lap is 1000 m long and the target lap 800 m long
+        let reference = Variables {
+            distance_pct: union_distances.iter().map(|d| d / 1000.).collect(),
+            ..Variables::default()
+        };
+        let target = Variables {
+            distance_pct: union_distances.iter().map(|d| (d / 800.).min(1.)).collect(),
+            ..Variables::default()
+        };
+
+        assert_eq!(
+            to_reference(&union_distances, &reference, &target, 400.),
+            Some(500.)
+        );
+        assert_eq!(
+            to_reference(&union_distances, &reference, &target, 0.),
+            Some(0.)
+        );
+        assert_eq!(
+            to_reference(&union_distances, &Variables::default(), &target, 400.),
+            None
+        );
+    }
+}
@@ -1,5 +1,14 @@
+use crate::analysis::domain::analysis::clustering_model::{Algorithm, ClusteringModel};
+use crate::analysis::domain::analysis::dbscan::Dbscan;
 use crate::analysis::domain::analysis::fuzzy_c_means::{FittedModel, FuzzyCMeans};
+use crate::analysis::domain::analysis::gaussian_mixture::GaussianMixture;
+use crate::analysis::domain::analysis::joint_clusters::JointConfig;
+use crate::analysis::domain::analysis::k_means::KMeans;
+use crate::analysis::domain::analysis::tag_policy::ChannelTagPolicy;
+use crate::analysis::domain::analysis::validity::{Criterion, ValidityIndices};
 use ndarray::Array2;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 /// Configuration for the FCM (Fuzzy C-Means) grid search.
 #[derive(Clone)]
@@ -12,6 +21,19 @@ pub struct Config {
     pub max_iter: (usize, Option<usize>, Option<usize>),
     /// Convergence error (`error`), with optional max value and increment.
     pub error: (f64, Option<f64>, Option<f64>),
+    /// Channels to cluster together, in addition to the per channel clustering.
+    pub joint: Option<JointConfig>,
+    /// Clustering algorithm whose parameters are searched.
+    pub algorithm: Algorithm,
+    /// Validity index used to choose the best model, or `None` to use the default one of the
+    /// algorithm.
+    pub criterion: Option<Criterion>,
+    /// Seed of the initialisation of every model. Runs with the same seed give the same
+    /// clusters, while `None` gives a different initialisation on every run.
+    pub seed: Option<u64>,
+    /// Policies used to tag the clusters of each channel. Channels without a policy use the
+    /// default one.
+    pub tag_policies: Vec<ChannelTagPolicy>,
 }
 
 impl Config {
@@ -39,10 +61,61 @@ impl Config {
             m,
             max_iter,
             error,
+            joint: None,
+            algorithm: Algorithm::FuzzyCMeans,
+            criterion: None,
+            seed: None,
+            tag_policies: Vec::new(),
         }
     }
+
+    /// Sets the policies used to tag the clusters of each channel.
+    #[must_use]
+    pub fn with_tag_policies(mut self, tag_policies: Vec<ChannelTagPolicy>) -> Self {
+        self.tag_policies = tag_policies;
+        self
+    }
+
+    /// Seeds the initialisation of every model of the grid.
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Selects the validity index used to choose the best model.
+    #[must_use]
+    pub const fn with_criterion(mut self, criterion: Criterion) -> Self {
+        self.criterion = Some(criterion);
+        self
+    }
+
+    /// Validity index used to choose the best model: the configured one, or the default one
+    /// of the algorithm.
+    #[must_use]
+    pub fn criterion(&self) -> Criterion {
+        self.criterion
+            .unwrap_or_else(|| self.algorithm.default_criterion())
+    }
+
+    /// Selects the clustering algorithm. Parameters that do not apply to it are ignored.
+    #[must_use]
+    pub fn with_algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Enables the joint clustering of several channels.
+    #[must_use]
+    pub fn with_joint(mut self, joint: JointConfig) -> Self {
+        self.joint = Some(joint);
+        self
+    }
 }
 
+/// Model of the grid along with the fuzziness exponent used to compute its validity indices.
+type Candidate = (Box<dyn ClusteringModel>, f64);
+
 /// FCM (Fuzzy C-Means) grid search for finding the best model configuration.
 pub struct FcmGrid {
     config: Config,
@@ -63,7 +136,10 @@ impl FcmGrid {
         Self { config }
     }
 
-    /// Performs the grid search and returns the best fitted model.
+    /// Performs the grid search and returns the best fitted model according to the configured
+    /// criterion, with its validity indices attached.
+    ///
+    /// With the `parallel` feature, the models of the grid are fitted on the rayon thread pool.
     ///
     /// # Arguments
     ///
@@ -79,29 +155,101 @@ impl FcmGrid {
     /// * `Error::FittingModel` - If there is an error fitting the model.
     /// * `Error::NoValidModelFound` - If no valid model is found during the grid search.
     pub fn get_best_fitted_model(&self, data: &Array2<f64>) -> Result<FittedModel, Error> {
+        let fit = |(model, m): Candidate| {
+            let model_result = model
+                .try_fit(data)
+                .map_err(|e| Error::FittingModel(e.to_string()))?;
+            let validity = ValidityIndices::calculate(data, &model_result, m);
+            Ok(model_result.with_validity(validity))
+        };
+
+        #[cfg(feature = "parallel")]
+        let fitted: Vec<Result<FittedModel, Error>> =
+            self.models()?.into_par_iter().map(fit).collect();
+        #[cfg(not(feature = "parallel"))]
+        let fitted: Vec<Result<FittedModel, Error>> = self.models()?.into_iter().map(fit).collect();
+
+        // Models are compared in grid order, so the result does not depend on scheduling
+        let criterion = self.config.criterion();
         let mut best_model: Option<FittedModel> = None;
+        for model_result in fitted {
+            let model_result = model_result?;
+            if best_model.as_ref().is_none_or(|best| {
+                best.validity().is_none_or(|best| {
+                    model_result
+                        .validity()
+                        .is_some_and(|validity| criterion.is_better(validity, best))
+                })
+            }) {
+                best_model = Some(model_result);
+            }
+        }
+
+        best_model.ok_or(Error::NoValidModelFound)
+    }
+
+    /// Builds every model of the grid for the configured algorithm.
+    fn models(&self) -> Result<Vec<Candidate>, Error> {
+        let mut models: Vec<Candidate> = Vec::new();
+        let creating = |e: &dyn std::error::Error| Error::CreatingModel(e.to_string());
+        let seed = self.config.seed;
+
+        if let Algorithm::Dbscan { eps, min_samples } = self.config.algorithm {
+            models.push((
+                Box::new(Dbscan::try_new(eps, min_samples).map_err(|e| creating(&e))?),
+                1.,
+            ));
+            return Ok(models);
+        }
 
         for c in Self::range(self.config.c) {
-            for m in Self::range_f64(self.config.m) {
-                for max_iter in Self::range(self.config.max_iter) {
-                    for error in Self::range_f64(self.config.error) {
-                        let model_result = FuzzyCMeans::try_new(c, m, max_iter, error)
-                            .map_err(|e| Error::CreatingModel(e.to_string()))?
-                            .try_fit(data)
-                            .map_err(|e| Error::FittingModel(e.to_string()))?;
-
-                        if best_model
-                            .as_ref()
-                            .map_or(true, |m| m.fpc() < model_result.fpc())
-                        {
-                            best_model = Some(model_result);
+            for max_iter in Self::range(self.config.max_iter) {
+                for error in Self::range_f64(self.config.error) {
+                    match self.config.algorithm {
+                        Algorithm::FuzzyCMeans => {
+                            for m in Self::range_f64(self.config.m) {
+                                models.push((
+                                    Box::new(
+                                        FuzzyCMeans::try_new(c, m, max_iter, error)
+                                            .map(|model| match seed {
+                                                Some(seed) => model.with_seed(seed),
+                                                None => model,
+                                            })
+                                            .map_err(|e| creating(&e))?,
+                                    ),
+                                    m,
+                                ));
+                            }
                         }
+                        Algorithm::KMeans => models.push((
+                            Box::new(
+                                KMeans::try_new(c, max_iter, error)
+                                    .map(|model| match seed {
+                                        Some(seed) => model.with_seed(seed),
+                                        None => model,
+                                    })
+                                    .map_err(|e| creating(&e))?,
+                            ),
+                            1.,
+                        )),
+                        Algorithm::GaussianMixture => models.push((
+                            Box::new(
+                                GaussianMixture::try_new(c, max_iter, error)
+                                    .map(|model| match seed {
+                                        Some(seed) => model.with_seed(seed),
+                                        None => model,
+                                    })
+                                    .map_err(|e| creating(&e))?,
+                            ),
+                            1.,
+                        )),
+                        Algorithm::Dbscan { .. } => (),
                     }
                 }
             }
         }
 
-        best_model.ok_or(Error::NoValidModelFound)
+        Ok(models)
     }
 
     fn range(param: (usize, Option<usize>, Option<usize>)) -> Box<dyn Iterator<Item = usize>> {
@@ -194,4 +342,76 @@ mod tests {
             Error::CreatingModel("Fuzziness factor must be greater than 1".to_string())
         );
     }
+
+    #[test]
+    fn test_fcm_grid_with_other_algorithms() {
+        let data = array![[1.0], [1.1], [1.2], [5.0], [5.1], [5.2]];
+
+        for algorithm in [
+            Algorithm::KMeans,
+            Algorithm::GaussianMixture,
+            Algorithm::Dbscan {
+                eps: 0.15,
+                min_samples: 2,
+            },
+        ] {
+            let config = Config::new(
+                (2, None, None),
+                (2.0, None, None),
+                (100, None, None),
+                (1e-6, None, None),
+            )
+            .with_algorithm(algorithm.clone());
+            // Every crisp partition has a partition coefficient of 1
+            assert_eq!(config.criterion(), Criterion::Silhouette, "{algorithm:?}");
+
+            let model = FcmGrid::new(config).get_best_fitted_model(&data);
+            assert!(model.is_ok(), "{algorithm:?}");
+            assert_eq!(model.unwrap().centroids().nrows(), 2, "{algorithm:?}");
+        }
+    }
+
+    #[test]
+    fn test_fcm_grid_is_reproducible_with_seed() {
+        let config = Config::new(
+            (3, None, None),
+            (2.0, None, None),
+            (100, None, None),
+            (1e-6, None, None),
+        )
+        .with_seed(42);
+        let data = array![[1.0], [1.5], [2.0], [4.0], [4.5], [7.0], [7.2], [9.0]];
+
+        let first = FcmGrid::new(config.clone())
+            .get_best_fitted_model(&data)
+            .unwrap();
+        let second = FcmGrid::new(config).get_best_fitted_model(&data).unwrap();
+
+        assert_eq!(first.centroids(), second.centroids());
+        assert_eq!(first.memberships(), second.memberships());
+    }
+
+    #[test]
+    fn test_fcm_grid_with_criterion() {
+        let config = Config::new(
+            (2, Some(4), Some(1)),
+            (2.0, None, None),
+            (100, None, None),
+            (1e-6, None, None),
+        )
+        .with_algorithm(Algorithm::KMeans)
+        .with_criterion(Criterion::Silhouette);
+        let data = array![[1.0], [1.1], [1.2], [5.0], [5.1], [5.2]];
+
+        let model = FcmGrid::new(config).get_best_fitted_model(&data).unwrap();
+
+        // The simplified silhouette may prefer splitting a group into singletons, so only
+        // check that no cluster mixes samples of both groups
+        let cluster = |i: usize| {
+            let row = model.memberships().row(i);
+            (0..row.len()).max_by(|&a, &b| row[a].total_cmp(&row[b]))
+        };
+        assert!((0..3).all(|i| (3..6).all(|j| cluster(i) != cluster(j))));
+        assert!(model.validity().is_some_and(|v| v.silhouette > 0.9));
+    }
 }
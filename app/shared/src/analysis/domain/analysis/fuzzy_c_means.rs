@@ -1,12 +1,17 @@
-use ndarray::{Array, Array1, Array2, ArrayView, Axis, Ix1, Zip};
-use ndarray_rand::{rand_distr::Uniform, RandomExt};
-use rand::thread_rng;
+use crate::analysis::domain::analysis::clustering_model::{
+    seeded_rng, ClusteringModel, Error as ClusteringError,
+};
+use crate::analysis::domain::analysis::k_means::k_means_plus_plus;
+use crate::analysis::domain::analysis::validity::ValidityIndices;
+
+use ndarray::{Array, Array2, ArrayView, Axis, Ix1, Zip};
 
 #[derive(Clone, Debug)]
 pub struct FittedModel {
     centroids: Array2<f64>,
     memberships: Array2<f64>,
     fpc: f64,
+    validity: Option<ValidityIndices>,
 }
 
 impl FittedModel {
@@ -29,9 +34,17 @@ impl FittedModel {
             centroids,
             memberships,
             fpc,
+            validity: None,
         }
     }
 
+    /// Attaches the validity indices computed when the model was selected.
+    #[must_use]
+    pub const fn with_validity(mut self, validity: ValidityIndices) -> Self {
+        self.validity = Some(validity);
+        self
+    }
+
     #[must_use]
     pub const fn centroids(&self) -> &Array2<f64> {
         &self.centroids
@@ -46,6 +59,11 @@ impl FittedModel {
     pub const fn fpc(&self) -> f64 {
         self.fpc
     }
+
+    #[must_use]
+    pub const fn validity(&self) -> Option<&ValidityIndices> {
+        self.validity.as_ref()
+    }
 }
 
 /// A struct representing the Fuzzy C-Means algorithm.
@@ -54,6 +72,7 @@ pub struct FuzzyCMeans {
     m: f64,
     max_iter: usize,
     error: f64,
+    seed: Option<u64>,
 }
 
 impl FuzzyCMeans {
@@ -103,21 +122,31 @@ impl FuzzyCMeans {
             m,
             max_iter,
             error,
+            seed: None,
         })
     }
 
-    /// Fits the Fuzzy C-Means model to the provided data by generating an initial random
-    /// membership matrix.
+    /// Seeds the initialisation, so fitting the same data always gives the same model.
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Fits the Fuzzy C-Means model to the provided data, initialising the membership matrix
+    /// k-means++ style.
     ///
-    /// This function initializes the clustering process by generating a random membership
-    /// matrix and then calling `try_fit_with_memberships` to iteratively update the cluster
-    /// centers (centroids) and the membership matrix until the algorithm converges or the
-    /// maximum number of iterations is reached.
+    /// This function initializes the clustering process by choosing `c` samples as initial
+    /// centroids, each one with a probability proportional to its squared distance to the
+    /// centroids already chosen, and computing the memberships to them. It then calls
+    /// `try_fit_with_memberships` to iteratively update the cluster centers (centroids) and the
+    /// membership matrix until the algorithm converges or the maximum number of iterations is
+    /// reached.
     ///
     /// # Arguments
     ///
     /// * `data` - A 2D array containing the data points to be clustered. Each row represents
-    ///            a data point, and each column represents a feature.
+    ///   a data point, and each column represents a feature.
     ///
     /// # Returns
     ///
@@ -148,8 +177,7 @@ impl FuzzyCMeans {
     /// }
     /// ```
     pub fn try_fit(&self, data: &Array2<f64>) -> Result<FittedModel, Error> {
-        let num_samples = data.shape()[0];
-        let mut initial_memberships = self.generate_random_memberships(num_samples);
+        let mut initial_memberships = self.generate_initial_memberships(data);
         self.try_fit_with_memberships(data, &mut initial_memberships)
     }
 
@@ -161,11 +189,11 @@ impl FuzzyCMeans {
     /// # Arguments
     ///
     /// * `data` - A 2D array containing the data points to be clustered. Each row represents
-    ///            a data point, and each column represents a feature.
+    ///   a data point, and each column represents a feature.
     /// * `memberships` - A mutable reference to a 2D array representing the membership matrix,
-    ///                   where each entry denotes the degree of membership of a data point
-    ///                   to a particular cluster. The number of rows should match the number
-    ///                   of data points in `data`.
+    ///   where each entry denotes the degree of membership of a data point
+    ///   to a particular cluster. The number of rows should match the number
+    ///   of data points in `data`.
     ///
     /// # Returns
     ///
@@ -229,26 +257,13 @@ impl FuzzyCMeans {
         Ok(FittedModel::new(centroids, memberships.to_owned()))
     }
 
-    fn generate_random_memberships(&self, num_samples: usize) -> Array2<f64> {
-        // Initialize memberships randomly
-        let mut memberships = {
-            let mut rng = thread_rng();
-            Array2::<f64>::random_using((num_samples, self.c), Uniform::new(0., 1.), &mut rng)
-        };
-
-        // Normalize memberships
-        for mut membership in memberships.outer_iter_mut() {
-            let sum: f64 = membership.sum();
-            if sum == 0.0 {
-                let len = membership.len();
-                #[allow(clippy::cast_precision_loss)]
-                membership.assign(&(Array1::ones(len) / len as f64));
-            } else {
-                membership /= sum;
-            }
+    fn generate_initial_memberships(&self, data: &Array2<f64>) -> Array2<f64> {
+        if data.nrows() == 0 {
+            return Array2::zeros((0, self.c));
         }
 
-        memberships
+        let centroids = k_means_plus_plus(data, self.c, &mut seeded_rng(self.seed));
+        self.update_memberships(data, &centroids)
     }
 
     fn update_cluster_centers(&self, data: &Array2<f64>, memberships: &Array2<f64>) -> Array2<f64> {
@@ -261,31 +276,45 @@ impl FuzzyCMeans {
         numerator / &denominator.mapv(|x| if x == 0. { f64::EPSILON } else { x })
     }
 
+    /// Memberships of every sample to every centroid:
+    /// `u_ij = 1 / sum_k (d_ij / d_ik) ^ (2 / (m - 1))`.
+    ///
+    /// The ratios are computed against the distance to the closest centroid of each sample,
+    /// `(d_min / d_ij) ^ (2 / (m - 1))`, which are all within `[0, 1]` and then normalised,
+    /// so no power can overflow. A sample lying on one or more centroids belongs only to them.
     fn update_memberships(&self, data: &Array2<f64>, centroids: &Array2<f64>) -> Array2<f64> {
-        let num_samples = data.shape()[0];
-        let mut new_memberships = Array2::<f64>::zeros((num_samples, self.c));
-
-        for (i, sample) in data.axis_iter(Axis(0)).enumerate() {
-            for (j, centroid) in centroids.axis_iter(Axis(0)).enumerate() {
-                let numerator = Self::distance(&sample, &centroid); // d_ij
-                let u_ij: f64 = 1.
-                    / (centroids
-                        .axis_iter(Axis(0))
-                        .map(|k| {
-                            let denominator = Self::distance(&sample, &k); // d_ik
-                            let denominator = if denominator == 0. {
-                                f64::EPSILON
-                            } else {
-                                denominator
-                            };
-                            (numerator / denominator).powf(2. / (self.m - 1.))
-                        })
-                        .sum::<f64>());
-                new_memberships[[i, j]] = u_ij;
-            }
-        }
+        let distances = Self::distances(data, centroids);
+        let exponent = 2. / (self.m - 1.);
+
+        let closest = distances
+            .fold_axis(Axis(1), f64::INFINITY, |&a, &b| a.min(b))
+            .insert_axis(Axis(1));
+        let ratios = Zip::from(&distances)
+            .and_broadcast(&closest)
+            .map_collect(|&d, &closest| {
+                if closest == 0. {
+                    if d == 0. {
+                        1.
+                    } else {
+                        0.
+                    }
+                } else {
+                    (closest / d).powf(exponent)
+                }
+            });
+
+        let sums = ratios.sum_axis(Axis(1)).insert_axis(Axis(1));
+        ratios / &sums
+    }
 
-        new_memberships
+    /// Matrix of euclidean distances between every sample (rows) and every centroid (columns).
+    fn distances(data: &Array2<f64>, centroids: &Array2<f64>) -> Array2<f64> {
+        let differences =
+            &data.view().insert_axis(Axis(1)) - &centroids.view().insert_axis(Axis(0));
+        differences
+            .mapv(|x| x * x)
+            .sum_axis(Axis(2))
+            .mapv(f64::sqrt)
     }
 
     #[must_use]
@@ -300,6 +329,12 @@ impl FuzzyCMeans {
     }
 }
 
+impl ClusteringModel for FuzzyCMeans {
+    fn try_fit(&self, data: &Array2<f64>) -> Result<FittedModel, ClusteringError> {
+        Self::try_fit(self, data).map_err(|e| ClusteringError::Fitting(e.to_string()))
+    }
+}
+
 #[derive(PartialEq, Eq, Debug, thiserror::Error)]
 pub enum Error {
     #[error("Fuzziness factor must be greater than 1")]
@@ -452,4 +487,21 @@ mod tests {
         // Verify that the error matches the expected error
         assert!(result.is_err_and(|e| e == expected_error));
     }
+
+    #[test]
+    fn samples_on_centroids_belong_only_to_them() {
+        let fcm =
+            FuzzyCMeans::try_new(3, 2., 1, 0.01).unwrap_or_else(|e| panic!("Test Failed: {e}"));
+
+        let data = array![[1.], [3.], [2.]];
+        let centroids = array![[1.], [1.], [3.]];
+
+        let memberships = fcm.update_memberships(&data, &centroids);
+
+        assert_array_eq(
+            &array![[0.5, 0.5, 0.], [0., 0., 1.], [1. / 3., 1. / 3., 1. / 3.]],
+            &memberships,
+            EPSILON,
+        );
+    }
 }
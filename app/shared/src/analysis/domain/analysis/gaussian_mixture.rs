@@ -0,0 +1,191 @@
+use crate::analysis::domain::analysis::clustering_model::{seeded_rng, ClusteringModel, Error};
+use crate::analysis::domain::analysis::fuzzy_c_means::FittedModel;
+use crate::analysis::domain::analysis::k_means::k_means_plus_plus;
+
+use ndarray::{Array1, Array2, Axis};
+
+/// Lower bound of every variance, to keep components from collapsing onto a single sample.
+const MIN_VARIANCE: f64 = 1e-6;
+
+/// Gaussian mixture model with diagonal covariances, fitted with expectation-maximisation.
+///
+/// Means are initialised with k-means++. The responsibilities of the components are returned
+/// as memberships and their means as centroids.
+pub struct GaussianMixture {
+    c: usize,
+    max_iter: usize,
+    error: f64,
+    seed: Option<u64>,
+}
+
+impl GaussianMixture {
+    /// Creates a new Gaussian mixture model.
+    ///
+    /// # Arguments
+    ///
+    /// * `c` - Number of components. Must be greater than 0.
+    /// * `max_iter` - Maximum number of EM iterations.
+    /// * `error` - Minimum improvement of the mean log-likelihood to keep iterating.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidParameter` if `c` is 0.
+    pub fn try_new(c: usize, max_iter: usize, error: f64) -> Result<Self, Error> {
+        if c == 0 {
+            return Err(Error::InvalidParameter(
+                "number of components must be greater than 0".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            c,
+            max_iter,
+            error,
+            seed: None,
+        })
+    }
+
+    /// Seeds the initialisation, so fitting the same data always gives the same model.
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// E-step: responsibilities of every component for every sample, and the mean
+    /// log-likelihood of the data.
+    fn expectation(
+        data: &Array2<f64>,
+        weights: &Array1<f64>,
+        means: &Array2<f64>,
+        variances: &Array2<f64>,
+    ) -> (Array2<f64>, f64) {
+        let mut responsibilities = Array2::<f64>::zeros((data.nrows(), weights.len()));
+        let mut log_likelihood = 0.;
+
+        for (sample, mut row) in data.outer_iter().zip(responsibilities.outer_iter_mut()) {
+            // Work in log space to avoid underflows far from every component
+            let log_densities: Vec<f64> = (0..weights.len())
+                .map(|k| {
+                    let log_density: f64 = sample
+                        .iter()
+                        .zip(means.row(k))
+                        .zip(variances.row(k))
+                        .map(|((x, mean), variance)| {
+                            -0.5 * ((x - mean).powi(2) / variance
+                                + (std::f64::consts::TAU * variance).ln())
+                        })
+                        .sum();
+                    weights[k].ln() + log_density
+                })
+                .collect();
+
+            let max = log_densities
+                .iter()
+                .copied()
+                .fold(f64::NEG_INFINITY, f64::max);
+            let sum: f64 = log_densities.iter().map(|l| (l - max).exp()).sum();
+            log_likelihood += max + sum.ln();
+
+            for (value, log_density) in row.iter_mut().zip(&log_densities) {
+                *value = (log_density - max).exp() / sum;
+            }
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let mean_log_likelihood = log_likelihood / data.nrows() as f64;
+        (responsibilities, mean_log_likelihood)
+    }
+
+    /// M-step: weights, means and variances maximising the likelihood for the given
+    /// responsibilities.
+    fn maximization(
+        data: &Array2<f64>,
+        responsibilities: &Array2<f64>,
+    ) -> (Array1<f64>, Array2<f64>, Array2<f64>) {
+        let totals = responsibilities
+            .sum_axis(Axis(0))
+            .mapv(|total| total.max(f64::EPSILON));
+
+        let means = responsibilities.t().dot(data) / totals.view().insert_axis(Axis(1));
+
+        let mut variances = Array2::<f64>::zeros(means.dim());
+        for (k, mut variance) in variances.outer_iter_mut().enumerate() {
+            for (sample, responsibility) in data.outer_iter().zip(responsibilities.column(k)) {
+                variance.zip_mut_with(&(&sample - &means.row(k)), |v, diff| {
+                    *v += responsibility * diff.powi(2);
+                });
+            }
+            variance.mapv_inplace(|v| (v / totals[k]).max(MIN_VARIANCE));
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let weights = &totals / data.nrows() as f64;
+        (weights, means, variances)
+    }
+}
+
+impl ClusteringModel for GaussianMixture {
+    fn try_fit(&self, data: &Array2<f64>) -> Result<FittedModel, Error> {
+        let num_samples = data.nrows();
+        if num_samples < self.c {
+            return Err(Error::NotEnoughSamples(num_samples, self.c));
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut weights = Array1::from_elem(self.c, 1. / self.c as f64);
+        let mut means = k_means_plus_plus(data, self.c, &mut seeded_rng(self.seed));
+        let global_variance = data.var_axis(Axis(0), 0.).mapv(|v| v.max(MIN_VARIANCE));
+        let mut variances = Array2::from_shape_fn(means.dim(), |(_, j)| global_variance[j]);
+
+        let (mut responsibilities, mut log_likelihood) =
+            Self::expectation(data, &weights, &means, &variances);
+
+        for _ in 0..self.max_iter {
+            (weights, means, variances) = Self::maximization(data, &responsibilities);
+            let (new_responsibilities, new_log_likelihood) =
+                Self::expectation(data, &weights, &means, &variances);
+            responsibilities = new_responsibilities;
+
+            let improvement = new_log_likelihood - log_likelihood;
+            log_likelihood = new_log_likelihood;
+            if improvement.abs() < self.error {
+                break;
+            }
+        }
+
+        if !log_likelihood.is_finite() {
+            return Err(Error::Fitting(
+                "log-likelihood is not a finite number".to_string(),
+            ));
+        }
+
+        Ok(FittedModel::new(means, responsibilities))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn fits_two_components() {
+        let data = array![[-5.0], [-5.2], [-4.8], [-5.1], [5.0], [5.2], [4.8], [4.9]];
+
+        let model = GaussianMixture::try_new(2, 200, 1e-9)
+            .unwrap()
+            .try_fit(&data)
+            .unwrap();
+
+        let mut means: Vec<f64> = model.centroids().iter().copied().collect();
+        means.sort_by(f64::total_cmp);
+        assert!((means[0] + 5.025).abs() < 1e-3, "{means:?}");
+        assert!((means[1] - 4.975).abs() < 1e-3, "{means:?}");
+
+        for row in model.memberships().outer_iter() {
+            assert!((row.sum() - 1.).abs() < 1e-9);
+            assert!(row.iter().any(|&r| r > 0.99));
+        }
+    }
+}
@@ -0,0 +1,652 @@
+use crate::analysis::domain::analysis::catalogue::{Language, Message};
+use crate::analysis::domain::analysis::segments::Segments;
+use crate::analysis::domain::analysis::thresholds::BRAKE_ON;
+use crate::common::domain::statistics::mean;
+use crate::lap::domain::lap::variables::Variables;
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::ops::Deref;
+
+/// Throttle position from which the driver is considered to be at full throttle.
+const FULL_THROTTLE: f32 = 0.98;
+/// Maximum distance (m) between the shifts of both laps to consider them the same shift.
+const MATCH_MARGIN: f32 = 50.;
+/// Minimum number of full throttle samples in a gear to fit its acceleration curve.
+const MIN_SAMPLES: usize = 5;
+/// Upshifts closer than this to the optimal RPM, and downshifts closer than this to the RPM of
+/// the reference lap, are not reported as early or late.
+const RPM_TOLERANCE: f32 = 250.;
+/// RPM step used to search where the next gear starts accelerating harder.
+const RPM_STEP: f32 = 10.;
+
+/// Direction of a gear change.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Default, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    #[default]
+    Up,
+    Down,
+}
+
+/// A gear change of a lap.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Shift {
+    pub from: i8,
+    pub to: i8,
+    /// Distance where the new gear is engaged (m).
+    pub distance: f32,
+    /// Engine speed right before the change (rpm).
+    pub rpm: f32,
+    /// Car speed right before the change (m/s).
+    pub speed: f32,
+}
+
+impl Shift {
+    #[must_use]
+    pub fn direction(&self) -> Direction {
+        if self.to > self.from {
+            Direction::Up
+        } else {
+            Direction::Down
+        }
+    }
+}
+
+/// The same gear change in both laps of an analysis.
+///
+/// Positive deltas mean that the target lap shifts later or at a higher RPM than the
+/// reference lap.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct GearShift {
+    pub direction: Direction,
+    /// Name of the corner or straight where the reference lap shifts, if known.
+    pub segment: Option<String>,
+    pub reference: Shift,
+    pub target: Shift,
+    pub rpm_delta: f32,
+    pub distance_delta: f32,
+}
+
+/// Average shift points of both laps in a gear, and the best point to leave it.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct GearStats {
+    pub gear: i8,
+    /// Average RPM when shifting up from this gear.
+    pub reference_upshift_rpm: Option<f32>,
+    pub target_upshift_rpm: Option<f32>,
+    /// Average RPM when shifting down from this gear.
+    pub reference_downshift_rpm: Option<f32>,
+    pub target_downshift_rpm: Option<f32>,
+    /// RPM from which the next gear accelerates harder than this one, at full throttle. `None`
+    /// if that does not happen within the RPM reached in this gear.
+    pub optimal_upshift_rpm: Option<f32>,
+}
+
+/// Whether a gear change comes before or after the RPM to aim for.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Timing {
+    Early,
+    Late,
+}
+
+/// A gear change of the target lap too far from the RPM to aim for.
+///
+/// Upshifts are early below the optimal RPM of the gear. Downshifts are early above the RPM
+/// of the same downshift in the reference lap, as the engine slows down while braking.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct ShiftSuggestion {
+    #[serde(default)]
+    pub direction: Direction,
+    pub timing: Timing,
+    pub segment: Option<String>,
+    pub shift: Shift,
+    /// RPM to aim for: the optimal one for upshifts, the one of the reference lap for
+    /// downshifts.
+    pub optimal_rpm: f32,
+    /// The suggestion in plain words, in English unless localised.
+    pub message: String,
+}
+
+impl ShiftSuggestion {
+    fn new(timing: Timing, segment: Option<String>, shift: Shift, optimal_rpm: f32) -> Self {
+        let mut suggestion = Self {
+            direction: shift.direction(),
+            timing,
+            segment,
+            shift,
+            optimal_rpm,
+            message: String::new(),
+        };
+        suggestion.localise(Language::default());
+        suggestion
+    }
+
+    /// Writes the message of the suggestion in `language`.
+    pub fn localise(&mut self, language: Language) {
+        let message = match (self.direction, self.timing) {
+            (Direction::Up, Timing::Early) => Message::EarlyUpshift,
+            (Direction::Up, Timing::Late) => Message::LateUpshift,
+            (Direction::Down, Timing::Early) => Message::EarlyDownshift,
+            (Direction::Down, Timing::Late) => Message::LateDownshift,
+        };
+        let segment = self.segment.as_ref().map_or_else(String::new, |name| {
+            Message::Segment.format(language, &[("name", name)])
+        });
+        self.message = message.format(
+            language,
+            &[
+                ("gear", &self.shift.from.to_string()),
+                ("rpm", &format!("{:.0}", self.shift.rpm)),
+                ("segment", &segment),
+                ("optimal", &format!("{:.0}", self.optimal_rpm)),
+            ],
+        );
+    }
+}
+
+/// Gear changes of both laps of an analysis, compared shift by shift and gear by gear.
+#[derive(Serialize, Deserialize, PartialEq, Default, Clone, Debug)]
+pub struct GearShifts {
+    shifts: Vec<GearShift>,
+    /// Statistics of every gear used by either lap, from the lowest.
+    pub gears: Vec<GearStats>,
+    /// Early and late gear changes of the target lap, in track order.
+    pub suggestions: Vec<ShiftSuggestion>,
+}
+
+impl From<Vec<GearShift>> for GearShifts {
+    /// Converts a vector of gear shifts into `GearShifts`, with the statistics of their gears
+    /// and the suggestions on their downshifts. Without acceleration curves, no optimal upshift
+    /// RPM is known.
+    fn from(shifts: Vec<GearShift>) -> Self {
+        let reference: Vec<Shift> = shifts.iter().map(|s| s.reference.clone()).collect();
+        let target: Vec<Shift> = shifts.iter().map(|s| s.target.clone()).collect();
+        let gears = gear_stats(&reference, &target, &[]);
+        let suggestions = downshift_suggestions(&shifts);
+
+        Self {
+            shifts,
+            gears,
+            suggestions,
+        }
+    }
+}
+
+impl Deref for GearShifts {
+    type Target = Vec<GearShift>;
+
+    /// Implements dereferencing for `GearShifts`.
+    fn deref(&self) -> &Self::Target {
+        &self.shifts
+    }
+}
+
+impl GearShifts {
+    /// Writes the message of every suggestion in `language`.
+    pub fn localise(&mut self, language: Language) {
+        for suggestion in &mut self.suggestions {
+            suggestion.localise(language);
+        }
+    }
+}
+
+/// Detects the gear changes of both laps and compares them.
+///
+/// Both `reference` and `target` must be sampled on the same distances. Shifts are matched
+/// when both laps change between the same gears close enough. The optimal upshift RPM of
+/// every gear is estimated from the full throttle acceleration over `driver_laps`, the laps of
+/// the driver of the target lap with the same car, or over both laps if there are none. The
+/// upshifts of the target lap far from the optimal RPM, and its downshifts far from those of
+/// the reference lap, are suggested.
+#[must_use]
+pub fn compare(
+    segments: &Segments,
+    reference: &Variables,
+    target: &Variables,
+    driver_laps: &[&Variables],
+) -> GearShifts {
+    let reference_shifts = detect(reference);
+    let target_shifts = detect(target);
+    let segment_at = |distance: f32| segments.at(distance).map(|s| s.name());
+
+    let shifts: Vec<GearShift> = reference_shifts
+        .iter()
+        .filter_map(|r| {
+            let t = target_shifts
+                .iter()
+                .filter(|t| {
+                    t.from == r.from
+                        && t.to == r.to
+                        && (t.distance - r.distance).abs() <= MATCH_MARGIN
+                })
+                .min_by(|a, b| {
+                    (a.distance - r.distance)
+                        .abs()
+                        .total_cmp(&(b.distance - r.distance).abs())
+                })?;
+
+            Some(GearShift {
+                direction: r.direction(),
+                segment: segment_at(r.distance),
+                rpm_delta: t.rpm - r.rpm,
+                distance_delta: t.distance - r.distance,
+                reference: r.clone(),
+                target: t.clone(),
+            })
+        })
+        .collect();
+
+    let both_laps = [reference, target];
+    let curve_laps = if driver_laps.is_empty() {
+        &both_laps[..]
+    } else {
+        driver_laps
+    };
+    let curves = curve_laps
+        .iter()
+        .flat_map(|variables| samples(variables))
+        .fold(Vec::<Curve>::new(), |mut curves, (gear, sample)| {
+            match curves.iter_mut().find(|c| c.gear == gear) {
+                Some(curve) => curve.samples.push(sample),
+                None => curves.push(Curve {
+                    gear,
+                    samples: vec![sample],
+                }),
+            }
+            curves
+        });
+
+    let gears = gear_stats(&reference_shifts, &target_shifts, &curves);
+
+    let mut suggestions: Vec<ShiftSuggestion> = target_shifts
+        .iter()
+        .filter(|s| s.direction() == Direction::Up)
+        .filter_map(|shift| {
+            let optimal = gears
+                .iter()
+                .find(|g| g.gear == shift.from)?
+                .optimal_upshift_rpm?;
+            let timing = if shift.rpm < optimal - RPM_TOLERANCE {
+                Timing::Early
+            } else if shift.rpm > optimal + RPM_TOLERANCE {
+                Timing::Late
+            } else {
+                return None;
+            };
+            Some(ShiftSuggestion::new(
+                timing,
+                segment_at(shift.distance),
+                shift.clone(),
+                optimal,
+            ))
+        })
+        .collect();
+    suggestions.extend(downshift_suggestions(&shifts));
+    suggestions.sort_by(|a, b| a.shift.distance.total_cmp(&b.shift.distance));
+
+    GearShifts {
+        shifts,
+        gears,
+        suggestions,
+    }
+}
+
+/// Statistics of every gear shifted from by either lap or with an acceleration curve, from the
+/// lowest.
+fn gear_stats(reference: &[Shift], target: &[Shift], curves: &[Curve]) -> Vec<GearStats> {
+    let mut gears: Vec<i8> = reference
+        .iter()
+        .chain(target)
+        .map(|s| s.from)
+        .chain(curves.iter().map(|c| c.gear))
+        .collect();
+    gears.sort_unstable();
+    gears.dedup();
+
+    let average_rpm = |shifts: &[Shift], gear: i8, direction: Direction| {
+        let rpms: Vec<f32> = shifts
+            .iter()
+            .filter(|s| s.from == gear && s.direction() == direction)
+            .map(|s| s.rpm)
+            .collect();
+        mean(&rpms)
+    };
+    gears
+        .into_iter()
+        .map(|gear| GearStats {
+            gear,
+            reference_upshift_rpm: average_rpm(reference, gear, Direction::Up),
+            target_upshift_rpm: average_rpm(target, gear, Direction::Up),
+            reference_downshift_rpm: average_rpm(reference, gear, Direction::Down),
+            target_downshift_rpm: average_rpm(target, gear, Direction::Down),
+            optimal_upshift_rpm: optimal_upshift_rpm(curves, gear),
+        })
+        .collect()
+}
+
+/// Downshifts of the target lap made at an RPM too far from the same downshift of the
+/// reference lap.
+fn downshift_suggestions(shifts: &[GearShift]) -> Vec<ShiftSuggestion> {
+    shifts
+        .iter()
+        .filter(|s| s.direction == Direction::Down)
+        .filter_map(|s| {
+            let timing = if s.rpm_delta > RPM_TOLERANCE {
+                Timing::Early
+            } else if s.rpm_delta < -RPM_TOLERANCE {
+                Timing::Late
+            } else {
+                return None;
+            };
+            Some(ShiftSuggestion::new(
+                timing,
+                s.segment.clone(),
+                s.target.clone(),
+                s.reference.rpm,
+            ))
+        })
+        .collect()
+}
+
+/// Detects the gear changes of a lap. Changes from or into neutral or reverse are ignored.
+#[must_use]
+pub fn detect(variables: &Variables) -> Vec<Shift> {
+    let len = variables
+        .gear
+        .len()
+        .min(variables.distance.len())
+        .min(variables.rpm.len())
+        .min(variables.speed.len());
+
+    (1..len)
+        .filter(|&i| {
+            let (from, to) = (variables.gear[i - 1], variables.gear[i]);
+            from != to && from > 0 && to > 0
+        })
+        .map(|i| Shift {
+            from: variables.gear[i - 1],
+            to: variables.gear[i],
+            distance: variables.distance[i],
+            rpm: variables.rpm[i - 1],
+            speed: variables.speed[i - 1],
+        })
+        .collect()
+}
+
+/// Full throttle samples of a gear: engine speed (rpm), car speed (m/s) and acceleration
+/// (m/s²).
+struct Curve {
+    gear: i8,
+    samples: Vec<(f32, f32, f32)>,
+}
+
+impl Curve {
+    /// Median ratio between car speed and engine speed.
+    fn ratio(&self) -> Option<f32> {
+        let mut ratios: Vec<f32> = self
+            .samples
+            .iter()
+            .filter(|(rpm, ..)| *rpm > 0.)
+            .map(|(rpm, speed, _)| speed / rpm)
+            .collect();
+        ratios.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        ratios.get(ratios.len() / 2).copied()
+    }
+
+    /// Least squares line of the acceleration against the engine speed, as (slope, intercept).
+    #[allow(clippy::cast_precision_loss)]
+    fn fit(&self) -> Option<(f32, f32)> {
+        if self.samples.len() < MIN_SAMPLES {
+            return None;
+        }
+        let count = self.samples.len() as f32;
+        let rpm_mean = self.samples.iter().map(|s| s.0).sum::<f32>() / count;
+        let acc_mean = self.samples.iter().map(|s| s.2).sum::<f32>() / count;
+        let variance: f32 = self.samples.iter().map(|s| (s.0 - rpm_mean).powi(2)).sum();
+        if variance <= f32::EPSILON {
+            return None;
+        }
+        let covariance: f32 = self
+            .samples
+            .iter()
+            .map(|s| (s.0 - rpm_mean) * (s.2 - acc_mean))
+            .sum();
+        let slope = covariance / variance;
+        Some((slope, slope.mul_add(-rpm_mean, acc_mean)))
+    }
+
+    /// Lowest and highest RPM of the samples.
+    fn rpm_range(&self) -> (f32, f32) {
+        self.samples
+            .iter()
+            .fold((f32::MAX, 0.), |(min, max), s| (min.min(s.0), max.max(s.0)))
+    }
+}
+
+/// Full throttle samples, without brake and away from gear changes, by gear.
+fn samples(variables: &Variables) -> Vec<(i8, (f32, f32, f32))> {
+    let len = [
+        variables.gear.len(),
+        variables.rpm.len(),
+        variables.speed.len(),
+        variables.throttle.len(),
+        variables.brake.len(),
+        variables.lap_current_lap_time.len(),
+    ]
+    .into_iter()
+    .min()
+    .unwrap_or_default();
+
+    (1..len.saturating_sub(1))
+        .filter(|&i| {
+            let gear = variables.gear[i];
+            gear > 0
+                && variables.gear[i - 1] == gear
+                && variables.gear[i + 1] == gear
+                && variables.throttle[i] >= FULL_THROTTLE
+                && variables.brake[i] <= BRAKE_ON
+        })
+        .filter_map(|i| {
+            let time = &variables.lap_current_lap_time;
+            let dt = time[i + 1] - time[i - 1];
+            (dt > 0.).then(|| {
+                let acceleration = (variables.speed[i + 1] - variables.speed[i - 1]) / dt;
+                (
+                    variables.gear[i],
+                    (variables.rpm[i], variables.speed[i], acceleration),
+                )
+            })
+        })
+        .collect()
+}
+
+/// RPM of `gear` from which the next gear accelerates at least as hard, at the same car
+/// speed.
+///
+/// The linear fits are only trusted within the RPM range reached in `gear`, so `None` is
+/// returned if the next gear does not catch up inside it, as well as if either gear has too
+/// few samples.
+fn optimal_upshift_rpm(curves: &[Curve], gear: i8) -> Option<f32> {
+    let current = curves.iter().find(|c| c.gear == gear)?;
+    let next = curves.iter().find(|c| c.gear == gear + 1)?;
+    let (current_slope, current_intercept) = current.fit()?;
+    let (next_slope, next_intercept) = next.fit()?;
+    let (current_ratio, next_ratio) = (current.ratio()?, next.ratio()?);
+
+    let next_is_faster = |rpm: f32| {
+        let next_rpm = rpm * current_ratio / next_ratio;
+        next_slope.mul_add(next_rpm, next_intercept)
+            >= current_slope.mul_add(rpm, current_intercept)
+    };
+
+    let (min_rpm, max_rpm) = current.rpm_range();
+    // Already faster at the lowest RPM seen: the curves cross outside the range
+    if next_is_faster(min_rpm) {
+        return None;
+    }
+    std::iter::successors(Some(min_rpm), |rpm| Some(rpm + RPM_STEP))
+        .take_while(|&rpm| rpm <= max_rpm)
+        .find(|&rpm| next_is_faster(rpm))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::domain::analysis::segment::Segment;
+
+    /// Wheel speed per engine speed of each gear ((m/s)/rpm).
+    const RATIOS: [f32; 3] = [0.004, 0.006, 0.008];
+
+    /// Acceleration (m/s²) of a car whose torque falls linearly with the engine speed.
+    fn acceleration(gear: i8, rpm: f32) -> f32 {
+        let torque = 0.1f32.mul_add(-rpm, 800.);
+        torque / (RATIOS[usize::try_from(gear - 1).unwrap()] * 10_000.)
+    }
+
+    /// A 2000 m lap at full throttle from 10 m/s, shifting up when reaching `upshift` rpm,
+    /// and shifting down from the top gear at 1800 m.
+    fn test_variables(upshift: f32) -> Variables {
+        let mut variables = Variables::default();
+        let (mut speed, mut time, mut gear) = (10f32, 0f32, 1i8);
+        for i in 0..2000 {
+            #[allow(clippy::cast_precision_loss)]
+            let d = i as f32;
+            if d >= 1800. && gear == 3 {
+                gear = 2;
+            }
+            let mut rpm = speed / RATIOS[usize::try_from(gear - 1).unwrap()];
+            if rpm >= upshift && gear < 3 && d < 1800. {
+                gear += 1;
+                rpm = speed / RATIOS[usize::try_from(gear - 1).unwrap()];
+            }
+            let braking = d >= 1800.;
+
+            variables.distance.push(d);
+            variables.speed.push(speed);
+            variables.gear.push(gear);
+            variables.rpm.push(rpm);
+            variables.throttle.push(if braking { 0. } else { 1. });
+            variables.brake.push(if braking { 1. } else { 0. });
+            variables.lap_current_lap_time.push(time);
+
+            let acceleration = if braking {
+                -5.
+            } else {
+                acceleration(gear, rpm)
+            };
+            let dt = 1. / speed;
+            time += dt;
+            speed = acceleration.mul_add(dt, speed);
+        }
+        variables
+    }
+
+    #[test]
+    fn detects_upshifts_and_downshifts() {
+        let shifts = detect(&test_variables(6000.));
+
+        let directions: Vec<(i8, i8, Direction)> = shifts
+            .iter()
+            .map(|s| (s.from, s.to, s.direction()))
+            .collect();
+        assert_eq!(
+            directions,
+            vec![
+                (1, 2, Direction::Up),
+                (2, 3, Direction::Up),
+                (3, 2, Direction::Down)
+            ]
+        );
+        assert!((shifts[0].rpm - 6000.).abs() < 100.);
+        assert!((shifts[2].distance - 1800.).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn suggests_shifting_up_later() {
+        let segments = Segments::from(vec![Segment::straight(1, 0., 2000.)]);
+        let reference = test_variables(7000.);
+        let target = test_variables(4000.);
+
+        let gear_shifts = compare(&segments, &reference, &target, &[]);
+
+        let first = &gear_shifts.gears[0];
+        assert_eq!(first.gear, 1);
+        let optimal = first.optimal_upshift_rpm.unwrap();
+        // Second gear accelerates harder once the first one is over 4800 rpm
+        assert!((optimal - 4800.).abs() < 100., "{optimal}");
+        assert!(first.target_upshift_rpm.unwrap() < first.reference_upshift_rpm.unwrap());
+
+        let suggestion = &gear_shifts.suggestions[0];
+        assert_eq!(suggestion.timing, Timing::Early);
+        assert_eq!(suggestion.shift.from, 1);
+        assert_eq!(suggestion.segment.as_deref(), Some("S1"));
+        assert!(suggestion
+            .message
+            .starts_with("You shift up from gear 1 at"));
+    }
+
+    #[test]
+    fn estimates_the_optimal_upshift_over_the_driver_laps() {
+        let segments = Segments::from(vec![Segment::straight(1, 0., 2000.)]);
+        let (reference, target) = (test_variables(4000.), test_variables(4000.));
+        let driver_lap = test_variables(7000.);
+
+        let optimal = |driver_laps: &[&Variables]| {
+            compare(&segments, &reference, &target, driver_laps).gears[0].optimal_upshift_rpm
+        };
+
+        // Neither lap reaches the RPM where second gear accelerates harder
+        assert_eq!(optimal(&[]), None);
+        let estimated = optimal(&[&driver_lap]).unwrap();
+        assert!((estimated - 4800.).abs() < 150., "{estimated}");
+    }
+
+    #[test]
+    fn does_not_estimate_the_optimal_upshift_outside_the_rpm_reached() {
+        let rising = |gear: i8| Curve {
+            gear,
+            // Acceleration rising with the RPM, as in the low end of a torque curve
+            samples: (0..20u8)
+                .map(|i| {
+                    let rpm = f32::from(i).mul_add(50., 5000.);
+                    let speed = rpm * RATIOS[usize::try_from(gear - 1).unwrap()];
+                    (rpm, speed, rpm / 1000.)
+                })
+                .collect(),
+        };
+
+        assert_eq!(optimal_upshift_rpm(&[rising(1), rising(2)], 1), None);
+    }
+
+    #[test]
+    fn suggests_shifting_down_later() {
+        let shift = |rpm: f32| Shift {
+            from: 3,
+            to: 2,
+            distance: 1800.,
+            rpm,
+            speed: 40.,
+        };
+        let gear_shifts = GearShifts::from(vec![GearShift {
+            direction: Direction::Down,
+            segment: Some("C1".to_string()),
+            reference: shift(5000.),
+            target: shift(6000.),
+            rpm_delta: 1000.,
+            distance_delta: 0.,
+        }]);
+
+        assert_eq!(gear_shifts.gears.len(), 1);
+        assert_eq!(gear_shifts.gears[0].target_downshift_rpm, Some(6000.));
+        assert_eq!(gear_shifts.gears[0].optimal_upshift_rpm, None);
+        let suggestion = &gear_shifts.suggestions[0];
+        assert_eq!(suggestion.direction, Direction::Down);
+        assert_eq!(suggestion.timing, Timing::Early);
+        assert!((suggestion.optimal_rpm - 5000.).abs() < f32::EPSILON);
+        assert!(suggestion
+            .message
+            .starts_with("You shift down from gear 3 at 6000 rpm"));
+    }
+}
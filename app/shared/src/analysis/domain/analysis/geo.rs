@@ -0,0 +1,116 @@
+/// Mean Earth radius (m).
+const EARTH_RADIUS: f64 = 6_371_000.;
+
+/// Projects GPS coordinates into a local metric frame centred on the first valid sample.
+///
+/// An equirectangular projection is used, which is accurate enough at circuit scale.
+/// Returned points are `(x, y)` in metres, `x` pointing east and `y` pointing north.
+/// Samples with non finite coordinates are projected onto the origin.
+#[must_use]
+pub fn project(latitudes: &[f64], longitudes: &[f64]) -> Vec<(f64, f64)> {
+    origin(latitudes, longitudes).map_or_else(
+        || vec![(0., 0.); latitudes.len().min(longitudes.len())],
+        |origin| project_from(latitudes, longitudes, origin),
+    )
+}
+
+/// First sample with finite coordinates, as `(latitude, longitude)`.
+#[must_use]
+pub fn origin(latitudes: &[f64], longitudes: &[f64]) -> Option<(f64, f64)> {
+    latitudes
+        .iter()
+        .zip(longitudes)
+        .find(|(lat, lon)| lat.is_finite() && lon.is_finite())
+        .map(|(&lat, &lon)| (lat, lon))
+}
+
+/// Projects GPS coordinates into a local metric frame centred on `origin`.
+///
+/// Use it to project several laps into the same frame so their points can be compared.
+#[must_use]
+pub fn project_from(
+    latitudes: &[f64],
+    longitudes: &[f64],
+    (lat0, lon0): (f64, f64),
+) -> Vec<(f64, f64)> {
+    let cos_lat0 = lat0.to_radians().cos();
+
+    latitudes
+        .iter()
+        .zip(longitudes)
+        .map(|(&lat, &lon)| {
+            if lat.is_finite() && lon.is_finite() {
+                (
+                    EARTH_RADIUS * (lon - lon0).to_radians() * cos_lat0,
+                    EARTH_RADIUS * (lat - lat0).to_radians(),
+                )
+            } else {
+                (0., 0.)
+            }
+        })
+        .collect()
+}
+
+/// Linearly interpolates a projected point at the given distance.
+///
+/// `distances` must be sorted in ascending order and have the same length as `points`.
+#[must_use]
+pub fn point_at(points: &[(f64, f64)], distances: &[f64], distance: f64) -> (f64, f64) {
+    let pos = distances.partition_point(|&d| d < distance);
+    match pos {
+        0 => points[0],
+        pos if pos >= points.len() => points[points.len() - 1],
+        pos => {
+            let (d0, d1) = (distances[pos - 1], distances[pos]);
+            let ((x0, y0), (x1, y1)) = (points[pos - 1], points[pos]);
+            if (d1 - d0).abs() < f64::EPSILON {
+                (x1, y1)
+            } else {
+                let t = (distance - d0) / (d1 - d0);
+                (t.mul_add(x1 - x0, x0), t.mul_add(y1 - y0, y0))
+            }
+        }
+    }
+}
+
+/// Heading (radians, counter-clockwise from east) of the segment going from `from` to `to`.
+#[must_use]
+pub fn heading(from: (f64, f64), to: (f64, f64)) -> f64 {
+    (to.1 - from.1).atan2(to.0 - from.0)
+}
+
+/// Signed smallest difference between two headings, in the range `(-PI, PI]`.
+#[must_use]
+pub fn heading_difference(from: f64, to: f64) -> f64 {
+    let diff = (to - from).rem_euclid(std::f64::consts::TAU);
+    if diff > std::f64::consts::PI {
+        diff - std::f64::consts::TAU
+    } else {
+        diff
+    }
+}
+
+/// Signed curvature (1/m) at each distance, positive when turning left.
+///
+/// The heading change is measured between the points located `window` metres before and
+/// after each sample, which smooths the noise of GPS positions.
+#[must_use]
+pub fn curvature(points: &[(f64, f64)], distances: &[f64], window: f64) -> Vec<f64> {
+    if points.len() < 2 || window <= 0. {
+        return vec![0.; points.len()];
+    }
+
+    distances
+        .iter()
+        .map(|&d| {
+            let before = point_at(points, distances, d - window);
+            let here = point_at(points, distances, d);
+            let after = point_at(points, distances, d + window);
+            if before == here || here == after {
+                0.
+            } else {
+                heading_difference(heading(before, here), heading(here, after)) / window
+            }
+        })
+        .collect()
+}
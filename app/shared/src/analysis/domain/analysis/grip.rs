@@ -0,0 +1,348 @@
+use crate::analysis::domain::analysis::segment::Segment;
+use crate::analysis::domain::analysis::segments::Segments;
+use crate::analysis::domain::analysis::thresholds::{BRAKE_ON, THROTTLE_ON};
+use crate::common::domain::statistics::mean;
+use crate::lap::domain::lap::variables::Variables;
+use crate::lap::domain::lap::Lap;
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::f32::consts::TAU;
+use std::ops::Deref;
+
+/// Number of fastest laps whose accelerations make up the grip envelope.
+const ENVELOPE_LAPS: usize = 5;
+/// Number of directions of the G-G diagram in which the envelope is measured.
+const SECTORS: usize = 24;
+/// Fraction of the samples of each direction below the envelope, to ignore kerb spikes.
+const ENVELOPE_PERCENTILE: f32 = 0.98;
+/// Combined accelerations (m/s²) below this value are not used, as the direction is noise.
+const MIN_ACCEL: f32 = 1.;
+
+/// A sample of the G-G diagram.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct GgPoint {
+    pub distance: f32,
+    /// Lateral acceleration (m/s²).
+    pub lat: f32,
+    /// Longitudinal acceleration (m/s²).
+    pub long: f32,
+}
+
+/// Highest combined acceleration reached in every direction of the G-G diagram.
+#[derive(Serialize, Deserialize, PartialEq, Default, Clone, Debug)]
+pub struct GripEnvelope {
+    /// Acceleration (m/s²) by direction, counterclockwise from pure left cornering.
+    pub sectors: Vec<f32>,
+}
+
+impl GripEnvelope {
+    /// Measures the grip demonstrated over the given laps.
+    ///
+    /// Returns `None` if the laps have no acceleration data.
+    #[must_use]
+    pub fn calculate(laps: &[&Variables]) -> Option<Self> {
+        let mut by_sector: Vec<Vec<f32>> = vec![vec![]; SECTORS];
+        for variables in laps {
+            for (&lat, &long) in variables.lat_accel.iter().zip(&variables.long_accel) {
+                let combined = lat.hypot(long);
+                if combined >= MIN_ACCEL {
+                    by_sector[sector(lat, long)].push(combined);
+                }
+            }
+        }
+        if by_sector.iter().all(Vec::is_empty) {
+            return None;
+        }
+
+        let sectors = by_sector
+            .into_iter()
+            .map(|mut values| {
+                values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+                #[allow(
+                    clippy::cast_possible_truncation,
+                    clippy::cast_sign_loss,
+                    clippy::cast_precision_loss
+                )]
+                let index = ((values.len() as f32 - 1.) * ENVELOPE_PERCENTILE).round() as usize;
+                values.get(index).copied().unwrap_or_default()
+            })
+            .collect();
+
+        Some(Self { sectors })
+    }
+
+    /// Combined acceleration as a percentage of the envelope in its direction, if the
+    /// envelope has been measured in that direction.
+    #[must_use]
+    pub fn utilisation(&self, lat: f32, long: f32) -> Option<f32> {
+        let limit = *self.sectors.get(sector(lat, long))?;
+        (limit > 0.).then(|| lat.hypot(long) / limit * 100.)
+    }
+}
+
+/// Direction of an acceleration in the G-G diagram.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn sector(lat: f32, long: f32) -> usize {
+    let angle = long.atan2(lat).rem_euclid(TAU);
+    ((angle / TAU * SECTORS as f32) as usize).min(SECTORS - 1)
+}
+
+/// Average grip utilisation (%) of a lap in every phase of one corner.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct CornerUtilisation {
+    pub segment: Segment,
+    /// While braking.
+    pub braking: Option<f32>,
+    /// Neither braking nor accelerating.
+    pub mid_corner: Option<f32>,
+    /// On the throttle after the apex.
+    pub exit: Option<f32>,
+    /// Over the whole corner.
+    pub overall: Option<f32>,
+}
+
+/// Represents how much of the grip envelope of the car a lap uses, per corner in track
+/// order.
+#[derive(Serialize, Deserialize, PartialEq, Default, Clone, Debug)]
+pub struct GripUtilisation {
+    pub envelope: GripEnvelope,
+    /// Average utilisation (%) over the corners of the lap.
+    pub lap: Option<f32>,
+    /// Accelerations of the lap, for the G-G diagram.
+    pub scatter: Vec<GgPoint>,
+    corners: Vec<CornerUtilisation>,
+}
+
+impl Deref for GripUtilisation {
+    type Target = Vec<CornerUtilisation>;
+
+    /// Implements dereferencing for `GripUtilisation`.
+    fn deref(&self) -> &Self::Target {
+        &self.corners
+    }
+}
+
+/// Measures the grip utilisation of a lap through every corner of `segments`, in the
+/// braking, mid-corner and exit phases.
+#[must_use]
+pub fn calculate(
+    segments: &Segments,
+    variables: &Variables,
+    envelope: GripEnvelope,
+) -> GripUtilisation {
+    let len = [
+        variables.distance.len(),
+        variables.lat_accel.len(),
+        variables.long_accel.len(),
+        variables.brake.len(),
+        variables.throttle.len(),
+    ]
+    .into_iter()
+    .min()
+    .unwrap_or_default();
+    let utilisation =
+        |i: usize| envelope.utilisation(variables.lat_accel[i], variables.long_accel[i]);
+
+    let corners: Vec<CornerUtilisation> = segments
+        .corners()
+        .map(|segment| {
+            let (mut braking, mut mid_corner, mut exit) = (vec![], vec![], vec![]);
+            for i in (0..len).filter(|&i| segment.contains(variables.distance[i])) {
+                let Some(value) = utilisation(i) else {
+                    continue;
+                };
+                let after_apex = segment
+                    .apex
+                    .is_some_and(|apex| variables.distance[i] >= apex);
+                if variables.brake[i] > BRAKE_ON {
+                    braking.push(value);
+                } else if after_apex && variables.throttle[i] > THROTTLE_ON {
+                    exit.push(value);
+                } else {
+                    mid_corner.push(value);
+                }
+            }
+            let all: Vec<f32> = braking
+                .iter()
+                .chain(&mid_corner)
+                .chain(&exit)
+                .copied()
+                .collect();
+
+            CornerUtilisation {
+                segment: segment.clone(),
+                braking: mean(&braking),
+                mid_corner: mean(&mid_corner),
+                exit: mean(&exit),
+                overall: mean(&all),
+            }
+        })
+        .collect();
+
+    let overall: Vec<f32> = corners.iter().filter_map(|c| c.overall).collect();
+    GripUtilisation {
+        lap: mean(&overall),
+        scatter: (0..len)
+            .map(|i| GgPoint {
+                distance: variables.distance[i],
+                lat: variables.lat_accel[i],
+                long: variables.long_accel[i],
+            })
+            .collect(),
+        envelope,
+        corners,
+    }
+}
+
+/// Telemetry of the fastest laps among `laps` that make up the grip envelope of their car on
+/// their circuit. Synthetic laps, laps without acceleration data and laps with events are left
+/// out, as an off-track excursion or a spin would inflate the envelope.
+#[must_use]
+pub fn envelope_laps(laps: &[Lap]) -> Vec<&Variables> {
+    let mut best: Vec<&Lap> = laps
+        .iter()
+        .filter(|l| !l.header.synthetic && l.is_valid() && l.header.time > 0.)
+        .filter(|l| !l.variables.lat_accel.is_empty())
+        .collect();
+    best.sort_by(|a, b| {
+        a.header
+            .time
+            .partial_cmp(&b.header.time)
+            .unwrap_or(Ordering::Equal)
+    });
+    best.iter()
+        .take(ENVELOPE_LAPS)
+        .map(|l| &l.variables)
+        .collect()
+}
+
+/// Grip utilisation of both laps of an analysis, against the same envelope.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct GripComparison {
+    pub reference: GripUtilisation,
+    pub target: GripUtilisation,
+}
+
+/// Measures the grip utilisation of both laps of an analysis through every corner of
+/// `segments`, against the envelope demonstrated over both laps and `best_laps`, usually the
+/// `envelope_laps` of the car on the circuit.
+///
+/// Returns `None` if none of the laps has acceleration data.
+#[must_use]
+pub fn compare(
+    segments: &Segments,
+    reference: &Variables,
+    target: &Variables,
+    best_laps: &[&Variables],
+) -> Option<GripComparison> {
+    let laps: Vec<&Variables> = [reference, target]
+        .into_iter()
+        .chain(best_laps.iter().copied())
+        .collect();
+    let envelope = GripEnvelope::calculate(&laps)?;
+
+    Some(GripComparison {
+        reference: calculate(segments, reference, envelope.clone()),
+        target: calculate(segments, target, envelope),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lap::domain::lap::events::{Event, EventKind};
+    use crate::lap::domain::lap::test_util;
+
+    /// A 300 m lap with a corner between 100 m and 200 m, with its apex at 150 m. The car
+    /// brakes at `brake` m/s² until 140 m, corners at `lat` m/s² until 160 m and accelerates
+    /// out of it.
+    fn test_variables(brake: f32, lat: f32) -> Variables {
+        let mut variables = Variables::default();
+        for i in 0..300 {
+            #[allow(clippy::cast_precision_loss)]
+            let d = i as f32;
+            let (lat_accel, long_accel, braking, throttle) = match d {
+                d if (100. ..140.).contains(&d) => (0., -brake, 0.8, 0.),
+                d if (140. ..160.).contains(&d) => (lat, 0., 0., 0.),
+                d if (160. ..=200.).contains(&d) => (0., 5., 0., 1.),
+                _ => (0., 0., 0., 1.),
+            };
+            variables.distance.push(d);
+            variables.lat_accel.push(lat_accel);
+            variables.long_accel.push(long_accel);
+            variables.brake.push(braking);
+            variables.throttle.push(throttle);
+        }
+        variables
+    }
+
+    #[test]
+    fn measures_utilisation_per_corner_phase() {
+        let best = test_variables(20., 15.);
+        let envelope = GripEnvelope::calculate(&[&best]).unwrap();
+        let segments = Segments::from(vec![Segment::corner(1, 100., 150., 200.)]);
+
+        let utilisation = calculate(&segments, &test_variables(10., 15.), envelope);
+
+        assert_eq!(utilisation.len(), 1);
+        let corner = &utilisation[0];
+        assert!((corner.braking.unwrap() - 50.).abs() < 1e-3);
+        assert!((corner.mid_corner.unwrap() - 100.).abs() < 1e-3);
+        assert!((corner.exit.unwrap() - 100.).abs() < 1e-3);
+        assert_eq!(utilisation.scatter.len(), 300);
+        assert!(utilisation.lap.is_some());
+    }
+
+    #[test]
+    fn compares_both_laps_against_the_same_envelope() {
+        let segments = Segments::from(vec![Segment::corner(1, 100., 150., 200.)]);
+        let (reference, target) = (test_variables(10., 15.), test_variables(10., 15.));
+        let best = test_variables(20., 15.);
+
+        let grip = compare(&segments, &reference, &target, &[&best]).unwrap();
+
+        assert_eq!(grip.reference.envelope, grip.target.envelope);
+        assert!((grip.target[0].braking.unwrap() - 50.).abs() < 1e-3);
+        assert_eq!(grip.reference.scatter.len(), 300);
+        let none = Variables::default();
+        assert!(compare(&segments, &none, &none, &[]).is_none());
+    }
+
+    #[test]
+    fn builds_the_envelope_from_the_fastest_valid_laps() {
+        let lap = |number: u16, time: f32| {
+            let mut variables = test_variables(10., 15.);
+            #[allow(clippy::cast_precision_loss)]
+            let pct: Vec<f32> = (0..300).map(|i| i as f32 / 299.).collect();
+            variables.distance_pct = pct;
+            let mut lap = test_util::test_lap(number, "Driver", variables);
+            lap.header.time = time;
+            lap
+        };
+        let mut laps: Vec<Lap> = (1..=7).map(|n| lap(n, 100. - f32::from(n))).collect();
+        laps[6].events.push(Event {
+            kind: EventKind::Spin,
+            distance: 150.,
+            time: 10.,
+        });
+
+        let best = envelope_laps(&laps);
+
+        let expected: Vec<&Variables> = [5, 4, 3, 2, 1]
+            .iter()
+            .map(|&i| &laps[i].variables)
+            .collect();
+        assert_eq!(best.len(), ENVELOPE_LAPS);
+        assert!(best.iter().zip(expected).all(|(a, b)| std::ptr::eq(*a, b)));
+    }
+
+    #[test]
+    fn no_envelope_without_accelerations() {
+        assert!(GripEnvelope::calculate(&[&Variables::default()]).is_none());
+    }
+}
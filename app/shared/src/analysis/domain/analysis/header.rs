@@ -15,6 +15,12 @@ pub struct Header {
     pub circuit: String,
     /// Current status of the analysis
     pub status: Status,
+    /// Reference lap of the analysis, kept to run it again
+    #[serde(default)]
+    pub ref_lap_id: Option<Uuid>,
+    /// Target lap of the analysis, kept to run it again
+    #[serde(default)]
+    pub target_lap_id: Option<Uuid>,
 }
 
 impl Header {
@@ -32,15 +38,18 @@ impl Header {
             name,
             date,
             circuit,
-            status: Status::Pending {
+            status: Status::Queued {
                 ref_id: ref_lap_id,
                 target_id: target_lap_id,
+                at: date,
             },
+            ref_lap_id: Some(ref_lap_id),
+            target_lap_id: Some(target_lap_id),
         }
     }
 
     #[must_use]
-    pub const fn with_error(
+    pub fn with_error(
         id: Uuid,
         name: String,
         date: DateTime<Utc>,
@@ -52,7 +61,13 @@ impl Header {
             name,
             date,
             circuit,
-            status: Status::Error(error_msg),
+            status: Status::Failed {
+                stage: None,
+                reason: error_msg,
+                at: Utc::now(),
+            },
+            ref_lap_id: None,
+            target_lap_id: None,
         }
     }
 }
@@ -102,6 +102,48 @@ pub fn interpolate_variables(variables: &Variables, distances: &[f32]) -> Result
             &distances_f64,
             false,
         )?,
+        lat_accel: try_f32_interpolation(
+            &variables.lat_accel,
+            &variables.distance,
+            &distances_f64,
+            false,
+        )?,
+        long_accel: try_f32_interpolation(
+            &variables.long_accel,
+            &variables.distance,
+            &distances_f64,
+            false,
+        )?,
+        yaw_rate: try_f32_interpolation(
+            &variables.yaw_rate,
+            &variables.distance,
+            &distances_f64,
+            false,
+        )?,
+        lf_speed: try_f32_interpolation(
+            &variables.lf_speed,
+            &variables.distance,
+            &distances_f64,
+            false,
+        )?,
+        rf_speed: try_f32_interpolation(
+            &variables.rf_speed,
+            &variables.distance,
+            &distances_f64,
+            false,
+        )?,
+        lr_speed: try_f32_interpolation(
+            &variables.lr_speed,
+            &variables.distance,
+            &distances_f64,
+            false,
+        )?,
+        rr_speed: try_f32_interpolation(
+            &variables.rr_speed,
+            &variables.distance,
+            &distances_f64,
+            false,
+        )?,
     })
 }
 
@@ -149,6 +191,11 @@ fn interpolate_vector(
     new_distances: &[f64],
     is_discrete: bool,
 ) -> Vec<f64> {
+    // Channels missing from older laps stay empty
+    if values.is_empty() {
+        return vec![];
+    }
+
     let mut interpolated_values = Vec::new();
 
     for &new_distance in new_distances {
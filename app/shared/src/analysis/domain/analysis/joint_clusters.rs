@@ -0,0 +1,341 @@
+use crate::analysis::domain::analysis::channel::Channel;
+use crate::analysis::domain::analysis::fcm_grid::{Config, FcmGrid};
+use crate::analysis::domain::analysis::tag::Base;
+use crate::analysis::domain::analysis::validity::ValidityIndices;
+use crate::lap::domain::lap::variables::Variables;
+
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Centroids closer to zero than this number of standard deviations are considered a "stay".
+const DEAD_BAND: f64 = 0.5;
+/// Highest level assigned to a tendency.
+const MAX_LEVEL: u8 = 3;
+/// Minimum membership of the second closest behaviour to report it as a tendency.
+const TENDENCY_THRESHOLD: f64 = 0.2;
+
+/// Configuration of the joint clustering of several difference channels.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct JointConfig {
+    /// Channels combined into the feature matrix.
+    pub channels: Vec<Channel>,
+    /// Length (m) of the centred moving average applied to every channel, if any.
+    pub window: Option<f32>,
+}
+
+/// What a behaviour asks the driver to do with one channel.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Tendency {
+    pub channel: Channel,
+    /// Centroid of the behaviour for this channel, in the units of the channel.
+    pub centroid: f64,
+    pub base: Base,
+}
+
+/// Combined driving behaviour represented by one cluster.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Behaviour {
+    pub tendencies: Vec<Tendency>,
+}
+
+/// Behaviour assigned to one sample: the closest one and, optionally, the second closest one
+/// when the sample also belongs to it.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum JointTag {
+    Single(usize),
+    Tendency(usize, usize),
+}
+
+/// Result of clustering several difference channels at once.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Default, Debug)]
+pub struct JointClusters {
+    pub channels: Vec<Channel>,
+    pub window: Option<f32>,
+    pub memberships: Vec<Vec<f64>>,
+    pub behaviours: Vec<Behaviour>,
+    pub tags: Vec<JointTag>,
+    /// Validity indices of the chosen model
+    pub validity: Option<ValidityIndices>,
+}
+
+impl JointClusters {
+    /// Fits FCM on the standardized feature matrix built from the channels of `joint_config`.
+    ///
+    /// Every channel is optionally smoothed over `window` metres and then standardized, so
+    /// all of them weigh the same in the distance used by FCM. Centroids are converted back to
+    /// the units of each channel and described as a `Behaviour`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no channel is configured, if the feature matrix cannot be built or
+    /// if the grid search does not find any model.
+    pub fn try_fit(
+        diff_variables: &Variables,
+        distances: &[f32],
+        fcm_grid_config: &Config,
+        joint_config: &JointConfig,
+    ) -> Result<Self, String> {
+        if joint_config.channels.is_empty() {
+            return Err("no channels configured for the joint clustering".to_string());
+        }
+
+        let columns: Vec<Vec<f64>> = joint_config
+            .channels
+            .iter()
+            .map(|channel| {
+                let values = channel.values(diff_variables);
+                joint_config.window.map_or_else(
+                    || values.clone(),
+                    |window| smooth(&values, distances, window),
+                )
+            })
+            .collect();
+
+        let n_samples = columns.iter().map(Vec::len).min().unwrap_or_default();
+        let statistics: Vec<(f64, f64)> = columns
+            .iter()
+            .map(|column| mean_and_std(&column[..n_samples]))
+            .collect();
+
+        let data = Array2::from_shape_fn((n_samples, columns.len()), |(i, j)| {
+            let (mean, std) = statistics[j];
+            if std > 0. {
+                (columns[j][i] - mean) / std
+            } else {
+                0.
+            }
+        });
+
+        let best_model = FcmGrid::new(fcm_grid_config.clone())
+            .get_best_fitted_model(&data)
+            .map_err(|e| format!("{e}"))?;
+
+        let behaviours = best_model
+            .centroids()
+            .outer_iter()
+            .map(|centroid| Behaviour {
+                tendencies: joint_config
+                    .channels
+                    .iter()
+                    .zip(centroid.iter())
+                    .zip(&statistics)
+                    .map(|((&channel, &z), &(mean, std))| Tendency {
+                        channel,
+                        centroid: z.mul_add(std, mean),
+                        base: base(z.mul_add(std, mean), std),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let memberships: Vec<Vec<f64>> = best_model
+            .memberships()
+            .outer_iter()
+            .map(|row| row.to_vec())
+            .collect();
+        let tags = memberships.iter().map(|row| tag(row)).collect();
+
+        Ok(Self {
+            channels: joint_config.channels.clone(),
+            window: joint_config.window,
+            memberships,
+            behaviours,
+            tags,
+            validity: best_model.validity().copied(),
+        })
+    }
+}
+
+impl Behaviour {
+    /// Describes the behaviour from the point of view of the target lap driver, e.g.
+    /// "Increase the brake and reduce the speed".
+    #[must_use]
+    pub fn description(&self) -> String {
+        let mut actions: Vec<String> = self
+            .tendencies
+            .iter()
+            .filter_map(|tendency| match tendency.base {
+                Base::Stay => None,
+                Base::Increase(level) => Some(format!(
+                    "increase the {}{}",
+                    tendency.channel,
+                    intensity(level)
+                )),
+                Base::Reduce(level) => Some(format!(
+                    "reduce the {}{}",
+                    tendency.channel,
+                    intensity(level)
+                )),
+            })
+            .collect();
+
+        let Some(last) = actions.pop() else {
+            return "Keep driving as you do".to_string();
+        };
+        let actions = if actions.is_empty() {
+            last
+        } else {
+            format!("{} and {last}", actions.join(", "))
+        };
+
+        let mut chars = actions.chars();
+        chars.next().map_or_else(String::new, |first| {
+            first.to_uppercase().chain(chars).collect()
+        })
+    }
+}
+
+/// Centred moving average of `values` over `window` metres.
+#[allow(clippy::cast_precision_loss)]
+fn smooth(values: &[f64], distances: &[f32], window: f32) -> Vec<f64> {
+    let len = values.len().min(distances.len());
+    let half = window / 2.;
+
+    let mut prefix = Vec::with_capacity(len + 1);
+    prefix.push(0.);
+    for value in &values[..len] {
+        prefix.push(prefix[prefix.len() - 1] + value);
+    }
+
+    (0..len)
+        .map(|i| {
+            let from = distances[..len].partition_point(|&d| d < distances[i] - half);
+            let to = distances[..len].partition_point(|&d| d <= distances[i] + half);
+            (prefix[to] - prefix[from]) / (to - from) as f64
+        })
+        .collect()
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn mean_and_std(values: &[f64]) -> (f64, f64) {
+    if values.is_empty() {
+        return (0., 0.);
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    (mean, variance.sqrt())
+}
+
+/// Tags a centroid from its distance to zero, measured in standard deviations.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn base(centroid: f64, std: f64) -> Base {
+    if std <= 0. || centroid.abs() < DEAD_BAND * std {
+        return Base::Stay;
+    }
+    let level = ((centroid.abs() / std - DEAD_BAND).floor() as u8).min(MAX_LEVEL);
+    if centroid > 0. {
+        Base::Increase(level)
+    } else {
+        Base::Reduce(level)
+    }
+}
+
+fn tag(memberships: &[f64]) -> JointTag {
+    let mut indexed: Vec<(usize, f64)> = memberships.iter().copied().enumerate().collect();
+    indexed.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+
+    match (indexed.first(), indexed.get(1)) {
+        (Some(&(first, _)), Some(&(second, membership))) if membership >= TENDENCY_THRESHOLD => {
+            JointTag::Tendency(first, second)
+        }
+        (Some(&(first, _)), _) => JointTag::Single(first),
+        _ => JointTag::Single(0),
+    }
+}
+
+fn intensity(level: u8) -> &'static str {
+    match level {
+        0 => "",
+        1 => " significantly",
+        2 => " greatly",
+        _ => " a lot",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> Config {
+        Config::new(
+            (2, None, None),
+            (2., None, None),
+            (100, None, None),
+            (1e-5, None, None),
+        )
+    }
+
+    /// Differences where the first half of the lap shows "more brake, less speed" and the
+    /// second half "more throttle, more speed" (differences are reference - target).
+    fn test_differences() -> (Variables, Vec<f32>) {
+        let mut variables = Variables::default();
+        let mut distances = Vec::new();
+        for i in 0..200 {
+            #[allow(clippy::cast_precision_loss)]
+            let d = i as f32;
+            let first_half = i < 100;
+            let noise = if i % 2 == 0 { 0.01 } else { -0.01 };
+            distances.push(d);
+            variables
+                .speed
+                .push(if first_half { -5. } else { 5. } + noise);
+            variables
+                .brake
+                .push(if first_half { 0.3 } else { 0. } + noise);
+            variables
+                .throttle
+                .push(if first_half { -0.4 } else { 0.4 } + noise);
+        }
+        (variables, distances)
+    }
+
+    #[test]
+    fn finds_combined_behaviours() {
+        let (variables, distances) = test_differences();
+        let joint_config = JointConfig {
+            channels: vec![Channel::Speed, Channel::Brake, Channel::Throttle],
+            window: Some(5.),
+        };
+
+        let clusters =
+            JointClusters::try_fit(&variables, &distances, &test_config(), &joint_config).unwrap();
+
+        assert_eq!(clusters.behaviours.len(), 2);
+        assert_eq!(clusters.tags.len(), 200);
+
+        let JointTag::Single(braking) = clusters.tags[10] else {
+            panic!("unexpected tag {:?}", clusters.tags[10]);
+        };
+        let behaviour = &clusters.behaviours[braking];
+        assert!(matches!(behaviour.tendencies[0].base, Base::Reduce(_)));
+        assert!(matches!(behaviour.tendencies[1].base, Base::Increase(_)));
+        assert!(matches!(behaviour.tendencies[2].base, Base::Reduce(_)));
+        assert_eq!(
+            behaviour.description(),
+            "Reduce the speed, increase the brake significantly and reduce the throttle"
+        );
+        assert_ne!(clusters.tags[150], clusters.tags[10]);
+    }
+
+    #[test]
+    fn fails_without_channels() {
+        let (variables, distances) = test_differences();
+        let joint_config = JointConfig {
+            channels: vec![],
+            window: None,
+        };
+
+        assert!(
+            JointClusters::try_fit(&variables, &distances, &test_config(), &joint_config).is_err()
+        );
+    }
+
+    #[test]
+    fn smooths_over_distance_window() {
+        let smoothed = smooth(&[0., 0., 3., 0., 0.], &[0., 1., 2., 3., 4.], 2.);
+
+        assert_eq!(smoothed, vec![0., 1., 1., 1., 0.]);
+    }
+}
@@ -0,0 +1,187 @@
+use crate::analysis::domain::analysis::clustering_model::{seeded_rng, ClusteringModel, Error};
+use crate::analysis::domain::analysis::fuzzy_c_means::{FittedModel, FuzzyCMeans};
+
+use ndarray::{Array2, ArrayView, Axis, Ix1};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+
+/// K-means clustering with k-means++ initialisation.
+pub struct KMeans {
+    c: usize,
+    max_iter: usize,
+    error: f64,
+    seed: Option<u64>,
+}
+
+impl KMeans {
+    /// Creates a new k-means model.
+    ///
+    /// # Arguments
+    ///
+    /// * `c` - Number of clusters. Must be greater than 0.
+    /// * `max_iter` - Maximum number of iterations.
+    /// * `error` - Maximum centroid shift to consider that the algorithm has converged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidParameter` if `c` is 0.
+    pub fn try_new(c: usize, max_iter: usize, error: f64) -> Result<Self, Error> {
+        if c == 0 {
+            return Err(Error::InvalidParameter(
+                "number of clusters must be greater than 0".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            c,
+            max_iter,
+            error,
+            seed: None,
+        })
+    }
+
+    /// Seeds the initialisation, so fitting the same data always gives the same model.
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl ClusteringModel for KMeans {
+    fn try_fit(&self, data: &Array2<f64>) -> Result<FittedModel, Error> {
+        let num_samples = data.nrows();
+        if num_samples < self.c {
+            return Err(Error::NotEnoughSamples(num_samples, self.c));
+        }
+
+        let mut centroids = k_means_plus_plus(data, self.c, &mut seeded_rng(self.seed));
+        let mut labels = vec![0; num_samples];
+
+        for _ in 0..self.max_iter {
+            for (label, sample) in labels.iter_mut().zip(data.outer_iter()) {
+                *label = nearest(&sample, &centroids);
+            }
+
+            let mut new_centroids = Array2::<f64>::zeros(centroids.dim());
+            let mut counts = vec![0usize; self.c];
+            for (&label, sample) in labels.iter().zip(data.outer_iter()) {
+                let mut centroid = new_centroids.row_mut(label);
+                centroid += &sample;
+                counts[label] += 1;
+            }
+            for (j, mut centroid) in new_centroids.outer_iter_mut().enumerate() {
+                if counts[j] == 0 {
+                    // Keep empty clusters where they were
+                    centroid.assign(&centroids.row(j));
+                } else {
+                    #[allow(clippy::cast_precision_loss)]
+                    let count = counts[j] as f64;
+                    centroid /= count;
+                }
+            }
+
+            let shift = centroids
+                .outer_iter()
+                .zip(new_centroids.outer_iter())
+                .map(|(old, new)| FuzzyCMeans::distance(&old, &new))
+                .fold(0., f64::max);
+            centroids = new_centroids;
+
+            if shift < self.error {
+                break;
+            }
+        }
+
+        for (label, sample) in labels.iter_mut().zip(data.outer_iter()) {
+            *label = nearest(&sample, &centroids);
+        }
+
+        Ok(FittedModel::new(centroids, one_hot(&labels, self.c)))
+    }
+}
+
+/// Chooses `c` initial centroids among the samples of `data`, each one with a probability
+/// proportional to its squared distance to the closest centroid already chosen.
+pub(crate) fn k_means_plus_plus<R: Rng>(data: &Array2<f64>, c: usize, rng: &mut R) -> Array2<f64> {
+    let num_samples = data.nrows();
+    let mut centroids = Array2::<f64>::zeros((c, data.ncols()));
+    centroids
+        .row_mut(0)
+        .assign(&data.row(rng.gen_range(0..num_samples)));
+
+    for j in 1..c {
+        let weights: Vec<f64> = data
+            .outer_iter()
+            .map(|sample| {
+                centroids
+                    .outer_iter()
+                    .take(j)
+                    .map(|centroid| FuzzyCMeans::distance(&sample, &centroid).powi(2))
+                    .fold(f64::INFINITY, f64::min)
+            })
+            .collect();
+
+        // All samples already match a centroid: pick any of them
+        let idx = match WeightedIndex::new(&weights) {
+            Ok(distribution) => distribution.sample(rng),
+            Err(_) => rng.gen_range(0..num_samples),
+        };
+        centroids.row_mut(j).assign(&data.row(idx));
+    }
+
+    centroids
+}
+
+/// Index of the closest centroid to `sample`.
+pub(crate) fn nearest(sample: &ArrayView<f64, Ix1>, centroids: &Array2<f64>) -> usize {
+    centroids
+        .axis_iter(Axis(0))
+        .map(|centroid| FuzzyCMeans::distance(sample, &centroid))
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map_or(0, |(j, _)| j)
+}
+
+/// Crisp membership matrix from the cluster label of each sample.
+pub(crate) fn one_hot(labels: &[usize], c: usize) -> Array2<f64> {
+    let mut memberships = Array2::<f64>::zeros((labels.len(), c));
+    for (i, &label) in labels.iter().enumerate() {
+        memberships[[i, label]] = 1.;
+    }
+    memberships
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn separates_two_groups() {
+        let data = array![[0.0], [0.1], [0.2], [10.0], [10.1], [10.2]];
+
+        let model = KMeans::try_new(2, 100, 1e-6)
+            .unwrap()
+            .try_fit(&data)
+            .unwrap();
+
+        let memberships = model.memberships();
+        assert_eq!(memberships.row(0), memberships.row(2));
+        assert_eq!(memberships.row(3), memberships.row(5));
+        assert_ne!(memberships.row(0), memberships.row(3));
+        let mut centroids: Vec<f64> = model.centroids().iter().copied().collect();
+        centroids.sort_by(f64::total_cmp);
+        assert!((centroids[0] - 0.1).abs() < 1e-9);
+        assert!((centroids[1] - 10.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn needs_enough_samples() {
+        let data = array![[0.0], [1.0]];
+
+        let result = KMeans::try_new(3, 100, 1e-6).unwrap().try_fit(&data);
+
+        assert_eq!(result.unwrap_err(), Error::NotEnoughSamples(2, 3));
+    }
+}
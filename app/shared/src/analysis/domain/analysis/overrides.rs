@@ -0,0 +1,62 @@
+use crate::analysis::domain::analysis::clustering_model::Algorithm;
+use crate::analysis::domain::analysis::fcm_grid::Config;
+use crate::analysis::domain::analysis::joint_clusters::JointConfig;
+use crate::analysis::domain::analysis::tag_policy::ChannelTagPolicy;
+use crate::analysis::domain::analysis::validity::Criterion;
+
+use serde::{Deserialize, Serialize};
+
+/// Parameters replacing the configured ones when an analysis is run again. Every `None` keeps
+/// the configured value.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug, Default)]
+pub struct Overrides {
+    /// Number of clusters (start, max, increment).
+    pub c: Option<(usize, Option<usize>, Option<usize>)>,
+    /// Fuzziness parameter (start, max, increment).
+    pub m: Option<(f64, Option<f64>, Option<f64>)>,
+    /// Maximum number of iterations (start, max, increment).
+    pub max_iter: Option<(usize, Option<usize>, Option<usize>)>,
+    /// Convergence error (start, max, increment).
+    pub error: Option<(f64, Option<f64>, Option<f64>)>,
+    pub joint: Option<JointConfig>,
+    pub algorithm: Option<Algorithm>,
+    pub criterion: Option<Criterion>,
+    pub seed: Option<u64>,
+    pub tag_policies: Option<Vec<ChannelTagPolicy>>,
+}
+
+impl Overrides {
+    /// Returns `config` with every parameter given in the overrides replaced.
+    #[must_use]
+    pub fn apply(&self, config: Config) -> Config {
+        let mut config = config;
+        if let Some(c) = self.c {
+            config.c = c;
+        }
+        if let Some(m) = self.m {
+            config.m = m;
+        }
+        if let Some(max_iter) = self.max_iter {
+            config.max_iter = max_iter;
+        }
+        if let Some(error) = self.error {
+            config.error = error;
+        }
+        if let Some(joint) = &self.joint {
+            config = config.with_joint(joint.clone());
+        }
+        if let Some(algorithm) = &self.algorithm {
+            config = config.with_algorithm(algorithm.clone());
+        }
+        if let Some(criterion) = self.criterion {
+            config = config.with_criterion(criterion);
+        }
+        if let Some(seed) = self.seed {
+            config = config.with_seed(seed);
+        }
+        if let Some(tag_policies) = &self.tag_policies {
+            config = config.with_tag_policies(tag_policies.clone());
+        }
+        config
+    }
+}
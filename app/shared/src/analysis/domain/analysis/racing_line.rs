@@ -0,0 +1,308 @@
+use crate::analysis::domain::analysis::geo;
+use crate::analysis::domain::analysis::segments::Segments;
+use crate::lap::domain::lap::variables::Variables;
+
+use serde::{Deserialize, Serialize};
+
+/// Distance (m) before and after each sample used to measure headings and curvature.
+const WINDOW: f64 = 15.;
+/// Radius (m) reported when a line is straight or almost straight.
+const MAX_RADIUS: f64 = 1000.;
+/// Distance (m) before and after each sample where the nearest point of the reference line is
+/// searched, so sections of the circuit running close to each other are not mistaken.
+const SEARCH_RANGE: f64 = 50.;
+
+/// Racing line of both laps through one corner.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct CornerLine {
+    pub number: u16,
+    /// Tightest radius (m) of the reference line within the corner.
+    pub reference_radius: f32,
+    /// Tightest radius (m) of the target line within the corner.
+    pub target_radius: f32,
+    /// Length (m) of the reference line from corner entry to exit.
+    pub reference_length: f32,
+    /// Length (m) of the target line from corner entry to exit.
+    pub target_length: f32,
+    /// Lateral offset (m) of the target line at the reference apex, positive to the left.
+    pub apex_offset: Option<f32>,
+}
+
+/// Comparison of the racing lines of both laps, computed from their GPS traces.
+///
+/// Channels are sampled on the union distances of the analysis, so they can be drawn on
+/// the circuit map.
+#[derive(Serialize, Deserialize, PartialEq, Default, Clone, Debug)]
+pub struct RacingLine {
+    /// Signed distance (m) from the nearest point of the reference line to the target line,
+    /// measured along the normal of the reference line. Positive when the target line is to
+    /// the left.
+    pub lateral_offset: Vec<f32>,
+    /// Signed distance (m) from the reference position to the target position at the same lap
+    /// distance, measured along the heading of the reference line. Positive when the target
+    /// lap is ahead.
+    #[serde(default)]
+    pub longitudinal_offset: Vec<f32>,
+    /// Radius (m) of the reference line, capped for straight sections.
+    pub reference_radius: Vec<f32>,
+    /// Radius (m) of the target line, capped for straight sections.
+    pub target_radius: Vec<f32>,
+    /// Cumulative length of the target line minus the cumulative length of the reference
+    /// line (m). Positive when the target lap has driven a longer line so far.
+    pub length_difference: Vec<f32>,
+    /// Per corner summary, in track order.
+    pub corners: Vec<CornerLine>,
+}
+
+/// Compares the racing lines of both laps.
+///
+/// Both `reference` and `target` must be sampled on the same distances. Both laps are
+/// projected into the same local metric frame. Returns `None` when any of the laps lacks
+/// GPS coordinates.
+#[must_use]
+pub fn calculate(
+    segments: &Segments,
+    reference: &Variables,
+    target: &Variables,
+) -> Option<RacingLine> {
+    let len = reference.distance.len();
+    let has_gps =
+        |variables: &Variables| variables.latitude.len() == len && variables.longitude.len() == len;
+    if len < 2 || target.distance.len() != len || !has_gps(reference) || !has_gps(target) {
+        return None;
+    }
+
+    let origin = geo::origin(&reference.latitude, &reference.longitude)?;
+    let reference_points = geo::project_from(&reference.latitude, &reference.longitude, origin);
+    let target_points = geo::project_from(&target.latitude, &target.longitude, origin);
+    let distances: Vec<f64> = reference.distance.iter().map(|&d| f64::from(d)).collect();
+
+    let lateral_offset = lateral_offset(&reference_points, &target_points, &distances);
+    let longitudinal_offset = longitudinal_offset(&reference_points, &target_points, &distances);
+    let reference_radius = radius(&reference_points, &distances);
+    let target_radius = radius(&target_points, &distances);
+    let reference_length = cumulative_length(&reference_points);
+    let target_length = cumulative_length(&target_points);
+    let length_difference = target_length
+        .iter()
+        .zip(&reference_length)
+        .map(|(t, r)| t - r)
+        .collect();
+
+    let corners = segments
+        .corners()
+        .filter_map(|corner| {
+            let entry = reference.distance.partition_point(|&d| d < corner.entry);
+            let exit = reference
+                .distance
+                .partition_point(|&d| d <= corner.exit)
+                .checked_sub(1)?;
+            if entry > exit {
+                return None;
+            }
+            let tightest = |radius: &[f32]| {
+                radius[entry..=exit]
+                    .iter()
+                    .copied()
+                    .fold(f32::INFINITY, f32::min)
+            };
+            let apex_offset = corner.apex.map(|apex| {
+                let idx = reference
+                    .distance
+                    .partition_point(|&d| d < apex)
+                    .min(len - 1);
+                lateral_offset[idx]
+            });
+
+            Some(CornerLine {
+                number: corner.number,
+                reference_radius: tightest(&reference_radius),
+                target_radius: tightest(&target_radius),
+                reference_length: reference_length[exit] - reference_length[entry],
+                target_length: target_length[exit] - target_length[entry],
+                apex_offset,
+            })
+        })
+        .collect();
+
+    Some(RacingLine {
+        lateral_offset,
+        longitudinal_offset,
+        reference_radius,
+        target_radius,
+        length_difference,
+        corners,
+    })
+}
+
+/// Offset of every target point from the nearest point of the reference line, as laps driven
+/// on different lines cover a different distance to reach the same point of the circuit.
+#[allow(clippy::cast_possible_truncation)]
+fn lateral_offset(reference: &[(f64, f64)], target: &[(f64, f64)], distances: &[f64]) -> Vec<f32> {
+    distances
+        .iter()
+        .zip(target)
+        .map(|(&d, &(xt, yt))| {
+            let from = distances.partition_point(|&x| x < d - SEARCH_RANGE);
+            let to = distances.partition_point(|&x| x <= d + SEARCH_RANGE);
+            let squared_distance =
+                |i: usize| (reference[i].0 - xt).powi(2) + (reference[i].1 - yt).powi(2);
+            let Some(nearest) =
+                (from..to).min_by(|&a, &b| squared_distance(a).total_cmp(&squared_distance(b)))
+            else {
+                return 0.;
+            };
+            let ((xr, yr), d) = (reference[nearest], distances[nearest]);
+
+            let before = geo::point_at(reference, distances, d - WINDOW);
+            let after = geo::point_at(reference, distances, d + WINDOW);
+            if before == after {
+                return 0.;
+            }
+            let heading = geo::heading(before, after);
+            // Projection onto the left hand normal of the reference line
+            ((xt - xr).mul_add(-heading.sin(), (yt - yr) * heading.cos())) as f32
+        })
+        .collect()
+}
+
+/// Offset of every target point from the reference point at the same distance, along the
+/// reference line.
+#[allow(clippy::cast_possible_truncation)]
+fn longitudinal_offset(
+    reference: &[(f64, f64)],
+    target: &[(f64, f64)],
+    distances: &[f64],
+) -> Vec<f32> {
+    distances
+        .iter()
+        .zip(reference.iter().zip(target))
+        .map(|(&d, (&(xr, yr), &(xt, yt)))| {
+            let before = geo::point_at(reference, distances, d - WINDOW);
+            let after = geo::point_at(reference, distances, d + WINDOW);
+            if before == after {
+                return 0.;
+            }
+            let heading = geo::heading(before, after);
+            // Projection onto the heading of the reference line
+            ((xt - xr).mul_add(heading.cos(), (yt - yr) * heading.sin())) as f32
+        })
+        .collect()
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn radius(points: &[(f64, f64)], distances: &[f64]) -> Vec<f32> {
+    geo::curvature(points, distances, WINDOW)
+        .into_iter()
+        .map(|curvature| (1. / curvature.abs()).min(MAX_RADIUS) as f32)
+        .collect()
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn cumulative_length(points: &[(f64, f64)]) -> Vec<f32> {
+    let mut length = 0.;
+    std::iter::once(0.)
+        .chain(points.windows(2).map(|w| {
+            length += (w[1].0 - w[0].0).hypot(w[1].1 - w[0].1);
+            length as f32
+        }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::domain::analysis::segment::Segment;
+
+    const METRES_PER_DEGREE: f64 = 111_195.;
+
+    /// A 600 m lap sampled every metre going east, with a 90 degree left hand corner of
+    /// `radius` metres starting at 200 m, shifted `offset` metres to the north.
+    fn test_variables(radius: f64, offset: f64) -> Variables {
+        let mut variables = Variables::default();
+        let (mut x, mut y, mut heading) = (0f64, offset, 0f64);
+        let corner_length = radius * std::f64::consts::FRAC_PI_2;
+
+        for i in 0..600 {
+            #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+            let d = i as f32;
+            if f64::from(d) >= 200. && f64::from(d) < 200. + corner_length {
+                heading += 1. / radius;
+            }
+            x += heading.cos();
+            y += heading.sin();
+
+            variables.distance.push(d);
+            variables.latitude.push(y / METRES_PER_DEGREE);
+            variables.longitude.push(x / METRES_PER_DEGREE);
+        }
+        variables
+    }
+
+    #[test]
+    fn measures_lateral_offset_to_the_left() {
+        let reference = test_variables(50., 0.);
+        let target = test_variables(50., 2.);
+
+        let line = calculate(&Segments::default(), &reference, &target).unwrap();
+
+        assert!((line.lateral_offset[100] - 2.).abs() < 0.01);
+        assert!(line.longitudinal_offset[100].abs() < 0.01);
+        assert!(line.length_difference[100].abs() < 0.01);
+    }
+
+    #[test]
+    fn same_line_ahead_has_no_lateral_offset() {
+        let reference = test_variables(50., 0.);
+        // The target lap is 10 m ahead on the same line, e.g. after a longer line before
+        let mut target = test_variables(50., 0.);
+        target.latitude.rotate_left(10);
+        target.longitude.rotate_left(10);
+
+        let line = calculate(&Segments::default(), &reference, &target).unwrap();
+
+        assert!(
+            line.lateral_offset[230].abs() < 0.1,
+            "{}",
+            line.lateral_offset[230]
+        );
+        assert!(
+            (line.longitudinal_offset[100] - 10.).abs() < 0.01,
+            "{}",
+            line.longitudinal_offset[100]
+        );
+    }
+
+    #[test]
+    fn measures_radius_and_length_through_corners() {
+        let segments = Segments::from(vec![Segment::corner(1, 210., 240., 270.)]);
+        let reference = test_variables(50., 0.);
+        let target = test_variables(80., 0.);
+
+        let line = calculate(&segments, &reference, &target).unwrap();
+
+        assert_eq!(line.corners.len(), 1);
+        let corner = &line.corners[0];
+        assert!(
+            (corner.reference_radius - 50.).abs() < 2.,
+            "{}",
+            corner.reference_radius
+        );
+        assert!(
+            (corner.target_radius - 80.).abs() < 3.,
+            "{}",
+            corner.target_radius
+        );
+        assert!((corner.reference_length - 60.).abs() < 1.);
+        assert!((line.reference_radius[50] - 1000.).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn no_racing_line_without_gps() {
+        let mut reference = test_variables(50., 0.);
+        let target = test_variables(50., 0.);
+        reference.latitude.clear();
+
+        assert_eq!(calculate(&Segments::default(), &reference, &target), None);
+    }
+}
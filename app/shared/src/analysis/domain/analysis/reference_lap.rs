@@ -1,4 +1,6 @@
+use crate::lap::domain::lap::events::Event;
 use crate::lap::domain::lap::variables::Variables;
+use crate::lap::domain::lap::wheel_slip::WheelSlip;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, PartialEq, Clone)]
@@ -8,6 +10,13 @@ pub struct ReferenceLap {
     pub category: String,
     pub car: String,
     pub variables: Variables,
+    /// Lock-ups and wheelspins of the lap, at the distances of the original lap
+    #[serde(default)]
+    pub wheel_slips: Vec<WheelSlip>,
+    /// Off-tracks, incidents, spins and pit-road entries of the lap, at the distances of the
+    /// original lap
+    #[serde(default)]
+    pub events: Vec<Event>,
 }
 
 impl ReferenceLap {
@@ -18,6 +27,8 @@ impl ReferenceLap {
         category: String,
         car: String,
         variables: Variables,
+        wheel_slips: Vec<WheelSlip>,
+        events: Vec<Event>,
     ) -> Self {
         Self {
             number,
@@ -25,6 +36,8 @@ impl ReferenceLap {
             category,
             car,
             variables,
+            wheel_slips,
+            events,
         }
     }
 }
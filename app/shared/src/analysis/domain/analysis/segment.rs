@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+/// Type of a track segment.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Kind {
+    Corner,
+    Straight,
+}
+
+/// A section of the circuit, numbered from the start/finish line.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Segment {
+    /// Segment type.
+    pub kind: Kind,
+    /// Order of the segment among the segments of the same kind (T1, T2...).
+    pub number: u16,
+    /// Distance where the segment starts (m).
+    pub entry: f32,
+    /// Distance of the slowest point of a corner (m). Straights have no apex.
+    pub apex: Option<f32>,
+    /// Distance where the segment ends (m).
+    pub exit: f32,
+}
+
+impl Segment {
+    #[must_use]
+    pub const fn corner(number: u16, entry: f32, apex: f32, exit: f32) -> Self {
+        Self {
+            kind: Kind::Corner,
+            number,
+            entry,
+            apex: Some(apex),
+            exit,
+        }
+    }
+
+    #[must_use]
+    pub const fn straight(number: u16, entry: f32, exit: f32) -> Self {
+        Self {
+            kind: Kind::Straight,
+            number,
+            entry,
+            apex: None,
+            exit,
+        }
+    }
+
+    #[must_use]
+    pub fn is_corner(&self) -> bool {
+        self.kind == Kind::Corner
+    }
+
+    /// Human friendly name of the segment, e.g. `T3` or `S2`.
+    #[must_use]
+    pub fn name(&self) -> String {
+        match self.kind {
+            Kind::Corner => format!("T{}", self.number),
+            Kind::Straight => format!("S{}", self.number),
+        }
+    }
+
+    /// Whether the given distance lies within the segment.
+    #[must_use]
+    pub fn contains(&self, distance: f32) -> bool {
+        distance >= self.entry && distance <= self.exit
+    }
+}
@@ -0,0 +1,295 @@
+use crate::analysis::domain::analysis::geo;
+use crate::analysis::domain::analysis::segment::Segment;
+use crate::analysis::domain::analysis::segments::Segments;
+use crate::lap::domain::lap::variables::Variables;
+
+use std::cmp::Ordering;
+
+/// Distance (m) before and after each sample used to measure the heading change.
+const CURVATURE_WINDOW: f64 = 15.;
+/// Minimum curvature (1/m) to consider that the car is cornering (radius below 250 m).
+const CURVATURE_THRESHOLD: f64 = 1. / 250.;
+/// Minimum steering wheel angle (rad) to consider that the car is cornering.
+const STEERING_THRESHOLD: f32 = 0.35;
+/// Corners separated by less than this distance (m) are merged into one.
+const MIN_GAP: f32 = 30.;
+/// Corners shorter than this distance (m) are discarded as noise.
+const MIN_CORNER_LENGTH: f32 = 20.;
+/// Distance (m) before and after a sample within which it has to be the slowest one to be a
+/// speed minimum.
+const SPEED_MINIMUM_WINDOW: f32 = 100.;
+/// Speed (m/s) the car has to lose before a speed minimum, and gain after it, for the minimum
+/// to be a corner rather than noise or a gear change.
+const MIN_SPEED_DROP: f32 = 5.;
+
+/// Splits a lap into corners and straights.
+///
+/// A sample is considered part of a corner when the curvature of the GPS trace or the
+/// steering wheel angle exceed a threshold, or when it is close to the speed of a clear speed
+/// minimum, which finds the slow corners taken with little steering. Close corners are merged
+/// and very short ones discarded. The apex of each corner is placed at its speed minimum. Corners and straights
+/// are numbered independently from the start/finish line. Laps of the same circuit may not
+/// yield exactly the same corners, so the segments of a circuit are detected only once.
+///
+/// When GPS coordinates are missing, the detection relies only on the steering wheel angle and
+/// the speed.
+#[must_use]
+pub fn detect(variables: &Variables) -> Segments {
+    let distances = &variables.distance;
+    let (Some(&first), Some(&last)) = (distances.first(), distances.last()) else {
+        return Segments::default();
+    };
+
+    let cornering = cornering_mask(variables);
+    let regions = corner_regions(&cornering, distances);
+
+    let mut segments = Vec::new();
+    let (mut corner_number, mut straight_number) = (0u16, 0u16);
+    let mut cursor = first;
+
+    for (start, end) in regions {
+        let (entry, exit) = (distances[start], distances[end]);
+
+        if entry > cursor {
+            straight_number += 1;
+            segments.push(Segment::straight(straight_number, cursor, entry));
+        }
+
+        corner_number += 1;
+        segments.push(Segment::corner(
+            corner_number,
+            entry,
+            apex(variables, start, end),
+            exit,
+        ));
+        cursor = exit;
+    }
+
+    if last > cursor {
+        straight_number += 1;
+        segments.push(Segment::straight(straight_number, cursor, last));
+    }
+
+    Segments::from(segments)
+}
+
+fn cornering_mask(variables: &Variables) -> Vec<bool> {
+    let slow = speed_minima_mask(variables);
+    let distances: Vec<f64> = variables.distance.iter().map(|&d| f64::from(d)).collect();
+
+    let curvature = if variables.latitude.len() == distances.len()
+        && variables.longitude.len() == distances.len()
+    {
+        let points = geo::project(&variables.latitude, &variables.longitude);
+        geo::curvature(&points, &distances, CURVATURE_WINDOW)
+    } else {
+        vec![0.; distances.len()]
+    };
+
+    (0..distances.len())
+        .map(|i| {
+            slow[i]
+                || curvature[i].abs() > CURVATURE_THRESHOLD
+                || variables
+                    .steering_wheel_angle
+                    .get(i)
+                    .is_some_and(|angle| angle.abs() > STEERING_THRESHOLD)
+        })
+        .collect()
+}
+
+/// Marks the samples around every speed minimum whose speed is within half of
+/// `MIN_SPEED_DROP` of it.
+fn speed_minima_mask(variables: &Variables) -> Vec<bool> {
+    let (speeds, distances) = (&variables.speed, &variables.distance);
+    let len = speeds.len().min(distances.len());
+    let mut mask = vec![false; distances.len()];
+
+    for i in 0..len {
+        let from = distances[..len].partition_point(|&d| d < distances[i] - SPEED_MINIMUM_WINDOW);
+        let to = distances[..len].partition_point(|&d| d <= distances[i] + SPEED_MINIMUM_WINDOW);
+        let speed = speeds[i];
+        let (before, after) = (&speeds[from..i], &speeds[i + 1..to]);
+
+        // The first sample of a plateau is the minimum
+        let is_minimum = before.iter().all(|&s| s > speed) && after.iter().all(|&s| s >= speed);
+        let dropped = |side: &[f32]| side.iter().any(|&s| s >= speed + MIN_SPEED_DROP);
+        if !(is_minimum && dropped(before) && dropped(after)) {
+            continue;
+        }
+
+        let slow = |j: &usize| speeds[*j] <= speed + MIN_SPEED_DROP / 2.;
+        let start = (from..i).rev().take_while(slow).last().unwrap_or(i);
+        let end = (i + 1..to).take_while(slow).last().unwrap_or(i);
+        mask[start..=end].fill(true);
+    }
+
+    mask
+}
+
+/// Returns the (start, end) inclusive indices of each corner.
+fn corner_regions(cornering: &[bool], distances: &[f32]) -> Vec<(usize, usize)> {
+    let mut regions: Vec<(usize, usize)> = Vec::new();
+    let mut start: Option<usize> = None;
+
+    for (i, &is_corner) in cornering.iter().enumerate() {
+        match (is_corner, start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                regions.push((s, i - 1));
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    if let Some(s) = start {
+        regions.push((s, cornering.len() - 1));
+    }
+
+    let merged = regions
+        .into_iter()
+        .fold(Vec::<(usize, usize)>::new(), |mut acc, (start, end)| {
+            match acc.last_mut() {
+                Some(previous) if distances[start] - distances[previous.1] < MIN_GAP => {
+                    previous.1 = end;
+                }
+                _ => acc.push((start, end)),
+            }
+            acc
+        });
+
+    merged
+        .into_iter()
+        .filter(|&(start, end)| distances[end] - distances[start] >= MIN_CORNER_LENGTH)
+        .collect()
+}
+
+fn apex(variables: &Variables, start: usize, end: usize) -> f32 {
+    variables
+        .speed
+        .get(start..=end)
+        .and_then(|speeds| {
+            speeds
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+                .map(|(i, _)| variables.distance[start + i])
+        })
+        .unwrap_or_else(|| (variables.distance[start] + variables.distance[end]) / 2.)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::domain::analysis::segment::Kind;
+
+    /// Builds a 1000 m lap sampled every metre with a left hand corner between 400 m and
+    /// 500 m (radius 50 m) and straights elsewhere.
+    fn test_variables() -> Variables {
+        let mut variables = Variables::default();
+        let (mut x, mut y, mut heading) = (0f64, 0f64, 0f64);
+        let metres_per_degree = 111_195.;
+
+        for i in 0..=1000 {
+            #[allow(clippy::cast_precision_loss)]
+            let d = i as f32;
+            let in_corner = (400. ..500.).contains(&d);
+            if in_corner {
+                heading += 1. / 50.;
+            }
+            x += heading.cos();
+            y += heading.sin();
+
+            variables.distance.push(d);
+            variables.latitude.push(y / metres_per_degree);
+            variables.longitude.push(x / metres_per_degree);
+            variables.speed.push(if in_corner {
+                20. + (d - 450.).abs() / 10.
+            } else {
+                60.
+            });
+            variables.steering_wheel_angle.push(0.);
+        }
+
+        variables
+    }
+
+    #[test]
+    fn detects_corner_between_straights() {
+        let segments = detect(&test_variables());
+
+        let kinds: Vec<Kind> = segments.iter().map(|s| s.kind).collect();
+        assert_eq!(kinds, vec![Kind::Straight, Kind::Corner, Kind::Straight]);
+
+        let corner = &segments[1];
+        assert_eq!(corner.name(), "T1");
+        assert!((corner.entry - 400.).abs() < 20., "entry {}", corner.entry);
+        assert!((corner.exit - 500.).abs() < 20., "exit {}", corner.exit);
+        assert!((corner.apex.unwrap() - 450.).abs() < 1.);
+    }
+
+    #[test]
+    fn detects_corners_from_steering_without_gps() {
+        let mut variables = test_variables();
+        variables.latitude.clear();
+        variables.longitude.clear();
+        // Without the slow corner of the GPS trace
+        variables.speed.fill(60.);
+        for (i, angle) in variables.steering_wheel_angle.iter_mut().enumerate() {
+            if (100..200).contains(&i) || (700..760).contains(&i) {
+                *angle = -1.;
+            }
+        }
+
+        let segments = detect(&variables);
+        let corners: Vec<&Segment> = segments.corners().collect();
+
+        assert_eq!(corners.len(), 2);
+        assert_eq!(corners[0].number, 1);
+        assert_eq!(corners[1].number, 2);
+        assert!((corners[1].entry - 700.).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn detects_corners_from_speed_minima() {
+        let mut variables = test_variables();
+        variables.latitude.clear();
+        variables.longitude.clear();
+
+        let segments = detect(&variables);
+        let corners: Vec<&Segment> = segments.corners().collect();
+
+        // The speed is within 2.5 m/s of the 20 m/s minimum between 425 m and 475 m
+        assert_eq!(corners.len(), 1);
+        assert!(
+            (corners[0].entry - 425.).abs() < 1.,
+            "entry {}",
+            corners[0].entry
+        );
+        assert!(
+            (corners[0].exit - 475.).abs() < 1.,
+            "exit {}",
+            corners[0].exit
+        );
+        assert!((corners[0].apex.unwrap() - 450.).abs() < 1.);
+    }
+
+    #[test]
+    fn ignores_small_speed_variations() {
+        let mut variables = test_variables();
+        variables.latitude.clear();
+        variables.longitude.clear();
+        for (i, speed) in variables.speed.iter_mut().enumerate() {
+            #[allow(clippy::cast_precision_loss)]
+            let d = i as f32;
+            *speed = 60. - (d / 50.).sin() * 2.;
+        }
+
+        assert_eq!(detect(&variables).corners().count(), 0);
+    }
+
+    #[test]
+    fn empty_lap_has_no_segments() {
+        assert!(detect(&Variables::default()).is_empty());
+    }
+}
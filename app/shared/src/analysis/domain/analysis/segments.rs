@@ -0,0 +1,57 @@
+use crate::analysis::domain::analysis::segment::Segment;
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, DerefMut};
+
+/// Represents the ordered collection of segments of a circuit.
+#[derive(Serialize, Deserialize, PartialEq, Default, Clone, Debug)]
+pub struct Segments {
+    segments: Vec<Segment>,
+}
+
+impl Segments {
+    /// Returns only the corners, in track order.
+    pub fn corners(&self) -> impl Iterator<Item = &Segment> {
+        self.segments.iter().filter(|s| s.is_corner())
+    }
+
+    /// Returns the segment containing the given distance, if any.
+    #[must_use]
+    pub fn at(&self, distance: f32) -> Option<&Segment> {
+        self.segments.iter().find(|s| s.contains(distance))
+    }
+}
+
+impl From<Vec<Segment>> for Segments {
+    /// Converts a vector of segments into `Segments`.
+    fn from(segments: Vec<Segment>) -> Self {
+        Self { segments }
+    }
+}
+
+impl FromIterator<Segment> for Segments {
+    /// Constructs `Segments` from an iterator over segments.
+    fn from_iter<T: IntoIterator<Item = Segment>>(iter: T) -> Self {
+        let segments_vec: Vec<Segment> = Vec::from_iter(iter);
+        Self::from(segments_vec)
+    }
+}
+
+impl Deref for Segments {
+    type Target = Vec<Segment>;
+
+    /// Implements dereferencing for `Segments`.
+    fn deref(&self) -> &Self::Target {
+        &self.segments
+    }
+}
+
+impl DerefMut for Segments {
+    /// Allows accessing the segments in the collection via mutable dereferencing.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the vector of segments.
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.segments
+    }
+}
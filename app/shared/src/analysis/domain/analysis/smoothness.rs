@@ -0,0 +1,85 @@
+use crate::analysis::domain::analysis::segment::Segment;
+use crate::analysis::domain::analysis::segments::Segments;
+use crate::lap::domain::lap::smoothness::{detect_corrections, Correction, Scores};
+use crate::lap::domain::lap::variables::Variables;
+
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+
+/// Comparison of the smoothness of both laps through one corner.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct CornerSmoothness {
+    pub segment: Segment,
+    pub reference: Scores,
+    pub target: Scores,
+    /// Steering corrections of each lap within the corner.
+    pub reference_corrections: usize,
+    pub target_corrections: usize,
+}
+
+/// Represents the smoothness comparison of an analysis over the whole lap and per corner, in
+/// track order.
+#[derive(Serialize, Deserialize, PartialEq, Default, Clone, Debug)]
+pub struct Smoothness {
+    /// Scores over the whole lap.
+    pub reference: Scores,
+    pub target: Scores,
+    /// Steering corrections of each lap, in track order.
+    pub reference_corrections: Vec<Correction>,
+    pub target_corrections: Vec<Correction>,
+    corners: Vec<CornerSmoothness>,
+}
+
+impl Deref for Smoothness {
+    type Target = Vec<CornerSmoothness>;
+
+    /// Implements dereferencing for `Smoothness`.
+    fn deref(&self) -> &Self::Target {
+        &self.corners
+    }
+}
+
+/// Scores the smoothness of both laps over the whole lap and through every corner of
+/// `segments`, and detects their steering corrections.
+///
+/// Both `reference` and `target` must be sampled on the same distances. Returns `None` if
+/// either lap is too short to be scored.
+#[must_use]
+pub fn compare(
+    segments: &Segments,
+    reference: &Variables,
+    target: &Variables,
+) -> Option<Smoothness> {
+    let distances = &reference.distance;
+    let reference_corrections = detect_corrections(reference);
+    let target_corrections = detect_corrections(target);
+    let count = |corrections: &[Correction], segment: &Segment| {
+        corrections
+            .iter()
+            .filter(|c| segment.contains(c.distance))
+            .count()
+    };
+
+    let corners = segments
+        .corners()
+        .filter_map(|segment| {
+            let start = distances.partition_point(|&d| d < segment.entry);
+            let end = distances.partition_point(|&d| d <= segment.exit);
+            Some(CornerSmoothness {
+                segment: segment.clone(),
+                reference: Scores::calculate(reference, start, end)?,
+                target: Scores::calculate(target, start, end)?,
+                reference_corrections: count(&reference_corrections, segment),
+                target_corrections: count(&target_corrections, segment),
+            })
+        })
+        .collect();
+
+    Some(Smoothness {
+        reference: Scores::calculate(reference, 0, distances.len())?,
+        target: Scores::calculate(target, 0, distances.len())?,
+        reference_corrections,
+        target_corrections,
+        corners,
+    })
+}
@@ -1,9 +1,238 @@
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::{Display, Formatter};
 use uuid::Uuid;
 
+/// Stages an analysis goes through, in order.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    /// Both laps are sampled on the same distances.
+    Interpolation,
+    /// Differences between both laps are calculated.
+    Differences,
+    /// Corners, braking zones, gear shifts, driving technique, smoothness and racing lines are
+    /// detected and compared.
+    Segmentation,
+    /// Differences are clustered and tagged.
+    Clustering,
+    /// Tags are merged into advice.
+    Coaching,
+}
+
+impl Stage {
+    pub const ALL: [Self; 5] = [
+        Self::Interpolation,
+        Self::Differences,
+        Self::Segmentation,
+        Self::Clustering,
+        Self::Coaching,
+    ];
+
+    /// Percentage of the analysis done when the stage starts.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn percent(self) -> u8 {
+        let index = Self::ALL.iter().position(|&s| s == self).unwrap_or(0);
+        (index * 100 / Self::ALL.len()) as u8
+    }
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Interpolation => "interpolation",
+            Self::Differences => "differences",
+            Self::Segmentation => "segmentation",
+            Self::Clustering => "clustering",
+            Self::Coaching => "coaching",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Lifecycle of an analysis. Every status keeps the time when the analysis entered it.
+///
+/// Statuses stored before the stages were tracked (`Pending`, `Error` and a bare `Completed`)
+/// are still read, as `Queued`, `Failed` and `Completed` at the Unix epoch.
 #[derive(Serialize, Deserialize, Eq, PartialEq, Clone, Debug)]
+#[serde(remote = "Self")]
 pub enum Status {
+    /// Waiting to compare the given laps.
+    Queued {
+        ref_id: Uuid,
+        target_id: Uuid,
+        at: DateTime<Utc>,
+    },
+    /// Running the given stage, with the percentage of the analysis already done.
+    Running {
+        stage: Stage,
+        percent: u8,
+        at: DateTime<Utc>,
+    },
+    Completed {
+        at: DateTime<Utc>,
+    },
+    /// Failed while running the given stage, if it had started.
+    Failed {
+        stage: Option<Stage>,
+        reason: String,
+        at: DateTime<Utc>,
+    },
+    /// Cancelled by the user while running the given stage, if it had started.
+    Cancelled {
+        stage: Option<Stage>,
+        at: DateTime<Utc>,
+    },
+}
+
+impl Status {
+    /// Name of the variant, used to filter the analyses by status.
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::Queued { .. } => "Queued",
+            Self::Running { .. } => "Running",
+            Self::Completed { .. } => "Completed",
+            Self::Failed { .. } => "Failed",
+            Self::Cancelled { .. } => "Cancelled",
+        }
+    }
+
+    /// Whether the analysis is waiting or running.
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        matches!(self, Self::Queued { .. } | Self::Running { .. })
+    }
+
+    /// Whether the analysis has been cancelled by the user.
+    #[must_use]
+    pub const fn is_cancelled(&self) -> bool {
+        matches!(self, Self::Cancelled { .. })
+    }
+
+    /// Stage being run, if any.
+    #[must_use]
+    pub const fn stage(&self) -> Option<Stage> {
+        match self {
+            Self::Running { stage, .. } => Some(*stage),
+            Self::Failed { stage, .. } | Self::Cancelled { stage, .. } => *stage,
+            Self::Queued { .. } | Self::Completed { .. } => None,
+        }
+    }
+}
+
+impl Serialize for Status {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Self::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Status {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Stored::deserialize(deserializer)? {
+            Stored::Current(status) => Ok(status),
+            Stored::Legacy(status) => Ok(status.into()),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Stored {
+    Current(#[serde(with = "Status")] Status),
+    Legacy(Legacy),
+}
+
+/// Status as stored before the stages were tracked.
+#[derive(Deserialize)]
+enum Legacy {
     Pending { ref_id: Uuid, target_id: Uuid },
     Error(String),
     Completed,
 }
+
+impl From<Legacy> for Status {
+    fn from(status: Legacy) -> Self {
+        let at = DateTime::UNIX_EPOCH;
+        match status {
+            Legacy::Pending { ref_id, target_id } => Self::Queued {
+                ref_id,
+                target_id,
+                at,
+            },
+            Legacy::Error(reason) => Self::Failed {
+                stage: None,
+                reason,
+                at,
+            },
+            Legacy::Completed => Self::Completed { at },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_legacy_statuses() {
+        let ref_id = Uuid::new_v4();
+        let target_id = Uuid::new_v4();
+        let pending = format!(r#"{{"Pending":{{"ref_id":"{ref_id}","target_id":"{target_id}"}}}}"#);
+
+        assert_eq!(
+            serde_json::from_str::<Status>(&pending).unwrap(),
+            Status::Queued {
+                ref_id,
+                target_id,
+                at: DateTime::UNIX_EPOCH
+            }
+        );
+        assert_eq!(
+            serde_json::from_str::<Status>(r#"{"Error":"boom"}"#).unwrap(),
+            Status::Failed {
+                stage: None,
+                reason: "boom".to_string(),
+                at: DateTime::UNIX_EPOCH
+            }
+        );
+        assert_eq!(
+            serde_json::from_str::<Status>(r#""Completed""#).unwrap(),
+            Status::Completed {
+                at: DateTime::UNIX_EPOCH
+            }
+        );
+    }
+
+    #[test]
+    fn round_trips_current_statuses() {
+        let statuses = [
+            Status::Queued {
+                ref_id: Uuid::new_v4(),
+                target_id: Uuid::new_v4(),
+                at: Utc::now(),
+            },
+            Status::Running {
+                stage: Stage::Clustering,
+                percent: Stage::Clustering.percent(),
+                at: Utc::now(),
+            },
+            Status::Completed { at: Utc::now() },
+            Status::Failed {
+                stage: Some(Stage::Coaching),
+                reason: "boom".to_string(),
+                at: Utc::now(),
+            },
+            Status::Cancelled {
+                stage: None,
+                at: Utc::now(),
+            },
+        ];
+
+        for status in statuses {
+            let json = serde_json::to_string(&status).unwrap();
+            assert_eq!(serde_json::from_str::<Status>(&json).unwrap(), status);
+        }
+    }
+}
@@ -30,6 +30,14 @@ impl Tag {
         Self::Single(Base::Reduce(0))
     }
 
+    /// The closest cluster of the tag, ignoring its tendency.
+    #[must_use]
+    pub const fn base(&self) -> Base {
+        match *self {
+            Self::Single(base) | Self::Tendency(base, _) => base,
+        }
+    }
+
     pub fn increment(&mut self) {
         if let Self::Single(Base::Increase(value) | Base::Reduce(value)) = self {
             *value += 1;
@@ -1,8 +1,11 @@
 use crate::analysis::domain::analysis::fuzzy_c_means::FittedModel;
+use crate::analysis::domain::analysis::tag::Base;
+use crate::analysis::domain::analysis::tag_policy::TagPolicy;
 use crate::analysis::domain::analysis::{tag::Tag, tags::Tags};
 
 use ndarray::{Array1, Array2};
 use std::cmp::Ordering;
+use std::num::NonZeroU8;
 
 #[derive(Clone, Debug)]
 pub struct TagAssigner {
@@ -10,6 +13,10 @@ pub struct TagAssigner {
     pub centroids: Array2<f64>,
     pub memberships: Array2<f64>,
     pub threshold: f64,
+    /// Centroids within this distance of zero are tagged as stay. Ignored when 0.
+    pub dead_band: f64,
+    /// Maximum number of increase and reduce levels, if limited.
+    pub max_levels: Option<NonZeroU8>,
 }
 
 impl TagAssigner {
@@ -20,6 +27,22 @@ impl TagAssigner {
             centroids: fitted_model.centroids().clone(),
             memberships: fitted_model.memberships().clone(),
             threshold,
+            dead_band: 0.,
+            max_levels: None,
+        }
+    }
+
+    /// Creates a `TagAssigner` following the given policy.
+    #[must_use]
+    pub fn with_policy(
+        differences: Array2<f64>,
+        fitted_model: &FittedModel,
+        policy: &TagPolicy,
+    ) -> Self {
+        Self {
+            dead_band: policy.dead_band,
+            max_levels: policy.max_levels,
+            ..Self::new(differences, fitted_model, policy.threshold)
         }
     }
 
@@ -27,8 +50,19 @@ impl TagAssigner {
     pub fn assign(&self) -> Tags {
         let sorted_centroids_with_index = self.sort_centroids_with_index();
 
-        let mut sorted_centroid_tags_with_index =
-            self.define_cluster_tags_with_index(&sorted_centroids_with_index);
+        let mut sorted_centroid_tags_with_index = if self.dead_band > 0. {
+            self.define_cluster_tags_with_dead_band(&sorted_centroids_with_index)
+        } else {
+            self.define_cluster_tags_with_index(&sorted_centroids_with_index)
+        };
+        if let Some(max_levels) = self.max_levels {
+            let max_level = max_levels.get() - 1;
+            for (_, tag) in &mut sorted_centroid_tags_with_index {
+                if let Tag::Single(Base::Increase(level) | Base::Reduce(level)) = tag {
+                    *level = (*level).min(max_level);
+                }
+            }
+        }
 
         sorted_centroid_tags_with_index
             .sort_by(|(idx_1, _), (idx_2, _)| idx_1.partial_cmp(idx_2).unwrap_or(Ordering::Equal));
@@ -127,6 +161,37 @@ impl TagAssigner {
         }
     }
 
+    /// Tags as stay every centroid within the dead band, and as reduce or increase the ones
+    /// below or above it, with levels growing away from the band.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn define_cluster_tags_with_dead_band(
+        &self,
+        sorted_indexed_centroids: &[(usize, Array1<f64>)],
+    ) -> Vec<(usize, Tag)> {
+        let in_band = |centroid: &Array1<f64>| Self::norm_l2(centroid) <= self.dead_band;
+        let below: Vec<usize> = sorted_indexed_centroids
+            .iter()
+            .filter(|(_, centroid)| !in_band(centroid) && centroid[0] < 0.)
+            .map(|(original_idx, _)| *original_idx)
+            .collect();
+        let mut above_level = 0;
+
+        sorted_indexed_centroids
+            .iter()
+            .map(|(original_idx, centroid)| {
+                if in_band(centroid) {
+                    (*original_idx, Tag::stay())
+                } else if let Some(position) = below.iter().position(|idx| idx == original_idx) {
+                    let level = below.len() - 1 - position;
+                    (*original_idx, Tag::reduce_with_level(level as u8))
+                } else {
+                    above_level += 1;
+                    (*original_idx, Tag::increase_with_level(above_level - 1))
+                }
+            })
+            .collect()
+    }
+
     fn assign_final_tags(&self, centroid_tags: &[Tag]) -> Tags {
         let mut final_tags: Vec<Tag> = Vec::new();
 
@@ -495,4 +560,30 @@ mod tests {
         // Ensure the assigned tags match the expected tags
         assert_eq!(assigned_tags, Tags::from(expected_tags));
     }
+
+    #[test]
+    fn test_assign_with_dead_band_and_max_levels() {
+        let centroids = array![[-30.0], [-10.0], [-1.0], [1.5], [10.0], [20.0], [40.0]];
+        let differences = array![[-30.0], [-10.0], [-1.0], [1.5], [10.0], [20.0], [40.0]];
+        let memberships = Array2::from_diag(&Array1::ones(7));
+        let fitted_model = FittedModel::new(centroids, memberships);
+        let policy = TagPolicy {
+            threshold: 0.5,
+            dead_band: 2.,
+            max_levels: NonZeroU8::new(2),
+        };
+
+        let assigned_tags = TagAssigner::with_policy(differences, &fitted_model, &policy).assign();
+
+        let expected_tags = vec![
+            Tag::reduce_with_level(1),
+            Tag::reduce(),
+            Tag::stay(),
+            Tag::stay(), // -1 and 1.5 are within the dead band
+            Tag::increase(),
+            Tag::increase_with_level(1),
+            Tag::increase_with_level(1), // level 2 is capped
+        ];
+        assert_eq!(assigned_tags, Tags::from(expected_tags));
+    }
 }
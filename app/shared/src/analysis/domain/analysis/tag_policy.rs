@@ -0,0 +1,91 @@
+use crate::analysis::domain::analysis::channel::Channel;
+
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU8;
+
+/// Minimum membership of the second closest cluster to report it as a tendency by default.
+pub const DEFAULT_THRESHOLD: f64 = 0.2;
+
+/// Rules used to turn the clusters of a channel into tags.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+pub struct TagPolicy {
+    /// Minimum membership of the second closest cluster to report it as a tendency.
+    pub threshold: f64,
+    /// Clusters whose centroid is within this distance of zero are tagged as `Base::Stay`.
+    /// With 0, the centroid closest to zero is the only stay. It is given in km/h for the
+    /// speed, as a fraction of the pedal travel (0 to 1) for the throttle and the brake, in
+    /// gears for the gear and in radians for the steering wheel angle.
+    pub dead_band: f64,
+    /// Maximum number of levels of increase and reduce tags. Unlimited when `None`.
+    pub max_levels: Option<NonZeroU8>,
+}
+
+impl TagPolicy {
+    /// Returns the policy with the dead band converted to the telemetry units of `channel`.
+    #[must_use]
+    pub fn in_telemetry_units(self, channel: Channel) -> Self {
+        Self {
+            dead_band: channel.to_telemetry_unit(self.dead_band),
+            ..self
+        }
+    }
+}
+
+impl Default for TagPolicy {
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_THRESHOLD,
+            dead_band: 0.,
+            max_levels: None,
+        }
+    }
+}
+
+/// Tag policy of one channel.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct ChannelTagPolicy {
+    pub channel: Channel,
+    #[serde(flatten)]
+    pub policy: TagPolicy,
+}
+
+/// Returns the policy of `channel` in `policies`, or the default one if it is not there.
+#[must_use]
+pub fn for_channel(policies: &[ChannelTagPolicy], channel: Channel) -> TagPolicy {
+    policies
+        .iter()
+        .find(|p| p.channel == channel)
+        .map(|p| p.policy)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_the_speed_dead_band_from_kmh() {
+        let policy = TagPolicy {
+            dead_band: 7.2,
+            ..TagPolicy::default()
+        };
+
+        let speed = policy.in_telemetry_units(Channel::Speed);
+        let brake = policy.in_telemetry_units(Channel::Brake);
+
+        assert!((speed.dead_band - 2.).abs() < 1e-9);
+        assert!((brake.dead_band - 7.2).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn rejects_zero_levels() {
+        let policy = |max_levels: u8| {
+            serde_json::from_str::<ChannelTagPolicy>(&format!(
+                r#"{{"channel":"speed","threshold":0.2,"dead_band":2,"max_levels":{max_levels}}}"#
+            ))
+        };
+
+        assert!(policy(0).is_err());
+        assert_eq!(policy(2).unwrap().policy.max_levels, NonZeroU8::new(2));
+    }
+}
@@ -0,0 +1,220 @@
+use crate::analysis::domain::analysis::segment::Segment;
+use crate::analysis::domain::analysis::segments::Segments;
+use crate::analysis::domain::analysis::thresholds::{BRAKE_ON, THROTTLE_ON};
+use crate::lap::domain::lap::variables::Variables;
+
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+
+/// Throttle and brake below this value are considered released.
+const PEDAL_OFF: f32 = 0.05;
+
+/// Driving technique of a lap over a stretch of the circuit. Times in seconds, distances in
+/// metres.
+#[derive(Serialize, Deserialize, PartialEq, Default, Clone, Debug)]
+pub struct TechniqueMetrics {
+    /// Time with both the throttle and the brake released.
+    pub coasting_time: f32,
+    pub coasting_distance: f32,
+    /// Distance braking while turning the steering wheel further.
+    pub trail_braking_distance: f32,
+    /// Average brake pressure while trail braking, 0 if the driver does not trail brake.
+    pub trail_braking_depth: f32,
+    /// Time pressing the throttle and the brake at the same time.
+    pub overlap_time: f32,
+    pub overlap_distance: f32,
+}
+
+impl TechniqueMetrics {
+    /// Computes the metrics of a lap between the samples `start` and `end - 1`.
+    ///
+    /// Every sample accounts for the time and distance until the next one.
+    #[must_use]
+    pub fn calculate(variables: &Variables, start: usize, end: usize) -> Self {
+        let len = [
+            variables.distance.len(),
+            variables.lap_current_lap_time.len(),
+            variables.throttle.len(),
+            variables.brake.len(),
+            variables.steering_wheel_angle.len(),
+        ]
+        .into_iter()
+        .min()
+        .unwrap_or_default();
+        let end = end.min(len.saturating_sub(1));
+
+        let mut metrics = Self::default();
+        let mut trail_braking_pressure = 0.;
+        for i in start..end {
+            let time = variables.lap_current_lap_time[i + 1] - variables.lap_current_lap_time[i];
+            let distance = variables.distance[i + 1] - variables.distance[i];
+            let (throttle, brake) = (variables.throttle[i], variables.brake[i]);
+
+            if throttle < PEDAL_OFF && brake < PEDAL_OFF {
+                metrics.coasting_time += time;
+                metrics.coasting_distance += distance;
+            }
+            if throttle > THROTTLE_ON && brake > BRAKE_ON {
+                metrics.overlap_time += time;
+                metrics.overlap_distance += distance;
+            }
+            let steering = &variables.steering_wheel_angle;
+            let turning_in = i > 0 && steering[i].abs() > steering[i - 1].abs();
+            if brake > BRAKE_ON && turning_in {
+                metrics.trail_braking_distance += distance;
+                trail_braking_pressure = brake.mul_add(distance, trail_braking_pressure);
+            }
+        }
+        if metrics.trail_braking_distance > 0. {
+            metrics.trail_braking_depth = trail_braking_pressure / metrics.trail_braking_distance;
+        }
+
+        metrics
+    }
+
+    /// Difference of `target` with respect to `reference`, metric by metric.
+    #[must_use]
+    pub fn delta(reference: &Self, target: &Self) -> Self {
+        Self {
+            coasting_time: target.coasting_time - reference.coasting_time,
+            coasting_distance: target.coasting_distance - reference.coasting_distance,
+            trail_braking_distance: target.trail_braking_distance
+                - reference.trail_braking_distance,
+            trail_braking_depth: target.trail_braking_depth - reference.trail_braking_depth,
+            overlap_time: target.overlap_time - reference.overlap_time,
+            overlap_distance: target.overlap_distance - reference.overlap_distance,
+        }
+    }
+}
+
+/// Comparison of the technique of both laps over a stretch of the circuit.
+///
+/// Positive deltas mean that the target lap does more of it than the reference lap.
+#[derive(Serialize, Deserialize, PartialEq, Default, Clone, Debug)]
+pub struct TechniqueComparison {
+    pub reference: TechniqueMetrics,
+    pub target: TechniqueMetrics,
+    pub delta: TechniqueMetrics,
+}
+
+impl TechniqueComparison {
+    fn new(reference: TechniqueMetrics, target: TechniqueMetrics) -> Self {
+        Self {
+            delta: TechniqueMetrics::delta(&reference, &target),
+            reference,
+            target,
+        }
+    }
+}
+
+/// Comparison of the technique of both laps through one corner.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct CornerTechnique {
+    pub segment: Segment,
+    pub comparison: TechniqueComparison,
+}
+
+/// Represents the technique comparison of an analysis over the whole lap and per corner, in
+/// track order.
+#[derive(Serialize, Deserialize, PartialEq, Default, Clone, Debug)]
+pub struct Technique {
+    /// Comparison over the whole lap.
+    pub lap: TechniqueComparison,
+    corners: Vec<CornerTechnique>,
+}
+
+impl Deref for Technique {
+    type Target = Vec<CornerTechnique>;
+
+    /// Implements dereferencing for `Technique`.
+    fn deref(&self) -> &Self::Target {
+        &self.corners
+    }
+}
+
+/// Computes the coasting, trail braking and overlap of both laps over the whole lap and
+/// through every corner of `segments`.
+///
+/// Both `reference` and `target` must be sampled on the same distances.
+#[must_use]
+pub fn calculate(segments: &Segments, reference: &Variables, target: &Variables) -> Technique {
+    let distances = &reference.distance;
+    let compare = |start: usize, end: usize| {
+        TechniqueComparison::new(
+            TechniqueMetrics::calculate(reference, start, end),
+            TechniqueMetrics::calculate(target, start, end),
+        )
+    };
+
+    let corners = segments
+        .corners()
+        .filter_map(|segment| {
+            let start = distances.partition_point(|&d| d < segment.entry);
+            let end = distances.partition_point(|&d| d <= segment.exit);
+            (end > start + 1).then(|| CornerTechnique {
+                segment: segment.clone(),
+                comparison: compare(start, end),
+            })
+        })
+        .collect();
+
+    Technique {
+        lap: compare(0, distances.len()),
+        corners,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 300 m lap sampled every metre at 10 m/s, braking between 100 m and 150 m while
+    /// turning in from 130 m, coasting until 160 m and pressing both pedals between 200 m
+    /// and `overlap_to`.
+    fn test_variables(overlap_to: f32) -> Variables {
+        let mut variables = Variables::default();
+        for i in 0..300 {
+            #[allow(clippy::cast_precision_loss)]
+            let d = i as f32;
+            let braking = (100. ..150.).contains(&d) || (200. ..overlap_to).contains(&d);
+
+            variables.distance.push(d);
+            variables.lap_current_lap_time.push(d / 10.);
+            variables.brake.push(if braking { 0.5 } else { 0. });
+            variables
+                .throttle
+                .push(if (100. ..160.).contains(&d) { 0. } else { 1. });
+            variables.steering_wheel_angle.push(if d >= 130. {
+                (d - 130.).min(40.) / 100.
+            } else {
+                0.
+            });
+        }
+        variables
+    }
+
+    #[test]
+    fn calculates_technique_of_both_laps() {
+        let segments = Segments::from(vec![
+            Segment::straight(1, 0., 99.),
+            Segment::corner(1, 100., 150., 180.),
+        ]);
+        let reference = test_variables(200.);
+        let target = test_variables(210.);
+
+        let technique = calculate(&segments, &reference, &target);
+
+        let lap = &technique.lap;
+        assert!((lap.reference.coasting_distance - 10.).abs() < 1e-4);
+        assert!((lap.reference.coasting_time - 1.).abs() < 1e-4);
+        assert!(lap.reference.overlap_time.abs() < 1e-4);
+        assert!((lap.delta.overlap_distance - 10.).abs() < 1e-4);
+        assert!((lap.delta.overlap_time - 1.).abs() < 1e-4);
+
+        assert_eq!(technique.len(), 1);
+        let corner = &technique[0].comparison;
+        assert!((corner.reference.trail_braking_distance - 19.).abs() < 1e-4);
+        assert!((corner.reference.trail_braking_depth - 0.5).abs() < 1e-4);
+        assert!(corner.delta.trail_braking_distance.abs() < 1e-4);
+    }
+}
@@ -0,0 +1,4 @@
+/// Brake pressure above which the driver is considered to be braking.
+pub(crate) const BRAKE_ON: f32 = 0.1;
+/// Throttle position above which the driver is considered to be accelerating.
+pub(crate) const THROTTLE_ON: f32 = 0.2;
@@ -0,0 +1,214 @@
+use crate::analysis::domain::analysis::channel::Channel;
+use crate::analysis::domain::analysis::fuzzy_c_means::{FittedModel, FuzzyCMeans};
+
+use ndarray::{Array2, Axis};
+use serde::{Deserialize, Serialize};
+
+/// Cluster validity index used to choose the best model of a grid search.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Criterion {
+    /// Fuzzy partition coefficient, the higher the better.
+    #[default]
+    Fpc,
+    /// Partition entropy, the lower the better.
+    PartitionEntropy,
+    /// Xie–Beni index, the lower the better.
+    XieBeni,
+    /// Fukuyama–Sugeno index, the lower the better.
+    FukuyamaSugeno,
+    /// Simplified silhouette, the higher the better.
+    Silhouette,
+}
+
+/// Validity indices of a fitted clustering model.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug, Default)]
+pub struct ValidityIndices {
+    pub fpc: f64,
+    pub partition_entropy: f64,
+    pub xie_beni: f64,
+    pub fukuyama_sugeno: f64,
+    pub silhouette: f64,
+}
+
+/// Validity indices of the model chosen for one channel.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct ChannelValidity {
+    pub channel: Channel,
+    pub indices: ValidityIndices,
+}
+
+impl ValidityIndices {
+    /// Computes every index of `model` fitted on `data`.
+    ///
+    /// `m` is the fuzziness exponent applied to the memberships. It has no effect on crisp
+    /// models, whose memberships are either 0 or 1.
+    ///
+    /// The silhouette is the simplified one: distances to the cluster centroids replace the
+    /// mean distances to the samples of each cluster, which keeps its cost linear in the
+    /// number of samples.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn calculate(data: &Array2<f64>, model: &FittedModel, m: f64) -> Self {
+        let memberships = model.memberships();
+        let centroids = model.centroids();
+        let n_samples = data.nrows().max(1) as f64;
+
+        // Squared distances between every sample and every centroid
+        let distances = Array2::from_shape_fn((data.nrows(), centroids.nrows()), |(i, k)| {
+            FuzzyCMeans::distance(&data.row(i), &centroids.row(k)).powi(2)
+        });
+        let weighted = memberships.mapv(|u| u.powf(m));
+        let compactness = (&weighted * &distances).sum();
+
+        let partition_entropy = -memberships
+            .iter()
+            .filter(|&&u| u > 0.)
+            .map(|&u| u * u.ln())
+            .sum::<f64>()
+            / n_samples;
+
+        let min_separation = (0..centroids.nrows())
+            .flat_map(|j| ((j + 1)..centroids.nrows()).map(move |k| (j, k)))
+            .map(|(j, k)| FuzzyCMeans::distance(&centroids.row(j), &centroids.row(k)).powi(2))
+            .fold(f64::INFINITY, f64::min);
+        // Undefined with less than two distinct centroids: report it as the worst value
+        let xie_beni = if min_separation > 0. && min_separation.is_finite() {
+            compactness / (n_samples * min_separation)
+        } else {
+            f64::MAX
+        };
+
+        let fukuyama_sugeno = centroids.mean_axis(Axis(0)).map_or(0., |mean| {
+            let separation: Vec<f64> = centroids
+                .outer_iter()
+                .map(|centroid| FuzzyCMeans::distance(&centroid, &mean.view()).powi(2))
+                .collect();
+            compactness
+                - weighted
+                    .outer_iter()
+                    .map(|row| row.iter().zip(&separation).map(|(u, s)| u * s).sum::<f64>())
+                    .sum::<f64>()
+        });
+
+        let silhouette = if centroids.nrows() < 2 {
+            0.
+        } else {
+            distances
+                .outer_iter()
+                .zip(memberships.outer_iter())
+                .map(|(distances, memberships)| {
+                    let own = memberships
+                        .iter()
+                        .enumerate()
+                        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                        .map_or(0, |(k, _)| k);
+                    let a = distances[own].sqrt();
+                    let b = distances
+                        .iter()
+                        .enumerate()
+                        .filter(|&(k, _)| k != own)
+                        .map(|(_, d)| d.sqrt())
+                        .fold(f64::INFINITY, f64::min);
+                    if a.max(b) > 0. {
+                        (b - a) / a.max(b)
+                    } else {
+                        0.
+                    }
+                })
+                .sum::<f64>()
+                / n_samples
+        };
+
+        Self {
+            fpc: model.fpc(),
+            partition_entropy,
+            xie_beni,
+            fukuyama_sugeno,
+            silhouette,
+        }
+    }
+}
+
+impl Criterion {
+    /// Returns `true` if `candidate` is better than `current` according to this criterion.
+    /// Undefined values are never better.
+    #[must_use]
+    pub fn is_better(self, candidate: &ValidityIndices, current: &ValidityIndices) -> bool {
+        let (candidate, current, higher_is_better) = match self {
+            Self::Fpc => (candidate.fpc, current.fpc, true),
+            Self::PartitionEntropy => (
+                candidate.partition_entropy,
+                current.partition_entropy,
+                false,
+            ),
+            Self::XieBeni => (candidate.xie_beni, current.xie_beni, false),
+            Self::FukuyamaSugeno => (candidate.fukuyama_sugeno, current.fukuyama_sugeno, false),
+            Self::Silhouette => (candidate.silhouette, current.silhouette, true),
+        };
+
+        if candidate.is_nan() {
+            false
+        } else if current.is_nan() {
+            true
+        } else if higher_is_better {
+            candidate > current
+        } else {
+            candidate < current
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn calculates_indices_of_crisp_partition() {
+        let data = array![[0.0], [2.0], [10.0], [12.0]];
+        let centroids = array![[1.0], [11.0]];
+        let memberships = array![[1.0, 0.0], [1.0, 0.0], [0.0, 1.0], [0.0, 1.0]];
+        let model = FittedModel::new(centroids, memberships);
+
+        let indices = ValidityIndices::calculate(&data, &model, 2.);
+
+        assert!((indices.fpc - 1.).abs() < 1e-9);
+        assert!(indices.partition_entropy.abs() < 1e-9);
+        // Compactness 4, separation 100
+        assert!((indices.xie_beni - 4. / 400.).abs() < 1e-9);
+        // Compactness 4 minus 4 samples at 25 from the mean of centroids
+        assert!((indices.fukuyama_sugeno - (4. - 100.)).abs() < 1e-9);
+        // Every sample is at 1 from its centroid and at 9 or 11 from the other one
+        assert!((indices.silhouette - (8. / 9. + 10. / 11.) / 2.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn compares_according_to_criterion() {
+        let good = ValidityIndices {
+            fpc: 0.9,
+            partition_entropy: 0.1,
+            xie_beni: 0.1,
+            fukuyama_sugeno: -10.,
+            silhouette: 0.8,
+        };
+        let bad = ValidityIndices {
+            fpc: 0.5,
+            partition_entropy: 0.6,
+            xie_beni: 1.,
+            fukuyama_sugeno: 10.,
+            silhouette: 0.1,
+        };
+
+        for criterion in [
+            Criterion::Fpc,
+            Criterion::PartitionEntropy,
+            Criterion::XieBeni,
+            Criterion::FukuyamaSugeno,
+            Criterion::Silhouette,
+        ] {
+            assert!(criterion.is_better(&good, &bad), "{criterion:?}");
+            assert!(!criterion.is_better(&bad, &good), "{criterion:?}");
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::analysis::domain::analysis::clusters_memberships::ClustersMemberships;
+use crate::analysis::domain::analysis::coaching::Coaching;
+use crate::analysis::domain::analysis::overrides::Overrides;
+
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, DerefMut};
+
+/// Result of a previous run of an analysis, kept when it is run again.
+///
+/// Only the results that depend on the parameters are kept: the laps, their differences and
+/// the segmentation are the same in every version.
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+pub struct Version {
+    /// Number of the version, starting at 1.
+    pub number: u32,
+    /// Parameters replaced in this run, if any.
+    pub overrides: Option<Overrides>,
+    pub seed: Option<u64>,
+    pub clustering: Option<ClustersMemberships>,
+    pub coaching: Option<Coaching>,
+}
+
+/// Represents the previous versions of an analysis, from the oldest to the newest.
+#[derive(Serialize, Deserialize, PartialEq, Default, Clone)]
+pub struct Versions {
+    versions: Vec<Version>,
+}
+
+impl From<Vec<Version>> for Versions {
+    /// Converts a vector of versions into `Versions`.
+    fn from(versions: Vec<Version>) -> Self {
+        Self { versions }
+    }
+}
+
+impl Deref for Versions {
+    type Target = Vec<Version>;
+
+    /// Implements dereferencing for `Versions`.
+    fn deref(&self) -> &Self::Target {
+        &self.versions
+    }
+}
+
+impl DerefMut for Versions {
+    /// Implements mutable dereferencing for `Versions`.
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.versions
+    }
+}
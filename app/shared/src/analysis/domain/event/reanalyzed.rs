@@ -0,0 +1,35 @@
+use crate::common::domain::event::Event;
+
+use std::any::Any;
+use std::fmt::Debug;
+use uuid::Uuid;
+
+/// Represents the "reanalyzed" event for an analysis, emitted when an existing analysis has
+/// to be run again.
+#[derive(Debug)]
+pub struct Reanalyzed {
+    /// The id of the analysis to run again.
+    pub id: Uuid,
+}
+
+impl Reanalyzed {
+    /// Creates a new `Reanalyzed` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The id of the analysis to run again.
+    ///
+    /// # Returns
+    ///
+    /// A new `Reanalyzed` event.
+    #[must_use]
+    pub const fn new(id: &Uuid) -> Self {
+        Self { id: *id }
+    }
+}
+
+impl Event for Reanalyzed {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -3,6 +3,8 @@ use crate::analysis::domain::analysis::Analysis;
 
 use crate::analysis::domain::analysis::header::Header;
 use crate::analysis::domain::analysis::headers::Headers;
+use crate::analysis::domain::analysis::segments::Segments;
+use crate::analysis::domain::analysis::status::Status;
 use crate::common::domain::criteria::Criteria;
 use async_trait::async_trait;
 use uuid::Uuid;
@@ -31,6 +33,29 @@ pub trait Repository: Send + Sync {
     /// This asynchronous function will return an `Err` if there is an error while attempting to update the analysis data.
     async fn update(&self, analysis: &Analysis) -> Result<(), String>;
 
+    /// Updates analysis data asynchronously only if its stored status is still `current`, and
+    /// returns whether it was updated, so a status changed meanwhile (e.g. a cancellation) is
+    /// never overwritten.
+    ///
+    /// # Errors
+    ///
+    /// This asynchronous function will return an `Err` if there is an error while attempting to update the analysis data.
+    async fn update_if_status(&self, analysis: &Analysis, current: &Status)
+        -> Result<bool, String>;
+
+    /// Replaces only the status of an analysis asynchronously given its ID, if its stored status
+    /// is still `current`, and returns whether it was replaced.
+    ///
+    /// # Errors
+    ///
+    /// This asynchronous function will return an `Err` if there is an error while attempting to update the analysis data.
+    async fn update_status(
+        &self,
+        id: &Uuid,
+        current: &Status,
+        status: &Status,
+    ) -> Result<bool, String>;
+
     /// Finds analysis data asynchronously by its ID.
     ///
     /// # Errors
@@ -59,4 +84,16 @@ pub trait Repository: Send + Sync {
     /// This asynchronous function will return an `Err` if there is an error while attempting to find the analysis data.
     async fn find_header_by_criteria(&self, criteria: &Criteria)
         -> Result<Option<Headers>, String>;
+
+    /// Finds asynchronously only the segments of the earliest analysis of `circuit` other than
+    /// the one with ID `exclude` that has them.
+    ///
+    /// # Errors
+    ///
+    /// This asynchronous function will return an `Err` if there is an error while attempting to find the analysis data.
+    async fn find_circuit_segments(
+        &self,
+        circuit: &str,
+        exclude: &Uuid,
+    ) -> Result<Option<Segments>, String>;
 }
@@ -1,6 +1,8 @@
 use crate::analysis::domain::analyses::Analyses;
 use crate::analysis::domain::analysis::header::Header;
 use crate::analysis::domain::analysis::headers::Headers;
+use crate::analysis::domain::analysis::segments::Segments;
+use crate::analysis::domain::analysis::status::Status;
 use crate::analysis::domain::analysis::Analysis;
 use crate::analysis::domain::repository::Repository;
 use crate::common::domain::criteria::filters::Filters;
@@ -82,6 +84,68 @@ impl Repository for InMemory {
         Ok(())
     }
 
+    /// Updates an analysis with the specified analysis instance from the in-memory storage, if
+    /// its stored status is still `current`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the analysis update fails or if no analysis with the specified data is found.
+    async fn update_if_status(
+        &self,
+        analysis: &Analysis,
+        current: &Status,
+    ) -> Result<bool, String> {
+        let mut analyses_guard = self.analyses.lock().map_err(|e| format!("{e}"))?;
+
+        let stored = analyses_guard
+            .iter_mut()
+            .find(|a| a.header.id == analysis.header.id)
+            .ok_or_else(|| {
+                format!(
+                    "No analysis with ID {} found to update.",
+                    analysis.header.id
+                )
+            })?;
+
+        let updated = stored.header.status == *current;
+        if updated {
+            *stored = analysis.clone();
+        }
+
+        drop(analyses_guard);
+
+        Ok(updated)
+    }
+
+    /// Replaces the status of the analysis with the specified ID in the in-memory storage, if
+    /// it is still `current`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the analysis update fails or if no analysis with the specified ID is found.
+    async fn update_status(
+        &self,
+        id: &Uuid,
+        current: &Status,
+        status: &Status,
+    ) -> Result<bool, String> {
+        let mut analyses_guard = self.analyses.lock().map_err(|e| format!("{e}"))?;
+
+        let stored = analyses_guard
+            .iter_mut()
+            .find(|a| a.header.id == *id)
+            .ok_or_else(|| format!("No analysis with ID {id} found to update."))?;
+
+        let updated = stored.header.status == *current;
+        if updated {
+            stored.header.status = status.clone();
+        }
+
+        drop(analyses_guard);
+
+        Ok(updated)
+    }
+
     /// Finds an analysis with the specified ID in the in-memory storage.
     ///
     /// # Errors
@@ -131,7 +195,7 @@ impl Repository for InMemory {
             });
         }
 
-        let opt_analyses: Option<Analyses> = if analyses.len() == 0 {
+        let opt_analyses: Option<Analyses> = if analyses.is_empty() {
             None
         } else {
             Some(analyses)
@@ -195,6 +259,22 @@ impl Repository for InMemory {
 
         Ok(opt_headers)
     }
+
+    async fn find_circuit_segments(
+        &self,
+        circuit: &str,
+        exclude: &Uuid,
+    ) -> Result<Option<Segments>, String> {
+        let analyses_guard = self.analyses.lock().map_err(|e| format!("{e}"))?;
+        let segments = analyses_guard
+            .iter()
+            .filter(|a| a.header.circuit == circuit && a.header.id != *exclude)
+            .filter_map(|a| Some((a.header.date, a.segments.as_ref()?)))
+            .min_by_key(|(date, _)| *date)
+            .map(|(_, segments)| segments.clone());
+        drop(analyses_guard);
+        Ok(segments)
+    }
 }
 
 impl InMemory {
@@ -216,7 +296,148 @@ impl InMemory {
             "name" => Ok(header.name.clone()),
             "circuit" => Ok(header.circuit.clone()),
             "date" => Ok(header.date.to_string()),
+            "status" => Ok(header.status.kind().to_string()),
             _ => Err(format!("`{field}` field does not exists in analysis")),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::domain::analysis::segment::Segment;
+    use crate::common::domain::criteria::filter::condition::Condition;
+    use crate::common::domain::criteria::filter::field::Field;
+    use crate::common::domain::criteria::filter::value::Value;
+    use crate::common::domain::criteria::filter::Filter;
+    use chrono::Utc;
+
+    fn status_criteria(condition: Condition, kind: &str) -> Criteria {
+        Criteria::new(
+            Some(Filters::from(vec![Filter::new(
+                Field::new("status"),
+                condition,
+                Value::new(kind),
+            )])),
+            None,
+            None,
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn filters_by_status_kind() {
+        let repository = InMemory::default();
+        let queued = Analysis::new(
+            Uuid::new_v4(),
+            "Queued".to_string(),
+            Utc::now(),
+            "Spa".to_string(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        let failed = Analysis::with_error(
+            Uuid::new_v4(),
+            "Failed".to_string(),
+            Utc::now(),
+            "Spa".to_string(),
+            "boom".to_string(),
+        );
+        repository.create(queued.clone()).await.unwrap();
+        repository.create(failed.clone()).await.unwrap();
+
+        let headers = repository
+            .find_header_by_criteria(&status_criteria(Condition::Equal, "Queued"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers[0].id, queued.header.id);
+
+        let headers = repository
+            .find_header_by_criteria(&status_criteria(Condition::NotEqual, "Queued"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers[0].id, failed.header.id);
+    }
+
+    #[tokio::test]
+    async fn keeps_a_status_changed_meanwhile() {
+        let repository = InMemory::default();
+        let mut analysis = Analysis::new(
+            Uuid::new_v4(),
+            "Analysis".to_string(),
+            Utc::now(),
+            "Spa".to_string(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        let id = analysis.header.id;
+        let queued = analysis.header.status.clone();
+        repository.create(analysis.clone()).await.unwrap();
+
+        let mut cancelled = analysis.clone();
+        cancelled.cancel().unwrap();
+        assert!(repository
+            .update_status(&id, &queued, &cancelled.header.status)
+            .await
+            .unwrap());
+
+        analysis.header.status = Status::Completed { at: Utc::now() };
+        assert!(!repository
+            .update_if_status(&analysis, &queued)
+            .await
+            .unwrap());
+        assert!(!repository
+            .update_status(&id, &queued, &analysis.header.status)
+            .await
+            .unwrap());
+
+        let stored = repository.find_header_by_id(&id).await.unwrap().unwrap();
+        assert!(stored.status.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn finds_the_segments_of_the_earliest_other_analysis_of_the_circuit() {
+        let repository = InMemory::default();
+        let analysis = |name: &str, circuit: &str, days_ago: i64, segments: Option<Segments>| {
+            let mut analysis = Analysis::new(
+                Uuid::new_v4(),
+                name.to_string(),
+                Utc::now() - chrono::Duration::days(days_ago),
+                circuit.to_string(),
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+            );
+            analysis.segments = segments;
+            analysis
+        };
+        let earliest = Segments::from(vec![Segment::straight(1, 0., 1000.)]);
+        let later = Segments::from(vec![Segment::straight(1, 0., 2000.)]);
+        let own = analysis("Own", "Spa", 4, Some(Segments::default()));
+        for analysis in [
+            own.clone(),
+            analysis("Other circuit", "Monza", 3, Some(Segments::default())),
+            analysis("Not analyzed", "Spa", 2, None),
+            analysis("Earliest", "Spa", 1, Some(earliest.clone())),
+            analysis("Later", "Spa", 0, Some(later)),
+        ] {
+            repository.create(analysis).await.unwrap();
+        }
+
+        let segments = repository
+            .find_circuit_segments("Spa", &own.header.id)
+            .await
+            .unwrap();
+        assert_eq!(segments, Some(earliest));
+        assert_eq!(
+            repository
+                .find_circuit_segments("Imola", &own.header.id)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+}
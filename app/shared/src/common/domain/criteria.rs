@@ -153,15 +153,19 @@ mod tests {
 
     #[test]
     fn criteria_is_empty_when_filters_are_empty_ok() {
-        let mut criteria = Criteria::default();
-        criteria.filters = Some(Filters::default());
+        let criteria = Criteria {
+            filters: Some(Filters::default()),
+            ..Criteria::default()
+        };
         assert!(criteria.is_empty())
     }
 
     #[test]
     fn criteria_is_empty_when_order_is_empty_ok() {
-        let mut criteria = Criteria::default();
-        criteria.order = Some(Order::new(By::new(""), Type::None));
+        let criteria = Criteria {
+            order: Some(Order::new(By::new(""), Type::None)),
+            ..Criteria::default()
+        };
         assert!(criteria.is_empty())
     }
 }
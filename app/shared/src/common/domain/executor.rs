@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+
+/// CPU bound work run by an `Executor`.
+pub type Work<T> = Box<dyn FnOnce() -> T + Send>;
+
+/// Trait for running CPU bound work without stalling the asynchronous tasks.
+#[async_trait]
+pub trait Executor: Send + Sync {
+    /// Runs `work` to completion and returns its result.
+    ///
+    /// `label` names the work in the logs and measurements of the executor.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the work could not be run to completion, e.g. because it panicked.
+    async fn run<T: Send + 'static>(&self, label: &str, work: Work<T>) -> Result<T, String>;
+}
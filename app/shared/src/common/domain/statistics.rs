@@ -0,0 +1,28 @@
+use std::iter::Sum;
+use std::ops::Div;
+
+/// Floating point samples that can be averaged.
+pub trait Sample: Copy + Sum + Div<Output = Self> {
+    /// Converts a number of samples into the type of the samples.
+    fn from_count(count: usize) -> Self;
+}
+
+impl Sample for f32 {
+    #[allow(clippy::cast_precision_loss)]
+    fn from_count(count: usize) -> Self {
+        count as Self
+    }
+}
+
+impl Sample for f64 {
+    #[allow(clippy::cast_precision_loss)]
+    fn from_count(count: usize) -> Self {
+        count as Self
+    }
+}
+
+/// Arithmetic mean of `values`, or `None` if there are none.
+#[must_use]
+pub fn mean<T: Sample>(values: &[T]) -> Option<T> {
+    (!values.is_empty()).then(|| values.iter().copied().sum::<T>() / T::from_count(values.len()))
+}
@@ -0,0 +1,83 @@
+use crate::common::domain::criteria::filter::condition::Condition;
+use crate::common::domain::criteria::filter::field::Field;
+use crate::common::domain::criteria::filter::value::Value;
+use crate::common::domain::criteria::filter::Filter;
+use crate::common::domain::criteria::filters::Filters;
+use crate::common::domain::criteria::Criteria;
+use crate::consistency::domain::consistency::scope::Scope;
+use crate::consistency::domain::consistency::Consistency;
+use crate::consistency::domain::repository::Repository;
+use crate::lap::domain::repository::Repository as LapRepository;
+
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// A struct responsible for measuring and storing the consistency of a driver over a stint.
+pub struct Creator<R: Repository, LR: LapRepository> {
+    repository: Arc<R>,
+    lap_repository: Arc<LR>,
+}
+
+impl<R: Repository, LR: LapRepository> Creator<R, LR> {
+    /// Creates a new `Creator` instance.
+    ///
+    /// # Parameters
+    ///
+    /// - `repository`: An asynchronous repository for consistency operations.
+    /// - `lap_repository`: An asynchronous repository for lap operations.
+    ///
+    /// # Returns
+    ///
+    /// A new `Creator` instance.
+    pub fn new(repository: Arc<R>, lap_repository: Arc<LR>) -> Self {
+        Self {
+            repository,
+            lap_repository,
+        }
+    }
+
+    /// Asynchronously measures the consistency of a driver over the laps of a file or session.
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: The UUID identifier for the consistency.
+    /// - `scope`: The file or session whose laps are compared.
+    /// - `driver`: The driver whose laps are compared.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the scope has no laps, if there are not enough valid laps of the
+    /// driver or if the repositories fail.
+    pub async fn create(&self, id: Uuid, scope: Scope, driver: &str) -> Result<(), String> {
+        // The date of a session is matched on the laps, every repository compares it the same
+        let filter = match &scope {
+            Scope::File(file_id) => {
+                Filter::new(Field::new("file_id"), Condition::Equal, Value::new(file_id))
+            }
+            Scope::Session { circuit, .. } => {
+                Filter::new(Field::new("circuit"), Condition::Equal, Value::new(circuit))
+            }
+        };
+        let criteria = Criteria::new(
+            Some(Filters::from(vec![
+                filter,
+                Filter::new(Field::new("driver"), Condition::Equal, Value::new(driver)),
+            ])),
+            None,
+            None,
+            None,
+        );
+
+        let laps = self
+            .lap_repository
+            .find_by_criteria(&criteria)
+            .await?
+            .ok_or(format!("No laps found for driver `{driver}` in {scope}"))?;
+
+        let consistency = Consistency::calculate(id, scope, driver.to_string(), Utc::now(), &laps)
+            .map_err(|e| format!("{e}"))?;
+
+        self.repository.create(consistency).await
+    }
+}
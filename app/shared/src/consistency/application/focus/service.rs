@@ -0,0 +1,72 @@
+use crate::analysis::domain::analysis::coaching::Coaching;
+use crate::analysis::domain::repository::Repository as AnalysisRepository;
+use crate::consistency::domain::repository::Repository;
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// A struct responsible for focusing the coaching of an analysis on the sections where the
+/// driver is least consistent.
+pub struct Focuser<R: Repository, AR: AnalysisRepository> {
+    repository: Arc<R>,
+    analysis_repository: Arc<AR>,
+}
+
+impl<R: Repository, AR: AnalysisRepository> Focuser<R, AR> {
+    /// Creates a new `Focuser` instance.
+    ///
+    /// # Parameters
+    ///
+    /// - `repository`: An asynchronous repository for consistency operations.
+    /// - `analysis_repository`: An asynchronous repository for analysis operations.
+    ///
+    /// # Returns
+    ///
+    /// A new `Focuser` instance.
+    pub fn new(repository: Arc<R>, analysis_repository: Arc<AR>) -> Self {
+        Self {
+            repository,
+            analysis_repository,
+        }
+    }
+
+    /// Asynchronously finds the coaching of an analysis, with the advice on the sections where
+    /// the driver is least consistent first.
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: The UUID identifier for the consistency.
+    /// - `analysis_id`: The UUID identifier for the analysis.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the consistency or the analysis is not found, if they belong to
+    /// different circuits or if the repositories fail.
+    ///
+    /// # Returns
+    ///
+    /// The focused coaching, empty if the analysis has not been coached yet.
+    pub async fn focus(&self, id: &Uuid, analysis_id: &Uuid) -> Result<Coaching, String> {
+        let consistency = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .ok_or(format!("Cannot find consistency with id `{id}`"))?;
+        let analysis = self
+            .analysis_repository
+            .find_by_id(analysis_id)
+            .await?
+            .ok_or(format!("Cannot find analysis with id `{analysis_id}`"))?;
+
+        if consistency.circuit != analysis.header.circuit {
+            return Err(format!(
+                "Consistency `{id}` at `{}` cannot focus analysis `{analysis_id}` at `{}`",
+                consistency.circuit, analysis.header.circuit
+            ));
+        }
+
+        let mut coaching = analysis.coaching.unwrap_or_default();
+        consistency.focus(&mut coaching);
+        Ok(coaching)
+    }
+}
@@ -0,0 +1,407 @@
+pub mod envelope;
+pub mod scope;
+pub mod section;
+
+use crate::analysis::domain::analysis::channel::Channel;
+use crate::analysis::domain::analysis::coaching::Coaching;
+use crate::analysis::domain::analysis::interpolation::interpolate_variables;
+use crate::analysis::domain::analysis::segmentation::detect as detect_segments;
+use crate::consistency::domain::consistency::envelope::{Envelope, Envelopes};
+use crate::consistency::domain::consistency::scope::Scope;
+use crate::consistency::domain::consistency::section::{Section, Sections};
+use crate::lap::domain::lap::variables::Variables;
+use crate::lap::domain::lap::Lap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Distance (m) between the samples of the common grid.
+const GRID_STEP: f32 = 5.;
+/// Minimum number of valid laps to measure the consistency.
+const MIN_LAPS: usize = 2;
+/// Maximum number of sections marked as the least consistent.
+const LEAST_CONSISTENT_SECTIONS: usize = 3;
+
+/// Lap-to-lap consistency of a driver over every valid lap of a stint.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Consistency {
+    pub id: Uuid,
+    /// File or session whose laps are compared
+    pub scope: Scope,
+    pub driver: String,
+    pub circuit: String,
+    /// Date on which the consistency was calculated
+    pub date: DateTime<Utc>,
+    /// Laps taken into account
+    pub laps: Vec<Uuid>,
+    /// Common distance grid of the envelopes (m)
+    pub distances: Vec<f32>,
+    /// Mean, standard deviation, minimum and maximum of every channel
+    pub envelopes: Envelopes,
+    /// Corners and straights, from the least to the most consistent
+    pub sections: Sections,
+}
+
+impl Consistency {
+    /// Measures the consistency of `driver` over the valid laps of `scope` among `laps`.
+    ///
+    /// Valid laps are the driven laps of the driver that cover the whole circuit without any
+    /// event: an off-track, incident, spin or pit-road entry would stand out as the least
+    /// consistent section without saying anything about the driving. When they were driven on more
+    /// than one circuit, only the circuit with the most laps is kept, the first one by name on
+    /// a tie, so the result does not depend on the order of `laps`. They are interpolated on a
+    /// common distance grid, where the envelope of every channel is calculated. The circuit is
+    /// segmented on the fastest lap and each section is scored by how much wider its spread is
+    /// than the spread over the whole lap.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::NotEnoughLaps` - If fewer than two valid laps are found.
+    /// * `Error::Interpolation` - If any lap cannot be interpolated on the common grid.
+    pub fn calculate(
+        id: Uuid,
+        scope: Scope,
+        driver: String,
+        date: DateTime<Utc>,
+        laps: &[Lap],
+    ) -> Result<Self, Error> {
+        let mut valid: Vec<&Lap> = laps
+            .iter()
+            .filter(|lap| lap.header.driver == driver && !lap.header.synthetic)
+            .filter(|lap| scope.contains(lap))
+            .filter(|lap| lap.is_valid() && lap.header.time > 0.)
+            .collect();
+        if let Some(circuit) = main_circuit(&valid) {
+            valid.retain(|lap| lap.header.circuit == circuit);
+        }
+        if valid.len() < MIN_LAPS {
+            return Err(Error::NotEnoughLaps(driver, valid.len()));
+        }
+        valid.sort_by(|a, b| {
+            a.header
+                .time
+                .partial_cmp(&b.header.time)
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let distances = grid(&valid);
+        let variables = valid
+            .iter()
+            .map(|lap| interpolate_variables(&lap.variables, &distances))
+            .collect::<Result<Vec<Variables>, _>>()
+            .map_err(|e| Error::Interpolation(format!("{e}")))?;
+
+        let envelopes: Envelopes = Channel::ALL
+            .iter()
+            .map(|&channel| {
+                let values: Vec<Vec<f64>> = variables.iter().map(|v| channel.values(v)).collect();
+                Envelope::calculate(channel, &values)
+            })
+            .collect::<Vec<Envelope>>()
+            .into();
+        let sections = sections(&variables, &distances, &envelopes);
+
+        Ok(Self {
+            id,
+            scope,
+            driver,
+            circuit: valid[0].header.circuit.clone(),
+            date,
+            laps: valid.iter().map(|lap| lap.header.id).collect(),
+            distances,
+            envelopes,
+            sections,
+        })
+    }
+
+    /// Sections where the driver is least consistent, from the widest spread.
+    pub fn least_consistent(&self) -> impl Iterator<Item = &Section> {
+        self.sections.iter().filter(|s| s.least_consistent)
+    }
+
+    /// Moves first the advice of `coaching` on the sections where the driver is least
+    /// consistent, so the coaching focuses on them.
+    pub fn focus(&self, coaching: &mut Coaching) {
+        let sections: Vec<(f32, f32)> = self
+            .least_consistent()
+            .map(|s| (s.segment.entry, s.segment.exit))
+            .collect();
+        coaching.focus(&sections);
+    }
+}
+
+/// Circuit where most of `laps` were driven, the first one by name on a tie.
+fn main_circuit(laps: &[&Lap]) -> Option<String> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for lap in laps {
+        *counts.entry(lap.header.circuit.as_str()).or_default() += 1;
+    }
+    // `max_by_key` keeps the last maximum, so the names are visited in reverse
+    counts
+        .into_iter()
+        .rev()
+        .max_by_key(|&(_, count)| count)
+        .map(|(circuit, _)| circuit.to_string())
+}
+
+/// Distances covered by every lap, every `GRID_STEP` meters.
+fn grid(laps: &[&Lap]) -> Vec<f32> {
+    let bound = |last: bool| {
+        laps.iter().filter_map(move |lap| {
+            let distance = &lap.variables.distance;
+            if last {
+                distance.last().copied()
+            } else {
+                distance.first().copied()
+            }
+        })
+    };
+    let start = bound(false).fold(0f32, f32::max);
+    let end = bound(true).fold(f32::INFINITY, f32::min);
+
+    std::iter::successors(Some(start), |d| Some(d + GRID_STEP))
+        .take_while(|&d| d <= end)
+        .collect()
+}
+
+/// Scores every section of the fastest lap, from the least to the most consistent.
+fn sections(variables: &[Variables], distances: &[f32], envelopes: &Envelopes) -> Sections {
+    let mut sections: Vec<Section> = detect_segments(&variables[0])
+        .iter()
+        .filter_map(|segment| {
+            let start = distances.partition_point(|&d| d < segment.entry);
+            let end = distances.partition_point(|&d| d <= segment.exit);
+            (end > start + 1).then(|| Section {
+                segment: segment.clone(),
+                spread: spread(envelopes, start, end),
+                time_std_dev: time_std_dev(variables, start, end),
+                least_consistent: false,
+            })
+        })
+        .collect();
+
+    sections.sort_by(|a, b| b.spread.partial_cmp(&a.spread).unwrap_or(Ordering::Equal));
+    for section in sections
+        .iter_mut()
+        .take(LEAST_CONSISTENT_SECTIONS)
+        .filter(|s| s.spread > 1.)
+    {
+        section.least_consistent = true;
+    }
+
+    sections.into()
+}
+
+/// Standard deviation in the samples `start..end` relative to the whole lap, averaged over
+/// the channels that vary between laps.
+#[allow(clippy::cast_precision_loss)]
+fn spread(envelopes: &Envelopes, start: usize, end: usize) -> f64 {
+    let ratios: Vec<f64> = envelopes
+        .iter()
+        .filter_map(|envelope| {
+            let lap = envelope.mean_std_dev(None);
+            (lap > f64::EPSILON).then(|| envelope.mean_std_dev(Some((start, end))) / lap)
+        })
+        .collect();
+
+    if ratios.is_empty() {
+        0.
+    } else {
+        ratios.iter().sum::<f64>() / ratios.len() as f64
+    }
+}
+
+/// Standard deviation of the time spent between the samples `start` and `end - 1`.
+#[allow(clippy::cast_precision_loss)]
+fn time_std_dev(variables: &[Variables], start: usize, end: usize) -> f64 {
+    let times: Vec<f64> = variables
+        .iter()
+        .filter_map(|v| {
+            let time = &v.lap_current_lap_time;
+            Some(f64::from(*time.get(end - 1)? - *time.get(start)?))
+        })
+        .collect();
+    if times.is_empty() {
+        return 0.;
+    }
+
+    let count = times.len() as f64;
+    let mean = times.iter().sum::<f64>() / count;
+    (times.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / count).sqrt()
+}
+
+#[derive(PartialEq, Eq, Debug, thiserror::Error)]
+pub enum Error {
+    #[error("at least two valid laps of driver `{0}` are needed, found {1}")]
+    NotEnoughLaps(String, usize),
+    #[error("error interpolating laps: {0}")]
+    Interpolation(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lap::domain::lap::events::{Event, EventKind};
+    use crate::lap::domain::lap::test_util;
+
+    const SAMPLES: usize = 201;
+
+    /// Builds a 1000 m lap with a corner in the middle where the speed drops by `drop` m/s.
+    fn test_lap(driver: &str, drop: f32) -> Lap {
+        let mut variables = Variables::default();
+        let mut time = 0f32;
+        #[allow(clippy::cast_precision_loss)]
+        for i in 0..SAMPLES {
+            let pct = i as f32 / (SAMPLES - 1) as f32;
+            let cornering = (0.4..=0.6).contains(&pct);
+            let speed = if cornering { 50. - drop } else { 50. };
+            if i > 0 {
+                time += 5. / speed;
+            }
+            variables.speed.push(speed);
+            variables.throttle.push(if cornering { 0.5 } else { 1. });
+            variables.brake.push(0.);
+            variables.clutch.push(1.);
+            variables.gear.push(if cornering { 3 } else { 5 });
+            variables.rpm.push(speed * 100.);
+            variables.distance.push(pct * 1000.);
+            variables.distance_pct.push(pct);
+            variables.track_temp.push(30.);
+            variables.latitude.push(f64::from(pct) * 0.01);
+            variables.longitude.push(0.);
+            variables.altitude.push(0.);
+            variables
+                .steering_wheel_angle
+                .push(if cornering { 1. } else { 0. });
+            variables.fuel_level.push(10.);
+            variables.lap_current_lap_time.push(time);
+        }
+
+        test_util::test_lap(1, driver, variables)
+    }
+
+    #[test]
+    fn marks_the_corner_as_least_consistent() {
+        let laps = vec![
+            test_lap("Driver", 10.),
+            test_lap("Driver", 20.),
+            test_lap("Driver", 15.),
+            test_lap("Other", 0.),
+        ];
+
+        let consistency = Consistency::calculate(
+            Uuid::new_v4(),
+            Scope::File("file".to_string()),
+            "Driver".to_string(),
+            Utc::now(),
+            &laps,
+        )
+        .unwrap();
+
+        assert_eq!(consistency.laps.len(), 3);
+        assert_eq!(consistency.laps[0], laps[0].header.id);
+        let speed = consistency.envelopes.channel(Channel::Speed).unwrap();
+        assert_eq!(speed.mean.len(), consistency.distances.len());
+        let apex = consistency.distances.partition_point(|&d| d < 500.);
+        assert!((speed.min[apex] - 30.).abs() < 1e-6);
+        assert!((speed.max[apex] - 40.).abs() < 1e-6);
+        assert!((speed.mean[apex] - 35.).abs() < 1e-6);
+        assert!(speed.std_dev[0].abs() < 1e-6);
+
+        let least: Vec<&Section> = consistency.least_consistent().collect();
+        assert_eq!(least.len(), 1);
+        assert!(least[0].segment.is_corner());
+        assert!(least[0].time_std_dev > 0.);
+    }
+
+    #[test]
+    fn keeps_the_circuit_with_most_laps() {
+        let mut laps = vec![
+            test_lap("Driver", 10.),
+            test_lap("Driver", 20.),
+            test_lap("Driver", 15.),
+        ];
+        laps[0].header.circuit = "Monza".to_string();
+
+        for laps in [laps.clone(), laps.into_iter().rev().collect()] {
+            let consistency = Consistency::calculate(
+                Uuid::new_v4(),
+                Scope::File("file".to_string()),
+                "Driver".to_string(),
+                Utc::now(),
+                &laps,
+            )
+            .unwrap();
+            assert_eq!(consistency.circuit, "Spa");
+            assert_eq!(consistency.laps.len(), 2);
+        }
+    }
+
+    #[test]
+    fn keeps_the_laps_of_the_session() {
+        let mut laps = vec![
+            test_lap("Driver", 10.),
+            test_lap("Driver", 20.),
+            test_lap("Driver", 15.),
+        ];
+        let date = laps[0].header.date;
+        laps[1].header.date = date;
+        laps[1].header.file_id = "other".to_string();
+        laps[2].header.date = date + chrono::Duration::days(1);
+
+        let consistency = Consistency::calculate(
+            Uuid::new_v4(),
+            Scope::Session {
+                circuit: "Spa".to_string(),
+                date,
+            },
+            "Driver".to_string(),
+            Utc::now(),
+            &laps,
+        )
+        .unwrap();
+
+        assert_eq!(consistency.laps, vec![laps[0].header.id, laps[1].header.id]);
+    }
+
+    #[test]
+    fn needs_two_valid_laps() {
+        let mut incomplete = test_lap("Driver", 10.);
+        incomplete.variables.distance_pct.truncate(SAMPLES / 2);
+
+        assert_eq!(
+            Consistency::calculate(
+                Uuid::new_v4(),
+                Scope::File("file".to_string()),
+                "Driver".to_string(),
+                Utc::now(),
+                &[test_lap("Driver", 10.), incomplete],
+            ),
+            Err(Error::NotEnoughLaps("Driver".to_string(), 1))
+        );
+    }
+
+    #[test]
+    fn ignores_the_laps_with_events() {
+        let mut off_track = test_lap("Driver", 10.);
+        off_track.events.push(Event {
+            kind: EventKind::OffTrack,
+            distance: 500.,
+            time: 10.,
+        });
+
+        assert_eq!(
+            Consistency::calculate(
+                Uuid::new_v4(),
+                Scope::File("file".to_string()),
+                "Driver".to_string(),
+                Utc::now(),
+                &[test_lap("Driver", 10.), off_track],
+            ),
+            Err(Error::NotEnoughLaps("Driver".to_string(), 1))
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use crate::analysis::domain::analysis::channel::Channel;
+
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+
+/// Spread of a channel over several laps, sample by sample on a common distance grid.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Envelope {
+    pub channel: Channel,
+    pub mean: Vec<f64>,
+    /// Population standard deviation.
+    pub std_dev: Vec<f64>,
+    pub min: Vec<f64>,
+    pub max: Vec<f64>,
+}
+
+impl Envelope {
+    /// Builds the envelope of `channel` from its values in every lap.
+    ///
+    /// All the laps must be sampled on the same grid. Samples missing in some lap are ignored.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn calculate(channel: Channel, laps: &[Vec<f64>]) -> Self {
+        let samples = laps.iter().map(Vec::len).min().unwrap_or(0);
+        let count = laps.len().max(1) as f64;
+
+        let mut envelope = Self {
+            channel,
+            mean: Vec::with_capacity(samples),
+            std_dev: Vec::with_capacity(samples),
+            min: Vec::with_capacity(samples),
+            max: Vec::with_capacity(samples),
+        };
+
+        for i in 0..samples {
+            let values = laps.iter().map(|lap| lap[i]);
+            let mean = values.clone().sum::<f64>() / count;
+            let variance = values.clone().map(|v| (v - mean).powi(2)).sum::<f64>() / count;
+            envelope.mean.push(mean);
+            envelope.std_dev.push(variance.sqrt());
+            envelope
+                .min
+                .push(values.clone().fold(f64::INFINITY, f64::min));
+            envelope.max.push(values.fold(f64::NEG_INFINITY, f64::max));
+        }
+
+        envelope
+    }
+
+    /// Mean standard deviation of the samples in `range`, or of the whole lap when `None`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mean_std_dev(&self, range: Option<(usize, usize)>) -> f64 {
+        let (start, end) = range.unwrap_or((0, self.std_dev.len()));
+        let std_dev = &self.std_dev[start.min(end)..end.min(self.std_dev.len())];
+        if std_dev.is_empty() {
+            return 0.;
+        }
+        std_dev.iter().sum::<f64>() / std_dev.len() as f64
+    }
+}
+
+/// Represents the envelopes of every channel.
+#[derive(Serialize, Deserialize, PartialEq, Default, Clone, Debug)]
+pub struct Envelopes {
+    envelopes: Vec<Envelope>,
+}
+
+impl Envelopes {
+    /// Envelope of the given channel, if any.
+    #[must_use]
+    pub fn channel(&self, channel: Channel) -> Option<&Envelope> {
+        self.envelopes.iter().find(|e| e.channel == channel)
+    }
+}
+
+impl From<Vec<Envelope>> for Envelopes {
+    /// Converts a vector of envelopes into `Envelopes`.
+    fn from(envelopes: Vec<Envelope>) -> Self {
+        Self { envelopes }
+    }
+}
+
+impl Deref for Envelopes {
+    type Target = Vec<Envelope>;
+
+    /// Implements dereferencing for `Envelopes`.
+    fn deref(&self) -> &Self::Target {
+        &self.envelopes
+    }
+}
@@ -0,0 +1,41 @@
+use crate::lap::domain::lap::Lap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Laps whose consistency is measured.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    /// Laps of a single file.
+    File(String),
+    /// Laps of a session on a circuit, whichever file they were recorded in. Laps of the same
+    /// session share the date of the session.
+    Session {
+        circuit: String,
+        date: DateTime<Utc>,
+    },
+}
+
+impl Scope {
+    /// Whether `lap` belongs to the scope.
+    #[must_use]
+    pub fn contains(&self, lap: &Lap) -> bool {
+        match self {
+            Self::File(file_id) => lap.header.file_id == *file_id,
+            Self::Session { circuit, date } => {
+                lap.header.circuit == *circuit && lap.header.date == *date
+            }
+        }
+    }
+}
+
+impl Display for Scope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::File(file_id) => write!(f, "file `{file_id}`"),
+            Self::Session { circuit, date } => write!(f, "session of {date} at `{circuit}`"),
+        }
+    }
+}
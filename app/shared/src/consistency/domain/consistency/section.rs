@@ -0,0 +1,39 @@
+use crate::analysis::domain::analysis::segment::Segment;
+
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+
+/// Consistency of the driver over a corner or straight.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Section {
+    pub segment: Segment,
+    /// Standard deviation of the channels in the section, relative to the whole lap and
+    /// averaged over the channels. Above 1 the driver is less consistent than on average.
+    pub spread: f64,
+    /// Standard deviation of the time spent in the section (s).
+    pub time_std_dev: f64,
+    /// Whether the section is one of those where the driver is least consistent.
+    pub least_consistent: bool,
+}
+
+/// Represents the sections of the circuit, from the least to the most consistent.
+#[derive(Serialize, Deserialize, PartialEq, Default, Clone, Debug)]
+pub struct Sections {
+    sections: Vec<Section>,
+}
+
+impl From<Vec<Section>> for Sections {
+    /// Converts a vector of sections into `Sections`.
+    fn from(sections: Vec<Section>) -> Self {
+        Self { sections }
+    }
+}
+
+impl Deref for Sections {
+    type Target = Vec<Section>;
+
+    /// Implements dereferencing for `Sections`.
+    fn deref(&self) -> &Self::Target {
+        &self.sections
+    }
+}
@@ -0,0 +1,29 @@
+use crate::consistency::domain::consistency::Consistency;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Trait for asynchronous consistency operations.
+#[async_trait]
+pub trait Repository: Send + Sync {
+    /// Creates new consistency data asynchronously.
+    ///
+    /// # Errors
+    ///
+    /// This asynchronous function will return an `Err` if there is an error while attempting to create the consistency data.
+    async fn create(&self, consistency: Consistency) -> Result<(), String>;
+
+    /// Deletes consistency data asynchronously given its ID.
+    ///
+    /// # Errors
+    ///
+    /// This asynchronous function will return an `Err` if there is an error while attempting to delete the consistency data.
+    async fn delete(&self, id: &Uuid) -> Result<(), String>;
+
+    /// Finds consistency data asynchronously by its ID.
+    ///
+    /// # Errors
+    ///
+    /// This asynchronous function will return an `Err` if there is an error while attempting to find the consistency data.
+    async fn find_by_id(&self, id: &Uuid) -> Result<Option<Consistency>, String>;
+}
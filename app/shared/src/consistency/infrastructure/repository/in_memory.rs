@@ -0,0 +1,68 @@
+use crate::consistency::domain::consistency::Consistency;
+use crate::consistency::domain::repository::Repository;
+
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// An in-memory repository implementation for asynchronous operations.
+pub struct InMemory {
+    consistencies: Arc<Mutex<Vec<Consistency>>>,
+}
+
+impl Default for InMemory {
+    /// Creates a new `InMemory` instance with an empty list of consistencies.
+    fn default() -> Self {
+        Self {
+            consistencies: Arc::new(Mutex::new(Vec::default())),
+        }
+    }
+}
+
+#[async_trait]
+impl Repository for InMemory {
+    /// Creates a new consistency and adds it to the in-memory storage.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the consistency creation fails.
+    async fn create(&self, consistency: Consistency) -> Result<(), String> {
+        self.consistencies
+            .lock()
+            .map_err(|e| format!("{e}"))?
+            .push(consistency);
+        Ok(())
+    }
+
+    /// Deletes a consistency with the specified ID from the in-memory storage.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the consistency deletion fails or if no consistency with the specified ID is found.
+    async fn delete(&self, id: &Uuid) -> Result<(), String> {
+        let mut consistencies_guard = self.consistencies.lock().map_err(|e| format!("{e}"))?;
+        let i = consistencies_guard
+            .iter()
+            .position(|consistency| consistency.id == *id)
+            .ok_or_else(|| format!("No consistency with ID {id} found."))?;
+
+        consistencies_guard.remove(i);
+        drop(consistencies_guard);
+        Ok(())
+    }
+
+    /// Finds a consistency with the specified ID in the in-memory storage.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the consistency retrieval fails.
+    async fn find_by_id(&self, id: &Uuid) -> Result<Option<Consistency>, String> {
+        let consistencies_guard = self.consistencies.lock().map_err(|e| format!("{e}"))?;
+        let result = consistencies_guard
+            .iter()
+            .find(|consistency| consistency.id == *id)
+            .cloned();
+        drop(consistencies_guard);
+        Ok(result)
+    }
+}
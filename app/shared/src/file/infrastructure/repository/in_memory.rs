@@ -79,7 +79,7 @@ impl Repository for InMemory {
             });
         }
 
-        let opt_files: Option<Files> = if files.len() == 0 { None } else { Some(files) };
+        let opt_files: Option<Files> = if files.is_empty() { None } else { Some(files) };
 
         Ok(opt_files)
     }
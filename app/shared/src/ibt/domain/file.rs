@@ -21,7 +21,7 @@ use std::fmt::Debug;
 use std::io::{Read, Seek};
 
 /// The allowed fields in the file.
-pub const ALLOWED_FIELDS: [&str; 16] = [
+pub const ALLOWED_FIELDS: [&str; 53] = [
     "Lap",
     "Speed",
     "Throttle",
@@ -38,6 +38,43 @@ pub const ALLOWED_FIELDS: [&str; 16] = [
     "SteeringWheelAngle",
     "FuelLevel",
     "LapCurrentLapTime",
+    "LatAccel",
+    "LongAccel",
+    "YawRate",
+    "LFspeed",
+    "RFspeed",
+    "LRspeed",
+    "RRspeed",
+    "LFtempCL",
+    "LFtempCM",
+    "LFtempCR",
+    "LFpress",
+    "LFwearL",
+    "LFwearM",
+    "LFwearR",
+    "RFtempCL",
+    "RFtempCM",
+    "RFtempCR",
+    "RFpress",
+    "RFwearL",
+    "RFwearM",
+    "RFwearR",
+    "LRtempCL",
+    "LRtempCM",
+    "LRtempCR",
+    "LRpress",
+    "LRwearL",
+    "LRwearM",
+    "LRwearR",
+    "RRtempCL",
+    "RRtempCM",
+    "RRtempCR",
+    "RRpress",
+    "RRwearL",
+    "RRwearM",
+    "RRwearR",
+    "PlayerTrackSurface",
+    "PlayerCarMyIncidentCount",
 ];
 
 /// Represents an IBT file.
@@ -62,7 +62,7 @@ mod tests {
     use std::io::Cursor;
 
     fn test_bytes() -> Vec<u8> {
-        vec!['a' as u8]
+        vec![b'a']
     }
 
     fn test_var_header() -> VarHeader {
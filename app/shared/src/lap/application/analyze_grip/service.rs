@@ -0,0 +1,90 @@
+use crate::analysis::domain::analysis::grip::{
+    calculate, envelope_laps, GripEnvelope, GripUtilisation,
+};
+use crate::analysis::domain::analysis::segmentation::detect as detect_segments;
+use crate::common::domain::criteria::filter::condition::Condition;
+use crate::common::domain::criteria::filter::field::Field;
+use crate::common::domain::criteria::filter::value::Value;
+use crate::common::domain::criteria::filter::Filter;
+use crate::common::domain::criteria::filters::Filters;
+use crate::common::domain::criteria::Criteria;
+use crate::lap::domain::repository::Repository;
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// A struct responsible for measuring how much of the grip of the car a lap uses.
+pub struct GripAnalyzer<R: Repository> {
+    repository: Arc<R>,
+}
+
+impl<R: Repository> GripAnalyzer<R> {
+    /// Creates a new `GripAnalyzer` instance.
+    ///
+    /// # Parameters
+    ///
+    /// - `repository`: An asynchronous repository for lap operations.
+    ///
+    /// # Returns
+    ///
+    /// A new `GripAnalyzer` instance.
+    pub fn new(repository: Arc<R>) -> Self {
+        Self { repository }
+    }
+
+    /// Asynchronously measures the grip utilisation of a lap in every corner.
+    ///
+    /// The grip envelope is taken from the fastest valid laps stored with the same car on
+    /// the same circuit, by any driver, and the corners are detected on the analysed lap.
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: The UUID identifier of the analysed lap.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the underlying repository fails, or if no lap with acceleration
+    /// data is found to build the envelope.
+    ///
+    /// # Returns
+    ///
+    /// The grip utilisation of the lap, or `None` if the lap does not exist.
+    pub async fn analyze(&self, id: &Uuid) -> Result<Option<GripUtilisation>, String> {
+        let Some(lap) = self.repository.find_by_id(id).await? else {
+            return Ok(None);
+        };
+
+        let criteria = Criteria::new(
+            Some(Filters::from(vec![
+                Filter::new(
+                    Field::new("car"),
+                    Condition::Equal,
+                    Value::new(&lap.header.car),
+                ),
+                Filter::new(
+                    Field::new("circuit"),
+                    Condition::Equal,
+                    Value::new(&lap.header.circuit),
+                ),
+            ])),
+            None,
+            None,
+            None,
+        );
+        let laps = self
+            .repository
+            .find_by_criteria(&criteria)
+            .await?
+            .unwrap_or_default();
+
+        let best = envelope_laps(&laps);
+
+        let envelope = GripEnvelope::calculate(&best).ok_or(format!(
+            "No acceleration data found for car `{}` on circuit `{}`",
+            lap.header.car, lap.header.circuit
+        ))?;
+        let segments = detect_segments(&lap.variables);
+
+        Ok(Some(calculate(&segments, &lap.variables, envelope)))
+    }
+}
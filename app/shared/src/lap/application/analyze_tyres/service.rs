@@ -0,0 +1,60 @@
+use crate::common::domain::criteria::filter::condition::Condition;
+use crate::common::domain::criteria::filter::field::Field;
+use crate::common::domain::criteria::filter::value::Value;
+use crate::common::domain::criteria::filter::Filter;
+use crate::common::domain::criteria::filters::Filters;
+use crate::common::domain::criteria::Criteria;
+use crate::lap::domain::lap::tyres::{report, TyreReport};
+use crate::lap::domain::repository::Repository;
+
+use std::sync::Arc;
+
+/// A struct responsible for reporting the behaviour of the tyres over the laps of a file.
+pub struct TyreAnalyzer<R: Repository> {
+    repository: Arc<R>,
+}
+
+impl<R: Repository> TyreAnalyzer<R> {
+    /// Creates a new `TyreAnalyzer` instance.
+    ///
+    /// # Parameters
+    ///
+    /// - `repository`: An asynchronous repository for lap operations.
+    ///
+    /// # Returns
+    ///
+    /// A new `TyreAnalyzer` instance.
+    pub fn new(repository: Arc<R>) -> Self {
+        Self { repository }
+    }
+
+    /// Asynchronously reports the temperature balance, pressure build-up and wear of every
+    /// tyre over the laps extracted from a file.
+    ///
+    /// # Parameters
+    ///
+    /// - `file_id`: The identifier of the file whose laps are analysed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the underlying repository fails while finding the laps.
+    ///
+    /// # Returns
+    ///
+    /// The tyre report of the stint, or `None` if the file has no laps.
+    pub async fn analyze(&self, file_id: &str) -> Result<Option<TyreReport>, String> {
+        let criteria = Criteria::new(
+            Some(Filters::from(vec![Filter::new(
+                Field::new("file_id"),
+                Condition::Equal,
+                Value::new(file_id),
+            )])),
+            None,
+            None,
+            None,
+        );
+
+        let laps = self.repository.find_by_criteria(&criteria).await?;
+        Ok(laps.map(|laps| report(file_id, &laps)))
+    }
+}
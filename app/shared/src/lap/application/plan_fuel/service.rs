@@ -0,0 +1,173 @@
+use crate::common::domain::criteria::filter::condition::Condition;
+use crate::common::domain::criteria::filter::field::Field;
+use crate::common::domain::criteria::filter::value::Value;
+use crate::common::domain::criteria::filter::Filter;
+use crate::common::domain::criteria::filters::Filters;
+use crate::common::domain::criteria::Criteria;
+use crate::lap::domain::lap::fuel::{plan, Consumption, Plan, RaceLength};
+use crate::lap::domain::lap::summary::summarize;
+use crate::lap::domain::laps::Laps;
+use crate::lap::domain::repository::Repository;
+
+use std::sync::Arc;
+
+/// A struct responsible for measuring the fuel consumption of a session and planning races
+/// from it.
+pub struct FuelPlanner<R: Repository> {
+    repository: Arc<R>,
+}
+
+impl<R: Repository> FuelPlanner<R> {
+    /// Creates a new `FuelPlanner` instance.
+    ///
+    /// # Parameters
+    ///
+    /// - `repository`: An asynchronous repository for lap operations.
+    ///
+    /// # Returns
+    ///
+    /// A new `FuelPlanner` instance.
+    pub fn new(repository: Arc<R>) -> Self {
+        Self { repository }
+    }
+
+    /// Asynchronously measures the fuel consumption over the laps extracted from a file.
+    ///
+    /// # Parameters
+    ///
+    /// - `file_id`: The identifier of the file whose laps are measured.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the underlying repository fails while finding the laps.
+    ///
+    /// # Returns
+    ///
+    /// The consumption, or `None` if the file has no complete laps with fuel telemetry.
+    pub async fn consumption(&self, file_id: &str) -> Result<Option<Consumption>, String> {
+        Ok(self
+            .laps(file_id)
+            .await?
+            .and_then(|laps| Consumption::calculate(&laps)))
+    }
+
+    /// Asynchronously plans the stints of a race from the consumption measured in a file.
+    ///
+    /// Races given in minutes are turned into laps using the median lap time of the file, and
+    /// the tank is the one read from the session info of the file when it was extracted.
+    ///
+    /// # Parameters
+    ///
+    /// - `file_id`: The identifier of the file whose laps are measured.
+    /// - `race`: Length of the race.
+    /// - `reserve_laps`: Laps of fuel kept in the tank at the end of every stint.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the file has no laps with fuel telemetry or tank settings, if the race
+    /// cannot be planned or if the repository fails.
+    pub async fn plan(
+        &self,
+        file_id: &str,
+        race: RaceLength,
+        reserve_laps: Option<f32>,
+    ) -> Result<Plan, String> {
+        let laps = self
+            .laps(file_id)
+            .await?
+            .ok_or(format!("No laps found in file with id `{file_id}`"))?;
+        let consumption = Consumption::calculate(&laps).ok_or(format!(
+            "No fuel consumption measured in file with id `{file_id}`"
+        ))?;
+        let tank = laps
+            .iter()
+            .find_map(|lap| lap.tank.as_ref())
+            .ok_or(format!(
+                "No tank settings found in file with id `{file_id}`"
+            ))?;
+        let lap_time = summarize(file_id, &laps).median.unwrap_or_default();
+
+        plan(&consumption, race, tank, lap_time, reserve_laps).map_err(|e| format!("{e}"))
+    }
+
+    async fn laps(&self, file_id: &str) -> Result<Option<Laps>, String> {
+        let criteria = Criteria::new(
+            Some(Filters::from(vec![Filter::new(
+                Field::new("file_id"),
+                Condition::Equal,
+                Value::new(file_id),
+            )])),
+            None,
+            None,
+            None,
+        );
+
+        self.repository.find_by_criteria(&criteria).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lap::domain::lap::fuel::Tank;
+    use crate::lap::domain::lap::test_util;
+    use crate::lap::domain::lap::variables::Variables;
+    use crate::lap::domain::lap::Lap;
+    use crate::lap::infrastructure::repository::in_memory::InMemory;
+
+    /// Builds a 5 km lap of 100 s burning 3 litres, with the fuel settings of the session.
+    fn test_lap(number: u16, tank: Option<Tank>) -> Lap {
+        let mut variables = Variables::default();
+        #[allow(clippy::cast_precision_loss)]
+        for i in 0..11 {
+            let pct = i as f32 / 10.;
+            variables.distance.push(pct * 5000.);
+            variables.distance_pct.push(pct);
+            variables.fuel_level.push(3f32.mul_add(-pct, 40.));
+            variables.lap_current_lap_time.push(pct * 100.);
+        }
+
+        let mut lap = test_util::test_lap(number, "Driver", variables);
+        lap.tank = tank;
+        lap
+    }
+
+    #[tokio::test]
+    async fn plans_with_the_tank_of_the_session() {
+        let repository = Arc::new(InMemory::default());
+        let tank = Tank {
+            capacity: 60.,
+            start: Some(30.),
+        };
+        repository
+            .create(Laps::from(vec![test_lap(1, Some(tank))]))
+            .await
+            .unwrap();
+        let planner = FuelPlanner::new(Arc::clone(&repository));
+
+        let plan = planner
+            .plan("file", RaceLength::Laps(36), None)
+            .await
+            .unwrap();
+
+        assert_eq!(plan.stints[0].last_lap, 9);
+        assert_eq!(plan.pit_stops, 2);
+    }
+
+    #[tokio::test]
+    async fn rejects_files_without_tank() {
+        let repository = Arc::new(InMemory::default());
+        repository
+            .create(Laps::from(vec![test_lap(1, None)]))
+            .await
+            .unwrap();
+        let planner = FuelPlanner::new(Arc::clone(&repository));
+
+        let result = planner.plan("file", RaceLength::Laps(36), None).await;
+
+        assert_eq!(
+            result,
+            Err("No tank settings found in file with id `file`".to_string())
+        );
+    }
+}
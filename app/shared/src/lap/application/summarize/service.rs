@@ -0,0 +1,59 @@
+use crate::common::domain::criteria::filter::condition::Condition;
+use crate::common::domain::criteria::filter::field::Field;
+use crate::common::domain::criteria::filter::value::Value;
+use crate::common::domain::criteria::filter::Filter;
+use crate::common::domain::criteria::filters::Filters;
+use crate::common::domain::criteria::Criteria;
+use crate::lap::domain::lap::summary::{summarize, Summary};
+use crate::lap::domain::repository::Repository;
+
+use std::sync::Arc;
+
+/// A struct responsible for summarising the laps of a file.
+pub struct Summarizer<R: Repository> {
+    repository: Arc<R>,
+}
+
+impl<R: Repository> Summarizer<R> {
+    /// Creates a new `Summarizer` instance.
+    ///
+    /// # Parameters
+    ///
+    /// - `repository`: An asynchronous repository for lap operations.
+    ///
+    /// # Returns
+    ///
+    /// A new `Summarizer` instance.
+    pub fn new(repository: Arc<R>) -> Self {
+        Self { repository }
+    }
+
+    /// Asynchronously summarises all the laps extracted from a file.
+    ///
+    /// # Parameters
+    ///
+    /// - `file_id`: The identifier of the file whose laps are summarised.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the underlying repository fails while finding the laps.
+    ///
+    /// # Returns
+    ///
+    /// The summary of the laps, or `None` if the file has no laps.
+    pub async fn summarize(&self, file_id: &str) -> Result<Option<Summary>, String> {
+        let criteria = Criteria::new(
+            Some(Filters::from(vec![Filter::new(
+                Field::new("file_id"),
+                Condition::Equal,
+                Value::new(file_id),
+            )])),
+            None,
+            None,
+            None,
+        );
+
+        let laps = self.repository.find_by_criteria(&criteria).await?;
+        Ok(laps.map(|laps| summarize(file_id, &laps)))
+    }
+}
@@ -0,0 +1,187 @@
+use crate::common::domain::criteria::filter::condition::Condition;
+use crate::common::domain::criteria::filter::field::Field;
+use crate::common::domain::criteria::filter::value::Value;
+use crate::common::domain::criteria::filter::Filter;
+use crate::common::domain::criteria::filters::Filters;
+use crate::common::domain::criteria::Criteria;
+use crate::lap::domain::lap::synthetic::{synthesize, Segmentation};
+use crate::lap::domain::lap::Lap;
+use crate::lap::domain::laps::Laps;
+use crate::lap::domain::repository::Repository;
+
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Number of equally sized segments used when neither the request nor the session of the laps
+/// give the sectors.
+const DEFAULT_SEGMENTS: usize = 20;
+
+/// A struct responsible for building and storing theoretical best laps.
+pub struct Synthesizer<R: Repository> {
+    repository: Arc<R>,
+}
+
+impl<R: Repository> Synthesizer<R> {
+    /// Creates a new `Synthesizer` instance.
+    ///
+    /// # Parameters
+    ///
+    /// - `repository`: An asynchronous repository for lap operations.
+    ///
+    /// # Returns
+    ///
+    /// A new `Synthesizer` instance.
+    pub fn new(repository: Arc<R>) -> Self {
+        Self { repository }
+    }
+
+    /// Asynchronously builds the theoretical best lap of a driver with a car on a circuit.
+    ///
+    /// All the driven laps stored for the given driver, car and circuit are split using
+    /// `segmentation`, and the fastest segments are stitched into a new synthetic lap, which
+    /// is stored in the repository with the given `id` so it can be used as an analysis
+    /// reference. Without `segmentation`, the laps are split in the sectors read from the
+    /// session info of their files, or in equally sized segments if no session has them.
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: The UUID identifier for the synthetic lap.
+    /// - `driver`: The driver whose laps are combined.
+    /// - `car`: The car used in the combined laps.
+    /// - `circuit`: The circuit where the combined laps were driven.
+    /// - `segmentation`: How laps are split into segments, if not the sectors of the session.
+    /// - `blend_pct`: Lap fraction used to blend telemetry at each seam.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if no laps are found, if the synthetic lap cannot be built or if the
+    /// repository fails while storing it.
+    pub async fn synthesize(
+        &self,
+        id: Uuid,
+        driver: &str,
+        car: &str,
+        circuit: &str,
+        segmentation: Option<&Segmentation>,
+        blend_pct: f32,
+    ) -> Result<(), String> {
+        let criteria = Criteria::new(
+            Some(Filters::from(vec![
+                Filter::new(Field::new("driver"), Condition::Equal, Value::new(driver)),
+                Filter::new(Field::new("car"), Condition::Equal, Value::new(car)),
+                Filter::new(Field::new("circuit"), Condition::Equal, Value::new(circuit)),
+            ])),
+            None,
+            None,
+            None,
+        );
+
+        let found = self
+            .repository
+            .find_by_criteria(&criteria)
+            .await?
+            .ok_or(format!(
+                "No laps found for driver `{driver}` with car `{car}` on circuit `{circuit}`"
+            ))?;
+        let laps: Vec<&Lap> = found.iter().filter(|lap| !lap.header.synthetic).collect();
+
+        let category = laps
+            .first()
+            .map(|lap| lap.header.category.clone())
+            .unwrap_or_default();
+
+        let segmentation = segmentation.cloned().unwrap_or_else(|| {
+            laps.iter()
+                .find_map(|lap| lap.sectors.clone())
+                .unwrap_or(Segmentation::Fixed(DEFAULT_SEGMENTS))
+        });
+
+        let variables = synthesize(&laps, &segmentation, blend_pct).map_err(|e| format!("{e}"))?;
+
+        let lap = Lap::synthetic(
+            id,
+            driver.to_string(),
+            category,
+            car.to_string(),
+            circuit.to_string(),
+            Utc::now(),
+            variables,
+        );
+
+        self.repository.create(Laps::from(vec![lap])).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lap::domain::lap::test_util;
+    use crate::lap::domain::lap::variables::Variables;
+    use crate::lap::infrastructure::repository::in_memory::InMemory;
+
+    /// Builds a 1000 m lap driven at `first_speed` m/s in its first half and at
+    /// `second_speed` m/s in the second one, in a session with a single sector.
+    fn test_lap(number: u16, first_speed: f32, second_speed: f32) -> Lap {
+        let mut variables = Variables::default();
+        let mut time = 0f32;
+        #[allow(clippy::cast_precision_loss)]
+        for i in 0..101 {
+            let pct = i as f32 / 100.;
+            if i > 0 {
+                time += 10.
+                    / if pct <= 0.5 {
+                        first_speed
+                    } else {
+                        second_speed
+                    };
+            }
+            variables.distance.push(pct * 1000.);
+            variables.distance_pct.push(pct);
+            variables.lap_current_lap_time.push(time);
+        }
+
+        let mut lap = test_util::test_lap(number, "Driver", variables);
+        lap.sectors = Some(Segmentation::Sectors(vec![0.]));
+        lap
+    }
+
+    async fn synthetic_time(segmentation: Option<&Segmentation>) -> f32 {
+        let repository = Arc::new(InMemory::default());
+        repository
+            .create(Laps::from(vec![
+                test_lap(1, 50., 25.),
+                test_lap(2, 25., 50.),
+            ]))
+            .await
+            .unwrap();
+        let synthesizer = Synthesizer::new(Arc::clone(&repository));
+        let id = Uuid::new_v4();
+
+        synthesizer
+            .synthesize(id, "Driver", "Car", "Spa", segmentation, 0.)
+            .await
+            .unwrap();
+
+        repository
+            .find_by_id(&id)
+            .await
+            .unwrap()
+            .unwrap()
+            .header
+            .time
+    }
+
+    #[tokio::test]
+    async fn splits_the_laps_in_the_sectors_of_the_session_by_default() {
+        // A single sector keeps a whole lap, 30 s
+        assert!((synthetic_time(None).await - 30.).abs() < 1e-3);
+    }
+
+    #[tokio::test]
+    async fn splits_the_laps_in_the_given_segments() {
+        // The fastest halves take 10 s each
+        let time = synthetic_time(Some(&Segmentation::Fixed(2))).await;
+        assert!((time - 20.).abs() < 1e-3);
+    }
+}
@@ -1,19 +1,53 @@
+/// Module for off-track, incident, spin and pit-road events.
+pub mod events;
+
+/// Module for fuel consumption and race planning.
+pub mod fuel;
+
 /// Module for lap headers.
 pub mod header;
 
 /// Module for lap headers (plural form).
 pub mod headers;
 
+/// Module for the smoothness of the driver inputs.
+pub mod smoothness;
+
+/// Module for the statistics of the laps of a file.
+pub mod summary;
+
+/// Module for synthetic (theoretical best) laps.
+pub mod synthetic;
+
+/// Module for tyre temperatures, pressures and wear.
+pub mod tyres;
+
 /// Module for lap variables.
 pub mod variables;
 
+/// Module for wheel lock-ups and wheelspin.
+pub mod wheel_slip;
+
+/// Module for the laps shared by the tests.
+#[cfg(test)]
+pub(crate) mod test_util;
+
+use crate::lap::domain::lap::events::Event;
+use crate::lap::domain::lap::fuel::Tank;
 use crate::lap::domain::lap::header::Header;
+use crate::lap::domain::lap::smoothness::Scores;
+use crate::lap::domain::lap::synthetic::{Segmentation, SYNTHETIC_FILE_ID};
+use crate::lap::domain::lap::tyres::Tyres;
 use crate::lap::domain::lap::variables::Variables;
+use crate::lap::domain::lap::wheel_slip::{detect as detect_wheel_slips, WheelSlip};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// Maximum gap (in lap fraction) allowed at the start and end of a lap to consider it complete.
+const COMPLETE_LAP_TOLERANCE: f32 = 0.02;
+
 /// Represents a lap with associated header and variables.
 #[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct Lap {
@@ -22,6 +56,26 @@ pub struct Lap {
 
     /// Variables data for the lap.
     pub variables: Variables,
+
+    /// Lock-ups and wheelspins of the lap, in track order.
+    #[serde(default)]
+    pub wheel_slips: Vec<WheelSlip>,
+
+    /// Setup values and live telemetry of the tyres in the lap.
+    #[serde(default)]
+    pub tyres: Tyres,
+
+    /// Off-tracks, incidents, spins and pit-road entries of the lap, in track order.
+    #[serde(default)]
+    pub events: Vec<Event>,
+
+    /// Fuel settings of the car in the session of the lap.
+    #[serde(default)]
+    pub tank: Option<Tank>,
+
+    /// Sectors of the circuit in the session of the lap.
+    #[serde(default)]
+    pub sectors: Option<Segmentation>,
 }
 
 impl Lap {
@@ -56,10 +110,83 @@ impl Lap {
         variables: Variables,
     ) -> Self {
         let time = *variables.lap_current_lap_time.last().unwrap_or(&0f32);
-        let header = Header::new(
+        let mut header = Header::new(
             id, file_id, number, driver, category, car, circuit, date, time,
         );
+        header.smoothness = Scores::calculate(&variables, 0, variables.lap_current_lap_time.len())
+            .map(|scores| scores.overall);
+        let wheel_slips = detect_wheel_slips(&variables);
+
+        Self {
+            header,
+            variables,
+            wheel_slips,
+            tyres: Tyres::default(),
+            events: Vec::new(),
+            tank: None,
+            sectors: None,
+        }
+    }
 
-        Self { header, variables }
+    /// Constructs a synthetic lap built from the telemetry of other laps.
+    ///
+    /// Synthetic laps have no source file nor lap number, and are flagged in their header so
+    /// they can be told apart from driven laps.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Unique identifier for the lap.
+    /// * `driver` - Name of the driver.
+    /// * `category` - Category of the race (e.g., GT, Formula 1).
+    /// * `car` - Name or model of the car.
+    /// * `circuit` - Name of the circuit where the lap was performed.
+    /// * `date` - Date and time when the lap was synthesized (in UTC).
+    /// * `variables` - Variables data associated with the lap.
+    ///
+    /// # Returns
+    ///
+    /// A new synthetic `Lap` instance.
+    #[must_use]
+    pub fn synthetic(
+        id: Uuid,
+        driver: String,
+        category: String,
+        car: String,
+        circuit: String,
+        date: DateTime<Utc>,
+        variables: Variables,
+    ) -> Self {
+        let mut lap = Self::new(
+            id,
+            SYNTHETIC_FILE_ID.to_string(),
+            0,
+            driver,
+            category,
+            car,
+            circuit,
+            date,
+            variables,
+        );
+        lap.header.synthetic = true;
+        lap
+    }
+
+    /// Whether the telemetry of the lap covers the whole circuit, from the start/finish line
+    /// to the next crossing.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        let pct = &self.variables.distance_pct;
+        matches!(
+            (pct.first(), pct.last()),
+            (Some(&first), Some(&last))
+                if first <= COMPLETE_LAP_TOLERANCE && last >= 1. - COMPLETE_LAP_TOLERANCE
+        )
+    }
+
+    /// Whether the lap can be used as a reference: it covers the whole circuit and was driven
+    /// without any off-track, incident, spin or pit-road entry.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.is_complete() && self.events.is_empty()
     }
 }
@@ -0,0 +1,223 @@
+use crate::lap::domain::lap::variables::Variables;
+use crate::lap::domain::lap::wheel_slip::MIN_SPEED;
+
+use serde::{Deserialize, Serialize};
+
+/// `PlayerTrackSurface` value while the car is off the racing surface.
+const OFF_TRACK: i32 = 0;
+/// `PlayerTrackSurface` value while the car is in its pit stall.
+const IN_PIT_STALL: i32 = 1;
+/// `PlayerTrackSurface` value while the car is on the pit road.
+const APPROACHING_PITS: i32 = 2;
+/// `PlayerTrackSurface` value while the car is on the racing surface.
+const ON_TRACK: i32 = 3;
+/// Yaw rate (rad/s) above which the car is spinning rather than cornering.
+const SPIN_YAW_RATE: f32 = 2.5;
+
+/// Type of an event of a lap.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    /// The car left the racing surface.
+    OffTrack,
+    /// The incident count of the driver increased.
+    Incident,
+    /// The car yawed faster than it can while cornering.
+    Spin,
+    /// The car entered the pit road.
+    PitEntry,
+}
+
+/// Something that happened during a lap.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Event {
+    pub kind: EventKind,
+    /// Distance where the event starts (m).
+    pub distance: f32,
+    /// Lap time when the event starts (s).
+    pub time: f32,
+}
+
+/// Live status channels of the car over a lap, sample by sample.
+#[derive(PartialEq, Default, Clone, Debug)]
+pub struct TrackStatus {
+    /// Values of `PlayerTrackSurface`.
+    pub surface: Vec<i32>,
+    /// Values of `PlayerCarMyIncidentCount`.
+    pub incidents: Vec<i32>,
+    /// Last `PlayerTrackSurface` value of the previous lap, to detect the events that start
+    /// across the start/finish line.
+    pub previous_surface: Option<i32>,
+    /// Last `PlayerCarMyIncidentCount` value of the previous lap.
+    pub previous_incidents: Option<i32>,
+}
+
+/// Detects the off-track excursions, incidents, spins and pit-road entries of a lap, in track
+/// order.
+///
+/// Spins are detected from the yaw rate of `variables`, the other events from `status`. The first
+/// sample is compared against the last one of the previous lap when `status` has it. Laps
+/// recorded without any of those channels have no events of that type.
+#[must_use]
+pub fn detect(variables: &Variables, status: &TrackStatus) -> Vec<Event> {
+    let len = variables
+        .distance
+        .len()
+        .min(variables.lap_current_lap_time.len());
+    let event = |kind: EventKind, i: usize| Event {
+        kind,
+        distance: variables.distance[i],
+        time: variables.lap_current_lap_time[i],
+    };
+
+    // Value of a channel at the sample before `i`, which for the first one is in the previous lap
+    let before = |values: &[i32], previous_lap: Option<i32>, i: usize| {
+        if i == 0 {
+            previous_lap
+        } else {
+            values.get(i - 1).copied()
+        }
+    };
+
+    let mut events: Vec<Event> = Vec::new();
+    for i in 0..len {
+        let previous_surface = before(&status.surface, status.previous_surface, i);
+        let entered = |matches: fn(i32) -> bool| {
+            matches!(
+                (previous_surface, status.surface.get(i)),
+                (Some(previous), Some(&current)) if !matches(previous) && matches(current)
+            )
+        };
+        if entered(|surface| surface == OFF_TRACK) {
+            events.push(event(EventKind::OffTrack, i));
+        }
+        // Teleporting to the pits from outside the world is not an entry
+        if entered(|surface| surface == APPROACHING_PITS || surface == IN_PIT_STALL)
+            && matches!(previous_surface, Some(OFF_TRACK | ON_TRACK))
+        {
+            events.push(event(EventKind::PitEntry, i));
+        }
+        if let (Some(previous), Some(&current)) = (
+            before(&status.incidents, status.previous_incidents, i),
+            status.incidents.get(i),
+        ) {
+            if current > previous {
+                events.push(event(EventKind::Incident, i));
+            }
+        }
+    }
+
+    let spinning = |i: usize| {
+        matches!(
+            (variables.yaw_rate.get(i), variables.speed.get(i)),
+            (Some(&yaw_rate), Some(&speed)) if speed >= MIN_SPEED && yaw_rate.abs() > SPIN_YAW_RATE
+        )
+    };
+    events.extend(
+        (0..len)
+            .filter(|&i| spinning(i) && (i == 0 || !spinning(i - 1)))
+            .map(|i| event(EventKind::Spin, i)),
+    );
+
+    events.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 100 samples at 10 per second and 40 m/s, leaving the track between samples 20 and 24,
+    /// spinning between samples 22 and 26 and entering the pits at sample 90.
+    fn test_lap() -> (Variables, TrackStatus) {
+        let mut variables = Variables::default();
+        let mut status = TrackStatus::default();
+        for i in 0..100 {
+            #[allow(clippy::cast_precision_loss)]
+            let t = i as f32 / 10.;
+            variables.lap_current_lap_time.push(t);
+            variables.distance.push(t * 40.);
+            variables.speed.push(40.);
+            variables
+                .yaw_rate
+                .push(if (22..27).contains(&i) { -3. } else { 0.5 });
+            status.surface.push(match i {
+                20..=24 => OFF_TRACK,
+                90.. => APPROACHING_PITS,
+                _ => ON_TRACK,
+            });
+            status.incidents.push(if i < 23 { 2 } else { 6 });
+        }
+        (variables, status)
+    }
+
+    #[test]
+    fn detects_every_event_type() {
+        let (variables, status) = test_lap();
+
+        let events = detect(&variables, &status);
+
+        let kinds: Vec<EventKind> = events.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                EventKind::OffTrack,
+                EventKind::Spin,
+                EventKind::Incident,
+                EventKind::PitEntry
+            ]
+        );
+        assert!((events[0].distance - 80.).abs() < 1e-3);
+        assert!((events[0].time - 2.).abs() < 1e-6);
+        assert!((events[3].distance - 360.).abs() < 1e-3);
+    }
+
+    #[test]
+    fn does_not_detect_a_pit_entry_when_teleporting_to_the_pits() {
+        let (variables, mut status) = test_lap();
+        // Towed from outside the world straight into the pit stall
+        status.surface[89] = -1;
+        status.surface[90..].fill(IN_PIT_STALL);
+
+        let events = detect(&variables, &status);
+
+        assert!(events.iter().all(|e| e.kind != EventKind::PitEntry));
+    }
+
+    #[test]
+    fn detects_events_across_the_start_finish_line() {
+        let (variables, mut status) = test_lap();
+        status.surface[0] = OFF_TRACK;
+        status.previous_surface = Some(ON_TRACK);
+        status.previous_incidents = Some(0);
+
+        let events = detect(&variables, &status);
+
+        let at_start: Vec<EventKind> = events
+            .iter()
+            .filter(|e| e.distance == 0.)
+            .map(|e| e.kind)
+            .collect();
+        assert_eq!(at_start, vec![EventKind::OffTrack, EventKind::Incident]);
+    }
+
+    #[test]
+    fn does_not_detect_events_at_the_start_without_the_previous_lap() {
+        let (variables, mut status) = test_lap();
+        status.surface[0] = OFF_TRACK;
+
+        let events = detect(&variables, &status);
+
+        assert!(events.iter().all(|e| e.distance > 0.));
+    }
+
+    #[test]
+    fn only_spins_without_status_channels() {
+        let (variables, _) = test_lap();
+
+        let events = detect(&variables, &TrackStatus::default());
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, EventKind::Spin);
+    }
+}
@@ -0,0 +1,409 @@
+use crate::common::domain::statistics::mean;
+use crate::ibt::domain::file::session_info::SessionInfo;
+use crate::lap::domain::lap::Lap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Fuel left in the tank at the finish, in laps of consumption, when no reserve is given.
+const DEFAULT_RESERVE_LAPS: f32 = 1.;
+
+/// Fuel used in a complete lap.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct LapFuel {
+    pub id: Uuid,
+    pub number: u16,
+    /// Fuel used in the lap, in the units of `fuel_level` (l).
+    pub used: f32,
+    /// Fuel used per kilometre (l/km).
+    pub per_km: f32,
+}
+
+impl LapFuel {
+    /// Measures the fuel used in `lap`.
+    ///
+    /// Returns `None` if the lap has no fuel telemetry, no distance or the car was refuelled
+    /// during it.
+    #[must_use]
+    pub fn measure(lap: &Lap) -> Option<Self> {
+        let fuel = &lap.variables.fuel_level;
+        let distance = &lap.variables.distance;
+        let used = *fuel.first()? - *fuel.last()?;
+        let km = (*distance.last()? - *distance.first()?) / 1000.;
+        (used >= 0. && km > 0.).then(|| Self {
+            id: lap.header.id,
+            number: lap.header.number,
+            used,
+            per_km: used / km,
+        })
+    }
+}
+
+/// Fuel consumption over the complete laps of a session.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Consumption {
+    /// Laps used to measure the consumption, in lap order. Laps where the car was refuelled
+    /// are left out.
+    pub laps: Vec<LapFuel>,
+    /// Average fuel used per lap (l).
+    pub per_lap: f32,
+    /// Variance of the fuel used per lap (l²).
+    pub variance: f32,
+    /// Average fuel used per kilometre (l/km).
+    pub per_km: f32,
+    /// Fuel left at the end of the last lap (l).
+    pub remaining: f32,
+    /// Laps that can still be driven with the remaining fuel.
+    pub laps_remaining: f32,
+}
+
+impl Consumption {
+    /// Measures the fuel consumption over the complete laps among `laps`.
+    ///
+    /// Returns `None` if there is no complete lap with fuel telemetry.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    pub fn calculate(laps: &[Lap]) -> Option<Self> {
+        // Laps with events still burn the fuel of a lap, so only complete laps are required
+        let mut complete: Vec<&Lap> = laps
+            .iter()
+            .filter(|lap| !lap.header.synthetic && lap.is_complete())
+            .collect();
+        complete.sort_by_key(|lap| lap.header.number);
+
+        let fuel_laps: Vec<LapFuel> = complete
+            .iter()
+            .filter_map(|lap| LapFuel::measure(lap))
+            .collect();
+        if fuel_laps.is_empty() {
+            return None;
+        }
+
+        let used: Vec<f64> = fuel_laps.iter().map(|l| f64::from(l.used)).collect();
+        let per_lap = mean(&used)?;
+        let deviations: Vec<f64> = used.iter().map(|u| (u - per_lap).powi(2)).collect();
+        let variance = mean(&deviations)?;
+        let per_km_laps: Vec<f64> = fuel_laps.iter().map(|l| f64::from(l.per_km)).collect();
+        let per_km = mean(&per_km_laps)?;
+        let remaining = complete
+            .last()
+            .and_then(|lap| lap.variables.fuel_level.last().copied())
+            .unwrap_or_default();
+        let laps_remaining = if per_lap > 0. {
+            f64::from(remaining) / per_lap
+        } else {
+            0.
+        };
+
+        Some(Self {
+            laps: fuel_laps,
+            per_lap: per_lap as f32,
+            variance: variance as f32,
+            per_km: per_km as f32,
+            remaining,
+            laps_remaining: laps_remaining as f32,
+        })
+    }
+}
+
+/// Fuel settings of the car.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Tank {
+    /// Maximum fuel allowed in the tank (l).
+    pub capacity: f32,
+    /// Fuel in the tank at the start, from the car setup (l).
+    pub start: Option<f32>,
+}
+
+impl Tank {
+    /// Reads the tank capacity, restricted by the series, from the driver info and the starting
+    /// fuel from the car setup.
+    ///
+    /// Returns `None` if the session does not report the tank capacity.
+    #[must_use]
+    pub fn from_session_info(session_info: &SessionInfo) -> Option<Self> {
+        let driver_info = session_info.driver_info.as_ref()?;
+        let capacity = driver_info.driver_car_fuel_max_ltr?
+            * driver_info.driver_car_max_fuel_pct.unwrap_or(1.);
+
+        let setup = session_info.car_setup.as_ref();
+        let start = setup
+            .and_then(|setup| setup.brakes_drive_unit.as_ref())
+            .and_then(|unit| unit.fuel.as_ref())
+            .and_then(|fuel| fuel.fuel_level.as_deref())
+            .or_else(|| {
+                setup
+                    .and_then(|setup| setup.chassis.as_ref())
+                    .and_then(|chassis| chassis.rear.as_ref())
+                    .and_then(|rear| rear.fuel_level.as_deref())
+            })
+            .and_then(parse_litres);
+
+        Some(Self { capacity, start })
+    }
+}
+
+/// Parses setup values such as `45.0 L`.
+fn parse_litres(value: &str) -> Option<f32> {
+    value.split_whitespace().next()?.parse().ok()
+}
+
+/// Length of a race.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum RaceLength {
+    Laps(u32),
+    Minutes(f32),
+}
+
+/// Laps driven between two stops, and the fuel to put in the car for them.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Stint {
+    pub number: u32,
+    /// First lap of the stint, starting at 1.
+    pub first_lap: u32,
+    pub last_lap: u32,
+    /// Fuel needed in the tank at the start of the stint (l).
+    pub fuel: f32,
+}
+
+/// Stints and pit stops to finish a race.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Plan {
+    pub race_laps: u32,
+    /// Fuel needed for the whole race, reserve included (l).
+    pub fuel_needed: f32,
+    pub stints: Vec<Stint>,
+    pub pit_stops: u32,
+}
+
+/// Plans the stints of a race from the measured consumption.
+///
+/// Races given in minutes are turned into laps using `lap_time` (s). Every stint keeps
+/// `reserve_laps` laps of fuel in the tank at its end. When the tank has a starting fuel, the
+/// first stint lasts as long as it allows and the rest of the race is split in stints as even as
+/// possible; otherwise all the stints are as even as possible.
+///
+/// # Errors
+///
+/// * `Error::NoConsumption` - If the consumption per lap is not positive.
+/// * `Error::NoLapTime` - If the race is given in minutes and `lap_time` is not positive.
+/// * `Error::EmptyRace` - If the race has no laps.
+/// * `Error::TankTooSmall` - If the tank cannot hold a single lap plus the reserve.
+/// * `Error::StartTooLow` - If the starting fuel does not cover a single lap plus the reserve.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+pub fn plan(
+    consumption: &Consumption,
+    race: RaceLength,
+    tank: &Tank,
+    lap_time: f32,
+    reserve_laps: Option<f32>,
+) -> Result<Plan, Error> {
+    if consumption.per_lap <= 0. {
+        return Err(Error::NoConsumption);
+    }
+    let race_laps = match race {
+        RaceLength::Laps(laps) => laps,
+        RaceLength::Minutes(_) if lap_time <= 0. => return Err(Error::NoLapTime),
+        RaceLength::Minutes(minutes) => (minutes * 60. / lap_time).ceil() as u32,
+    };
+    if race_laps == 0 {
+        return Err(Error::EmptyRace);
+    }
+
+    let reserve = reserve_laps.unwrap_or(DEFAULT_RESERVE_LAPS).max(0.) * consumption.per_lap;
+    let laps_per_tank = ((tank.capacity - reserve) / consumption.per_lap).floor();
+    if laps_per_tank < 1. {
+        return Err(Error::TankTooSmall(tank.capacity));
+    }
+    let laps_per_tank = laps_per_tank as u32;
+
+    let mut lengths = Vec::new();
+    if let Some(start) = tank.start {
+        let laps_on_start = ((start.min(tank.capacity) - reserve) / consumption.per_lap).floor();
+        if laps_on_start < 1. {
+            return Err(Error::StartTooLow(start));
+        }
+        lengths.push((laps_on_start as u32).min(race_laps));
+    }
+    let remaining = race_laps - lengths.iter().sum::<u32>();
+    lengths.extend(even_stints(remaining, laps_per_tank));
+
+    let mut first_lap = 1;
+    let stints: Vec<Stint> = lengths
+        .iter()
+        .zip(1..)
+        .map(|(&laps, number)| {
+            let stint = Stint {
+                number,
+                first_lap,
+                last_lap: first_lap + laps - 1,
+                fuel: (laps as f32).mul_add(consumption.per_lap, reserve),
+            };
+            first_lap += laps;
+            stint
+        })
+        .collect();
+
+    Ok(Plan {
+        race_laps,
+        fuel_needed: (race_laps as f32).mul_add(consumption.per_lap, reserve),
+        pit_stops: stints.len() as u32 - 1,
+        stints,
+    })
+}
+
+/// Splits `laps` in as few and as even stints as a tank lasting `laps_per_tank` laps allows.
+fn even_stints(laps: u32, laps_per_tank: u32) -> Vec<u32> {
+    let stint_count = laps.div_ceil(laps_per_tank);
+    (0..stint_count)
+        .map(|i| laps / stint_count + u32::from(i < laps % stint_count))
+        .collect()
+}
+
+#[derive(PartialEq, Debug, thiserror::Error)]
+pub enum Error {
+    #[error("no fuel consumption measured")]
+    NoConsumption,
+    #[error("a lap time is needed to plan races given in minutes")]
+    NoLapTime,
+    #[error("the race must last at least one lap")]
+    EmptyRace,
+    #[error("a tank of {0} l cannot hold a lap plus the reserve")]
+    TankTooSmall(f32),
+    #[error("starting with {0} l does not cover a lap plus the reserve")]
+    StartTooLow(f32),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lap::domain::lap::test_util;
+    use crate::lap::domain::lap::variables::Variables;
+
+    const SAMPLES: usize = 11;
+
+    /// Builds a 5 km lap starting with `fuel` litres and burning `used` litres.
+    fn test_lap(number: u16, fuel: f32, used: f32) -> Lap {
+        let mut variables = Variables::default();
+        #[allow(clippy::cast_precision_loss)]
+        for i in 0..SAMPLES {
+            let pct = i as f32 / (SAMPLES - 1) as f32;
+            variables.distance.push(pct * 5000.);
+            variables.distance_pct.push(pct);
+            variables.fuel_level.push(used.mul_add(-pct, fuel));
+            variables.lap_current_lap_time.push(pct * 100.);
+        }
+
+        test_util::test_lap(number, "Driver", variables)
+    }
+
+    #[test]
+    fn measures_consumption() {
+        let laps = vec![
+            test_lap(2, 37., 3.),
+            test_lap(1, 40., 3.),
+            test_lap(3, 34., 2.),
+            // Refuelled
+            test_lap(4, 20., -30.),
+        ];
+
+        let consumption = Consumption::calculate(&laps).unwrap();
+
+        assert_eq!(consumption.laps.len(), 3);
+        assert_eq!(consumption.laps[0].number, 1);
+        assert!((consumption.per_lap - 8. / 3.).abs() < 1e-5);
+        assert!((consumption.variance - 2. / 9.).abs() < 1e-5);
+        assert!((consumption.per_km - 8. / 15.).abs() < 1e-5);
+        assert!((consumption.remaining - 50.).abs() < 1e-5);
+        assert!((consumption.laps_remaining - 18.75).abs() < 1e-4);
+    }
+
+    #[test]
+    fn plans_even_stints() {
+        let consumption = Consumption::calculate(&[test_lap(1, 40., 3.)]).unwrap();
+        let tank = Tank {
+            capacity: 60.,
+            start: None,
+        };
+
+        let plan = plan(&consumption, RaceLength::Minutes(60.), &tank, 100., None).unwrap();
+
+        assert_eq!(plan.race_laps, 36);
+        assert_eq!(plan.pit_stops, 1);
+        assert_eq!(plan.stints.len(), 2);
+        assert_eq!(plan.stints[0].last_lap, 18);
+        assert_eq!(plan.stints[1].first_lap, 19);
+        assert_eq!(plan.stints[1].last_lap, 36);
+        assert!((plan.stints[0].fuel - 57.).abs() < 1e-4);
+        assert!((plan.fuel_needed - 111.).abs() < 1e-4);
+        assert_eq!(
+            super::plan(
+                &consumption,
+                RaceLength::Laps(10),
+                &Tank {
+                    capacity: 5.,
+                    start: None
+                },
+                100.,
+                None
+            ),
+            Err(Error::TankTooSmall(5.))
+        );
+    }
+
+    #[test]
+    fn plans_the_first_stint_on_the_starting_fuel() {
+        let consumption = Consumption::calculate(&[test_lap(1, 40., 3.)]).unwrap();
+        let tank = Tank {
+            capacity: 60.,
+            start: Some(30.),
+        };
+
+        let plan = plan(&consumption, RaceLength::Laps(36), &tank, 100., None).unwrap();
+
+        // 30 l cover 9 laps plus the reserve, and the 27 laps left need two full tanks
+        let lengths: Vec<u32> = plan
+            .stints
+            .iter()
+            .map(|stint| stint.last_lap - stint.first_lap + 1)
+            .collect();
+        assert_eq!(lengths, [9, 14, 13]);
+        assert_eq!(plan.pit_stops, 2);
+        assert_eq!(plan.stints[2].last_lap, 36);
+        assert_eq!(
+            super::plan(
+                &consumption,
+                RaceLength::Laps(36),
+                &Tank {
+                    capacity: 60.,
+                    start: Some(5.)
+                },
+                100.,
+                None
+            ),
+            Err(Error::StartTooLow(5.))
+        );
+    }
+
+    #[test]
+    fn reads_the_tank_from_the_session_info() {
+        let session_info: SessionInfo = serde_yaml::from_str(
+            "DriverInfo:\n  DriverCarFuelMaxLtr: 120.0\n  DriverCarMaxFuelPct: 0.5\n\
+             CarSetup:\n  BrakesDriveUnit:\n    Fuel:\n      FuelLevel: 45.0 L\n",
+        )
+        .unwrap();
+
+        let tank = Tank::from_session_info(&session_info).unwrap();
+
+        assert!((tank.capacity - 60.).abs() < 1e-5);
+        assert_eq!(tank.start, Some(45.));
+        assert!(
+            Tank::from_session_info(&serde_yaml::from_str("WeekendInfo: {}\n").unwrap()).is_none()
+        );
+    }
+}
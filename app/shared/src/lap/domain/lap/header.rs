@@ -23,6 +23,12 @@ pub struct Header {
     pub date: DateTime<Utc>,
     /// The time taken to complete the lap (in seconds).
     pub time: f32,
+    /// Whether the lap has been synthesized from other laps instead of being driven.
+    #[serde(default)]
+    pub synthetic: bool,
+    /// Overall smoothness of the driver inputs, from 0 (harsh) to 100 (perfectly steady).
+    #[serde(default)]
+    pub smoothness: Option<f32>,
 }
 
 impl Header {
@@ -50,6 +56,8 @@ impl Header {
             circuit,
             date,
             time,
+            synthetic: false,
+            smoothness: None,
         }
     }
 }
@@ -0,0 +1,207 @@
+use crate::lap::domain::lap::variables::Variables;
+
+use serde::{Deserialize, Serialize};
+
+/// Root mean square steering jerk (rad/s²) that halves the steering score.
+const STEERING_JERK_SCALE: f32 = 20.;
+/// Root mean square pedal rate (1/s) that halves the throttle and brake scores.
+const PEDAL_RATE_SCALE: f32 = 2.;
+/// Steering rate (rad/s) from which a quick reversal of the wheel is a correction.
+const CORRECTION_RATE: f32 = 3.;
+/// Maximum time (s) between both movements of a correction.
+const CORRECTION_WINDOW: f32 = 0.3;
+
+/// How smooth the inputs of a lap are over a stretch of the circuit, from 0 (harsh) to 100
+/// (perfectly steady).
+#[derive(Serialize, Deserialize, PartialEq, Default, Clone, Debug)]
+pub struct Scores {
+    /// From the steering jerk, the change of the steering rate.
+    pub steering: f32,
+    /// From the throttle application rate.
+    pub throttle: f32,
+    /// From the brake application rate.
+    pub brake: f32,
+    /// Average of the other scores.
+    pub overall: f32,
+}
+
+impl Scores {
+    /// Scores the inputs of a lap between the samples `start` and `end - 1`.
+    ///
+    /// Returns `None` if there are fewer than three samples with time between them.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn calculate(variables: &Variables, start: usize, end: usize) -> Option<Self> {
+        let end = end.min(variables.lap_current_lap_time.len());
+        let time = variables.lap_current_lap_time.get(start..end)?;
+        let slice = |values: &Vec<f32>| values.get(start..end).unwrap_or_default().to_vec();
+
+        let steering_rate = rate(&slice(&variables.steering_wheel_angle), time);
+        let steering_jerk = rate(&steering_rate, time);
+        if steering_jerk.is_empty() {
+            return None;
+        }
+
+        let steering = score(rms(&steering_jerk), STEERING_JERK_SCALE);
+        let throttle = score(
+            rms(&rate(&slice(&variables.throttle), time)),
+            PEDAL_RATE_SCALE,
+        );
+        let brake = score(rms(&rate(&slice(&variables.brake), time)), PEDAL_RATE_SCALE);
+
+        Some(Self {
+            steering,
+            throttle,
+            brake,
+            overall: (steering + throttle + brake) / 3.,
+        })
+    }
+}
+
+/// Kind of steering correction.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum CorrectionKind {
+    /// The wheel is quickly turned past the centre, against the corner.
+    CounterSteer,
+    /// The wheel is quickly turned and straight back, without crossing the centre.
+    Spike,
+}
+
+/// A quick back and forth movement of the steering wheel.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Correction {
+    pub kind: CorrectionKind,
+    /// Distance where the correction starts (m).
+    pub distance: f32,
+    /// Highest steering rate reached during the correction (rad/s).
+    pub peak_rate: f32,
+}
+
+/// Detects the steering corrections of a lap, in track order.
+///
+/// A correction is a steering rate above `CORRECTION_RATE` followed, within
+/// `CORRECTION_WINDOW`, by a rate as quick in the opposite direction.
+#[must_use]
+pub fn detect_corrections(variables: &Variables) -> Vec<Correction> {
+    let time = &variables.lap_current_lap_time;
+    let angle = &variables.steering_wheel_angle;
+    let len = time.len().min(angle.len()).min(variables.distance.len());
+    let rates = rate(&angle[..len], &time[..len]);
+
+    let mut corrections = Vec::new();
+    let mut i = 0;
+    while i < rates.len() {
+        if rates[i].abs() < CORRECTION_RATE {
+            i += 1;
+            continue;
+        }
+        let reversal = (i + 1..rates.len())
+            .take_while(|&j| time[j] - time[i] <= CORRECTION_WINDOW)
+            .find(|&j| rates[j].abs() >= CORRECTION_RATE && rates[j].signum() != rates[i].signum());
+        let Some(j) = reversal else {
+            i += 1;
+            continue;
+        };
+
+        let crosses_centre = angle[i..=j + 1]
+            .iter()
+            .any(|a| a.signum() != angle[i].signum() && *a != 0.);
+        corrections.push(Correction {
+            kind: if crosses_centre {
+                CorrectionKind::CounterSteer
+            } else {
+                CorrectionKind::Spike
+            },
+            distance: variables.distance[i],
+            peak_rate: rates[i..=j].iter().fold(0., |max, r| r.abs().max(max)),
+        });
+        i = j + 1;
+    }
+
+    corrections
+}
+
+/// Change per second of `values`, one element shorter than them. Samples without time between
+/// them have no change.
+fn rate(values: &[f32], time: &[f32]) -> Vec<f32> {
+    values
+        .windows(2)
+        .zip(time.windows(2))
+        .map(|(v, t)| {
+            let dt = t[1] - t[0];
+            if dt > 0. {
+                (v[1] - v[0]) / dt
+            } else {
+                0.
+            }
+        })
+        .collect()
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn rms(values: &[f32]) -> f32 {
+    if values.is_empty() {
+        return 0.;
+    }
+    (values.iter().map(|v| v * v).sum::<f32>() / values.len() as f32).sqrt()
+}
+
+/// Score from 0 to 100 that halves every time `value` grows by `scale`.
+fn score(value: f32, scale: f32) -> f32 {
+    100. / (1. + value / scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 10 s sampled at 60 Hz, 10 m per sample, steering with a sine of `frequency` Hz.
+    fn test_variables(frequency: f32) -> Variables {
+        let mut variables = Variables::default();
+        for i in 0..600 {
+            #[allow(clippy::cast_precision_loss)]
+            let t = i as f32 / 60.;
+            variables.lap_current_lap_time.push(t);
+            #[allow(clippy::cast_precision_loss)]
+            variables.distance.push(i as f32 * 10.);
+            variables
+                .steering_wheel_angle
+                .push((std::f32::consts::TAU * frequency * t).sin() * 0.5);
+            variables.throttle.push(1.);
+            variables.brake.push(0.);
+        }
+        variables
+    }
+
+    #[test]
+    fn quicker_steering_scores_lower() {
+        let smooth = Scores::calculate(&test_variables(0.1), 0, 600).unwrap();
+        let harsh = Scores::calculate(&test_variables(2.), 0, 600).unwrap();
+
+        assert!(smooth.steering > 95.);
+        assert!(harsh.steering < smooth.steering);
+        assert!((smooth.throttle - 100.).abs() < f32::EPSILON);
+        assert!((smooth.overall - (smooth.steering + 200.) / 3.).abs() < 1e-3);
+    }
+
+    #[test]
+    fn detects_counter_steer() {
+        let mut variables = test_variables(0.);
+        // Steady at 0.3 rad, then flicked to -0.2 rad and back within 0.2 s
+        for (i, angle) in variables.steering_wheel_angle.iter_mut().enumerate() {
+            *angle = match i {
+                300..=305 => -0.2,
+                _ => 0.3,
+            };
+        }
+
+        let corrections = detect_corrections(&variables);
+
+        assert_eq!(corrections.len(), 1);
+        assert_eq!(corrections[0].kind, CorrectionKind::CounterSteer);
+        assert!((corrections[0].distance - 2990.).abs() < f32::EPSILON);
+        assert!((corrections[0].peak_rate - 30.).abs() < 1e-3);
+        assert!(detect_corrections(&test_variables(0.1)).is_empty());
+    }
+}
@@ -0,0 +1,305 @@
+use crate::common::domain::statistics::mean;
+use crate::lap::domain::lap::fuel::LapFuel;
+use crate::lap::domain::lap::synthetic::{synthesize, Segmentation};
+use crate::lap::domain::lap::Lap;
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Number of segments used to build the theoretical best lap.
+const THEORETICAL_BEST_SEGMENTS: usize = 20;
+/// Laps slower than the best by less than this ratio are considered on pace.
+const ON_PACE_RATIO: f64 = 1.01;
+
+/// Identifies a lap and its time.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct LapTime {
+    pub id: Uuid,
+    pub number: u16,
+    /// Lap time (s).
+    pub time: f32,
+}
+
+/// Statistics of all the laps of a file.
+///
+/// Lap time statistics only take into account the complete laps, so out and in laps are
+/// ignored. Laps with events are kept in them, as they are part of the pace of the session, but
+/// the best lap and the theoretical best only use valid laps, as cutting the track may have made
+/// them faster.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Summary {
+    pub file_id: String,
+    /// Number of driven laps in the file.
+    pub laps: usize,
+    /// Number of complete laps, used for the statistics.
+    pub timed_laps: usize,
+    /// Fastest valid lap.
+    pub best: Option<LapTime>,
+    /// Average lap time (s).
+    pub average: Option<f32>,
+    /// Median lap time (s).
+    pub median: Option<f32>,
+    /// Time of the lap stitched from the fastest segments of every lap of a driver, the fastest
+    /// among the drivers of the file (s).
+    pub theoretical_best: Option<f32>,
+    /// Standard deviation of the lap times (s).
+    pub std_dev: Option<f32>,
+    /// Number of laps within 101% of the best lap time.
+    pub within_101_pct: usize,
+    /// Average fuel used per lap, in the units of `fuel_level`.
+    pub fuel_per_lap: Option<f32>,
+    /// Track temperature change from the first to the last lap (ºC).
+    pub track_temp_drift: Option<f32>,
+    /// Lap time change per lap, from a linear fit (s/lap). Negative values mean the driver is
+    /// getting faster.
+    pub trend: Option<f32>,
+}
+
+/// Summarises the driven laps of `file_id` among `laps`.
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+pub fn summarize(file_id: &str, laps: &[Lap]) -> Summary {
+    let mut driven: Vec<&Lap> = laps
+        .iter()
+        .filter(|lap| lap.header.file_id == file_id && !lap.header.synthetic)
+        .collect();
+    driven.sort_by_key(|lap| lap.header.number);
+    let timed: Vec<&Lap> = driven
+        .iter()
+        .copied()
+        .filter(|lap| lap.is_complete() && lap.header.time > 0.)
+        .collect();
+    let times: Vec<f64> = timed.iter().map(|lap| f64::from(lap.header.time)).collect();
+
+    let best = timed
+        .iter()
+        .filter(|lap| lap.is_valid())
+        .min_by(|a, b| {
+            a.header
+                .time
+                .partial_cmp(&b.header.time)
+                .unwrap_or(Ordering::Equal)
+        })
+        .map(|lap| LapTime {
+            id: lap.header.id,
+            number: lap.header.number,
+            time: lap.header.time,
+        });
+    let within_101_pct = best.as_ref().map_or(0, |best| {
+        let limit = f64::from(best.time) * ON_PACE_RATIO;
+        times.iter().filter(|&&time| time <= limit).count()
+    });
+
+    let average = mean(&times);
+    let std_dev = average.map(|average| {
+        (times.iter().map(|t| (t - average).powi(2)).sum::<f64>() / times.len() as f64).sqrt()
+    });
+
+    let theoretical_best = theoretical_best(&timed);
+
+    let fuel_used: Vec<f64> = timed
+        .iter()
+        .filter_map(|lap| LapFuel::measure(lap))
+        .map(|fuel| f64::from(fuel.used))
+        .collect();
+
+    let track_temps: Vec<f64> = driven
+        .iter()
+        .filter_map(|lap| {
+            let temps: Vec<f64> = lap
+                .variables
+                .track_temp
+                .iter()
+                .map(|&t| f64::from(t))
+                .collect();
+            mean(&temps)
+        })
+        .collect();
+    let track_temp_drift = match (track_temps.first(), track_temps.last()) {
+        (Some(first), Some(last)) if track_temps.len() > 1 => Some(last - first),
+        _ => None,
+    };
+
+    let numbers: Vec<f64> = timed
+        .iter()
+        .map(|lap| f64::from(lap.header.number))
+        .collect();
+
+    Summary {
+        file_id: file_id.to_string(),
+        laps: driven.len(),
+        timed_laps: timed.len(),
+        best,
+        average: average.map(|v| v as f32),
+        median: median(&times).map(|v| v as f32),
+        theoretical_best,
+        std_dev: std_dev.map(|v| v as f32),
+        within_101_pct,
+        fuel_per_lap: mean(&fuel_used).map(|v| v as f32),
+        track_temp_drift: track_temp_drift.map(|v| v as f32),
+        trend: slope(&numbers, &times).map(|v| v as f32),
+    }
+}
+
+/// Fastest of the theoretical best laps of each driver, so that segments of different drivers
+/// are never stitched together.
+fn theoretical_best(laps: &[&Lap]) -> Option<f32> {
+    let mut by_driver: BTreeMap<&str, Vec<&Lap>> = BTreeMap::new();
+    for &lap in laps {
+        by_driver
+            .entry(lap.header.driver.as_str())
+            .or_default()
+            .push(lap);
+    }
+
+    by_driver
+        .values()
+        .filter_map(|laps| {
+            synthesize(laps, &Segmentation::Fixed(THEORETICAL_BEST_SEGMENTS), 0.)
+                .ok()
+                .and_then(|variables| variables.lap_current_lap_time.last().copied())
+        })
+        .min_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+}
+
+fn median(values: &[f64]) -> Option<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    let middle = sorted.len() / 2;
+    match sorted.len() {
+        0 => None,
+        len if len % 2 == 0 => Some((sorted[middle - 1] + sorted[middle]) / 2.),
+        _ => Some(sorted[middle]),
+    }
+}
+
+/// Slope of the least squares line fitting `ys` against `xs`.
+fn slope(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    let (x_mean, y_mean) = (mean(xs)?, mean(ys)?);
+    let variance: f64 = xs.iter().map(|x| (x - x_mean).powi(2)).sum();
+    if variance <= f64::EPSILON {
+        return None;
+    }
+    let covariance: f64 = xs
+        .iter()
+        .zip(ys)
+        .map(|(x, y)| (x - x_mean) * (y - y_mean))
+        .sum();
+    Some(covariance / variance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lap::domain::lap::events::{Event, EventKind};
+    use crate::lap::domain::lap::test_util;
+    use crate::lap::domain::lap::variables::Variables;
+
+    const SAMPLES: usize = 101;
+
+    /// Builds a 1000 m lap driven at `speed` m/s, burning `fuel` and with the track at
+    /// `temp` ºC.
+    fn test_lap(number: u16, speed: f32, fuel: f32, temp: f32) -> Lap {
+        let mut variables = Variables::default();
+        let mut time = 0f32;
+        #[allow(clippy::cast_precision_loss)]
+        for i in 0..SAMPLES {
+            let pct = i as f32 / (SAMPLES - 1) as f32;
+            if i > 0 {
+                time += 10. / speed;
+            }
+            variables.speed.push(speed);
+            variables.distance.push(pct * 1000.);
+            variables.distance_pct.push(pct);
+            variables.track_temp.push(temp);
+            variables.fuel_level.push(fuel.mul_add(-pct, 50.));
+            variables.lap_current_lap_time.push(time);
+        }
+
+        test_util::test_lap(number, "Driver", variables)
+    }
+
+    #[test]
+    fn summarizes_the_laps_of_a_file() {
+        let mut out_lap = test_lap(0, 20., 3., 25.);
+        out_lap
+            .variables
+            .distance_pct
+            .iter_mut()
+            .for_each(|p| *p /= 2.);
+        let laps = vec![
+            out_lap,
+            test_lap(1, 10., 2., 26.),
+            test_lap(2, 12.5, 2., 27.),
+            test_lap(3, 12.5, 2.5, 28.),
+        ];
+
+        let summary = summarize("file", &laps);
+
+        assert_eq!(summary.laps, 4);
+        assert_eq!(summary.timed_laps, 3);
+        let best = summary.best.unwrap();
+        assert_eq!(best.number, 2);
+        assert!((best.time - 80.).abs() < 1e-3);
+        assert!((summary.median.unwrap() - 80.).abs() < 1e-3);
+        assert!((summary.average.unwrap() - 260. / 3.).abs() < 1e-3);
+        assert_eq!(summary.within_101_pct, 2);
+        assert!((summary.theoretical_best.unwrap() - 80.).abs() < 1e-2);
+        assert!((summary.fuel_per_lap.unwrap() - 6.5 / 3.).abs() < 1e-3);
+        assert!((summary.track_temp_drift.unwrap() - 3.).abs() < 1e-3);
+        assert!((summary.trend.unwrap() + 10.).abs() < 1e-3);
+    }
+
+    #[test]
+    fn does_not_stitch_segments_of_different_drivers() {
+        let mut other = test_lap(2, 10., 2., 26.);
+        other.header.driver = "Other".to_string();
+        // The other driver takes 100 s in the first half of the lap and 25 s in the second one
+        other
+            .variables
+            .lap_current_lap_time
+            .iter_mut()
+            .for_each(|t| {
+                *t = if *t <= 50. {
+                    *t * 2.
+                } else {
+                    (*t - 50.).mul_add(0.5, 100.)
+                }
+            });
+        let laps = vec![test_lap(1, 10., 2., 26.), other];
+
+        let summary = summarize("file", &laps);
+
+        // Stitching both drivers would give 75 s
+        assert!((summary.theoretical_best.unwrap() - 100.).abs() < 1e-2);
+    }
+
+    #[test]
+    fn takes_the_best_lap_among_the_valid_ones() {
+        let mut cut = test_lap(2, 12.5, 2., 27.);
+        cut.events.push(Event {
+            kind: EventKind::OffTrack,
+            distance: 500.,
+            time: 40.,
+        });
+        let laps = vec![test_lap(1, 10., 2., 26.), cut];
+
+        let summary = summarize("file", &laps);
+
+        assert_eq!(summary.timed_laps, 2);
+        assert_eq!(summary.best.unwrap().number, 1);
+    }
+
+    #[test]
+    fn summarizes_a_file_without_laps() {
+        let summary = summarize("other", &[test_lap(1, 10., 2., 26.)]);
+
+        assert_eq!(summary.laps, 0);
+        assert!(summary.best.is_none());
+        assert!(summary.theoretical_best.is_none());
+        assert!(summary.trend.is_none());
+        assert_eq!(summary.within_101_pct, 0);
+    }
+}
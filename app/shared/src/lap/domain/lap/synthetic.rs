@@ -0,0 +1,587 @@
+use crate::ibt::domain::file::session_info::split_time_info::SplitTimeInfo;
+use crate::lap::domain::lap::variables::Variables;
+use crate::lap::domain::lap::Lap;
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// File identifier assigned to laps that are not extracted from any uploaded file.
+pub const SYNTHETIC_FILE_ID: &str = "synthetic";
+
+/// Describes how a lap is split into micro-sectors before stitching the fastest ones.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub enum Segmentation {
+    /// Sector start points expressed as a fraction of the lap (0 to 1).
+    Sectors(Vec<f32>),
+    /// A fixed number of equally sized segments.
+    Fixed(usize),
+}
+
+impl Segmentation {
+    /// Returns the sorted segment boundaries, always starting at 0 and ending at 1.
+    #[must_use]
+    pub fn boundaries(&self) -> Vec<f64> {
+        let mut boundaries: Vec<f64> = match self {
+            Self::Sectors(starts) => starts
+                .iter()
+                .map(|&pct| f64::from(pct))
+                .filter(|pct| *pct > 0. && *pct < 1.)
+                .collect(),
+            #[allow(clippy::cast_precision_loss)]
+            Self::Fixed(segments) => (1..*segments)
+                .map(|i| i as f64 / *segments as f64)
+                .collect(),
+        };
+
+        boundaries.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        boundaries.dedup();
+        boundaries.insert(0, 0.);
+        boundaries.push(1.);
+        boundaries
+    }
+}
+
+impl From<&SplitTimeInfo> for Segmentation {
+    /// Builds the segmentation from the sector start points found in the session info.
+    fn from(split_time_info: &SplitTimeInfo) -> Self {
+        let starts = split_time_info
+            .sectors
+            .iter()
+            .flatten()
+            .filter_map(|sector| sector.sector_start_pct)
+            .collect();
+        Self::Sectors(starts)
+    }
+}
+
+/// Builds the telemetry of a theoretical best lap from the fastest segments of `laps`.
+///
+/// Every lap is split using `segmentation`, the fastest lap of each segment is selected
+/// and its samples are stitched together. Continuous channels are cross-faded across a
+/// window of `blend_pct` (lap fraction) on both sides of each seam so that the resulting
+/// telemetry does not jump between laps. The lap time is rebuilt by accumulating the
+/// time spent in each selected segment. Segments whose fastest lap was recorded without a
+/// channel take it from the fastest of the laps that have it, and the channel is only left
+/// empty when none of them has it.
+///
+/// # Errors
+///
+/// * `Error::NoCompleteLaps` - If none of the given laps covers the whole circuit without any
+///   event.
+/// * `Error::InvalidBlend` - If `blend_pct` is negative or not a number.
+pub fn synthesize(
+    laps: &[&Lap],
+    segmentation: &Segmentation,
+    blend_pct: f32,
+) -> Result<Variables, Error> {
+    if blend_pct.is_nan() || blend_pct < 0. {
+        return Err(Error::InvalidBlend(blend_pct));
+    }
+
+    let candidates: Vec<Candidate> = laps
+        .iter()
+        .filter_map(|&lap| Candidate::try_from_lap(lap))
+        .collect();
+
+    if candidates.is_empty() {
+        return Err(Error::NoCompleteLaps);
+    }
+
+    let stitch = Stitch::new(
+        &candidates,
+        &segmentation.boundaries(),
+        f64::from(blend_pct),
+    );
+
+    Ok(stitch.variables())
+}
+
+/// A valid lap with its lap fraction and lap time converted to `f64` for lookups.
+struct Candidate<'a> {
+    variables: &'a Variables,
+    pct: Vec<f64>,
+    time: Vec<f64>,
+}
+
+impl<'a> Candidate<'a> {
+    fn try_from_lap(lap: &'a Lap) -> Option<Self> {
+        let variables = &lap.variables;
+        let pct: Vec<f64> = variables
+            .distance_pct
+            .iter()
+            .map(|&p| f64::from(p))
+            .collect();
+        let time: Vec<f64> = variables
+            .lap_current_lap_time
+            .iter()
+            .map(|&t| f64::from(t))
+            .collect();
+
+        (lap.is_valid() && time.len() == pct.len()).then_some(Self {
+            variables,
+            pct,
+            time,
+        })
+    }
+
+    /// Linearly interpolates `values` at the given lap fraction, or `None` if `values` is
+    /// shorter than the lap fraction channel.
+    fn value_at(&self, values: &[f64], pct: f64) -> Option<f64> {
+        let pos = self.pct.partition_point(|&p| p < pct);
+        match pos {
+            0 => values.first().copied(),
+            pos if pos >= self.pct.len() => values.get(self.pct.len() - 1).copied(),
+            pos => {
+                let (p0, p1) = (self.pct[pos - 1], self.pct[pos]);
+                let (v0, v1) = (*values.get(pos - 1)?, *values.get(pos)?);
+                Some(if (p1 - p0).abs() < f64::EPSILON {
+                    v1
+                } else {
+                    ((pct - p0) / (p1 - p0)).mul_add(v1 - v0, v0)
+                })
+            }
+        }
+    }
+
+    /// Whether `values` has a sample for every sample of the lap fraction channel.
+    fn covers(&self, values: &[f64]) -> bool {
+        values.len() == self.pct.len()
+    }
+
+    /// Index of the sample closest to the given lap fraction.
+    fn index_at(&self, pct: f64) -> usize {
+        let pos = self
+            .pct
+            .partition_point(|&p| p < pct)
+            .min(self.pct.len() - 1);
+        if pos > 0 && pct - self.pct[pos - 1] < self.pct[pos] - pct {
+            pos - 1
+        } else {
+            pos
+        }
+    }
+
+    fn time_at(&self, pct: f64) -> f64 {
+        // Both channels have the same length, checked when the candidate is built
+        self.value_at(&self.time, pct).unwrap_or_default()
+    }
+
+    fn segment_time(&self, start: f64, end: f64) -> f64 {
+        self.time_at(end) - self.time_at(start)
+    }
+}
+
+/// A sample of the synthetic lap, taken from one of the candidates.
+struct Sample {
+    segment: usize,
+    candidate: usize,
+    index: usize,
+    pct: f64,
+}
+
+/// Stitching plan: which candidate wins each segment and which samples are taken.
+struct Stitch<'a, 'b> {
+    candidates: &'b [Candidate<'a>],
+    boundaries: Vec<f64>,
+    winners: Vec<usize>,
+    samples: Vec<Sample>,
+    blend: f64,
+}
+
+/// Index of the fastest of `candidates` between `start` and `end` among the ones accepted by
+/// `eligible`.
+fn fastest(
+    candidates: &[Candidate],
+    start: f64,
+    end: f64,
+    eligible: impl Fn(usize) -> bool,
+) -> Option<usize> {
+    candidates
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| eligible(*i))
+        .min_by(|(_, a), (_, b)| {
+            a.segment_time(start, end)
+                .partial_cmp(&b.segment_time(start, end))
+                .unwrap_or(Ordering::Equal)
+        })
+        .map(|(i, _)| i)
+}
+
+impl<'a, 'b> Stitch<'a, 'b> {
+    fn new(candidates: &'b [Candidate<'a>], boundaries: &[f64], blend: f64) -> Self {
+        let winners: Vec<usize> = boundaries
+            .windows(2)
+            .map(|bounds| fastest(candidates, bounds[0], bounds[1], |_| true).unwrap_or(0))
+            .collect();
+
+        let last_segment = winners.len() - 1;
+        let samples = winners
+            .iter()
+            .enumerate()
+            .flat_map(|(segment, &candidate)| {
+                let (start, end) = (boundaries[segment], boundaries[segment + 1]);
+                candidates[candidate]
+                    .pct
+                    .iter()
+                    .enumerate()
+                    .filter(move |(_, &pct)| {
+                        pct >= start && (pct < end || (segment == last_segment && pct <= end))
+                    })
+                    .map(move |(index, &pct)| Sample {
+                        segment,
+                        candidate,
+                        index,
+                        pct,
+                    })
+            })
+            .collect();
+
+        Self {
+            candidates,
+            boundaries: boundaries.to_vec(),
+            winners,
+            samples,
+            blend,
+        }
+    }
+
+    fn variables(&self) -> Variables {
+        let track_length = self.track_length();
+
+        Variables {
+            speed: self.blended_f32(|v| &v.speed),
+            throttle: self.blended_f32(|v| &v.throttle),
+            brake: self.blended_f32(|v| &v.brake),
+            clutch: self.blended_f32(|v| &v.clutch),
+            gear: self.raw(|v| &v.gear),
+            rpm: self.blended_f32(|v| &v.rpm),
+            #[allow(clippy::cast_possible_truncation)]
+            distance: self
+                .samples
+                .iter()
+                .map(|s| (s.pct * track_length) as f32)
+                .collect(),
+            #[allow(clippy::cast_possible_truncation)]
+            distance_pct: self.samples.iter().map(|s| s.pct as f32).collect(),
+            track_temp: self.blended_f32(|v| &v.track_temp),
+            latitude: self.blended(|v| v.latitude.clone()),
+            longitude: self.blended(|v| v.longitude.clone()),
+            altitude: self.blended_f32(|v| &v.altitude),
+            steering_wheel_angle: self.blended_f32(|v| &v.steering_wheel_angle),
+            fuel_level: self.blended_f32(|v| &v.fuel_level),
+            lap_current_lap_time: self.rebased_times(),
+            lat_accel: self.blended_f32(|v| &v.lat_accel),
+            long_accel: self.blended_f32(|v| &v.long_accel),
+            yaw_rate: self.blended_f32(|v| &v.yaw_rate),
+            lf_speed: self.blended_f32(|v| &v.lf_speed),
+            rf_speed: self.blended_f32(|v| &v.rf_speed),
+            lr_speed: self.blended_f32(|v| &v.lr_speed),
+            rr_speed: self.blended_f32(|v| &v.rr_speed),
+        }
+    }
+
+    /// Average lap length of the winning candidates, used to rebuild the distance channel.
+    fn track_length(&self) -> f64 {
+        let mut winners = self.winners.clone();
+        winners.sort_unstable();
+        winners.dedup();
+        let lengths: Vec<f64> = winners
+            .iter()
+            .map(|&winner| &self.candidates[winner])
+            .filter_map(|c| {
+                let distance = f64::from(*c.variables.distance.last()?);
+                let pct = *c.pct.last()?;
+                (pct > 0.).then_some(distance / pct)
+            })
+            .collect();
+
+        #[allow(clippy::cast_precision_loss)]
+        let length = lengths.iter().sum::<f64>() / lengths.len().max(1) as f64;
+        length
+    }
+
+    /// Candidate each segment takes `channel` from: its winner when it has the channel, or
+    /// else the fastest candidate in the segment that has it. `None` when no candidate has it.
+    fn sources(&self, has: impl Fn(usize) -> bool) -> Option<Vec<usize>> {
+        if !(0..self.candidates.len()).any(&has) {
+            return None;
+        }
+
+        Some(
+            self.winners
+                .iter()
+                .enumerate()
+                .map(|(segment, &winner)| {
+                    if has(winner) {
+                        winner
+                    } else {
+                        let (start, end) = (self.boundaries[segment], self.boundaries[segment + 1]);
+                        fastest(self.candidates, start, end, &has).unwrap_or(winner)
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Values of a channel that cannot be interpolated, taken from the closest sample.
+    fn raw<T: Copy>(&self, channel: impl Fn(&Variables) -> &Vec<T>) -> Vec<T> {
+        let has =
+            |c: usize| channel(self.candidates[c].variables).len() == self.candidates[c].pct.len();
+        let Some(sources) = self.sources(has) else {
+            return vec![];
+        };
+
+        self.samples
+            .iter()
+            .map(|s| {
+                let source = sources[s.segment];
+                let index = if source == s.candidate {
+                    s.index
+                } else {
+                    self.candidates[source].index_at(s.pct)
+                };
+                channel(self.candidates[source].variables)[index]
+            })
+            .collect()
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn blended_f32(&self, channel: impl Fn(&Variables) -> &Vec<f32>) -> Vec<f32> {
+        self.blended(|v| channel(v).iter().map(|&x| f64::from(x)).collect())
+            .into_iter()
+            .map(|x| x as f32)
+            .collect()
+    }
+
+    fn blended(&self, channel: impl Fn(&Variables) -> Vec<f64>) -> Vec<f64> {
+        let channels: Vec<Vec<f64>> = self
+            .candidates
+            .iter()
+            .map(|c| channel(c.variables))
+            .collect();
+
+        let Some(sources) = self.sources(|c| self.candidates[c].covers(&channels[c])) else {
+            return vec![];
+        };
+
+        self.samples
+            .iter()
+            .map(|s| {
+                let source = sources[s.segment];
+                let own = if source == s.candidate {
+                    channels[source][s.index]
+                } else {
+                    // The source covers the channel, so the value is always found
+                    self.candidates[source]
+                        .value_at(&channels[source], s.pct)
+                        .unwrap_or_default()
+                };
+                self.blend_sample(s, source, own, &channels, &sources)
+            })
+            .collect()
+    }
+
+    /// Cross-fades `own`, taken from `source`, with the source of the neighbouring segment
+    /// when the sample lies within the blend window of a seam. Both sides weight 50% exactly
+    /// at the seam.
+    fn blend_sample(
+        &self,
+        sample: &Sample,
+        source: usize,
+        own: f64,
+        channels: &[Vec<f64>],
+        sources: &[usize],
+    ) -> f64 {
+        if self.blend <= 0. {
+            return own;
+        }
+
+        let (start, end) = (
+            self.boundaries[sample.segment],
+            self.boundaries[sample.segment + 1],
+        );
+
+        let neighbour = if sample.segment > 0 && sample.pct < start + self.blend {
+            let alpha = (sample.pct - (start - self.blend)) / (2. * self.blend);
+            Some((sources[sample.segment - 1], alpha))
+        } else if sample.segment + 1 < sources.len() && sample.pct > end - self.blend {
+            let alpha = (sample.pct - (end - self.blend)) / (2. * self.blend);
+            Some((sources[sample.segment + 1], 1. - alpha))
+        } else {
+            None
+        };
+
+        match neighbour {
+            Some((other, _)) if other == source => own,
+            Some((other, own_weight)) => self.candidates[other]
+                .value_at(&channels[other], sample.pct)
+                .map_or(own, |other_value| {
+                    own_weight.mul_add(own - other_value, other_value)
+                }),
+            None => own,
+        }
+    }
+
+    /// Lap time where each segment starts at the time accumulated by the previous ones.
+    #[allow(clippy::cast_possible_truncation)]
+    fn rebased_times(&self) -> Vec<f32> {
+        let segment_offsets: Vec<f64> = self
+            .winners
+            .iter()
+            .enumerate()
+            .scan(0., |accumulated, (segment, &candidate)| {
+                let (start, end) = (self.boundaries[segment], self.boundaries[segment + 1]);
+                let candidate = &self.candidates[candidate];
+                let offset = *accumulated - candidate.time_at(start);
+                *accumulated += candidate.segment_time(start, end);
+                Some(offset)
+            })
+            .collect();
+
+        self.samples
+            .iter()
+            .map(|s| {
+                let time = self.candidates[s.candidate].time[s.index];
+                (time + segment_offsets[s.segment]).max(0.) as f32
+            })
+            .collect()
+    }
+}
+
+#[derive(PartialEq, Debug, thiserror::Error)]
+pub enum Error {
+    #[error("no complete lap without events found to build a synthetic lap")]
+    NoCompleteLaps,
+    #[error("blend window must be a non negative lap fraction, got `{0}`")]
+    InvalidBlend(f32),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lap::domain::lap::test_util;
+
+    const SAMPLES: usize = 101;
+
+    /// Builds a lap of 1000 m where the first half is driven at `first_speed` and the second
+    /// half at `second_speed` (m/s).
+    fn test_lap(first_speed: f32, second_speed: f32) -> Lap {
+        let mut variables = Variables::default();
+        let mut time = 0f32;
+        #[allow(clippy::cast_precision_loss)]
+        for i in 0..SAMPLES {
+            let pct = i as f32 / (SAMPLES - 1) as f32;
+            let speed = if pct < 0.5 { first_speed } else { second_speed };
+            if i > 0 {
+                time += 10. / speed;
+            }
+            variables.speed.push(speed);
+            variables.throttle.push(1.);
+            variables.brake.push(0.);
+            variables.clutch.push(1.);
+            variables.gear.push(3);
+            variables.rpm.push(speed * 100.);
+            variables.distance.push(pct * 1000.);
+            variables.distance_pct.push(pct);
+            variables.track_temp.push(30.);
+            variables.latitude.push(f64::from(pct));
+            variables.longitude.push(f64::from(pct));
+            variables.altitude.push(0.);
+            variables.steering_wheel_angle.push(0.);
+            variables.fuel_level.push(10.);
+            variables.lap_current_lap_time.push(time);
+        }
+
+        test_util::test_lap(1, "driver", variables)
+    }
+
+    #[test]
+    fn fixed_segmentation_boundaries() {
+        let boundaries = Segmentation::Fixed(4).boundaries();
+        assert_eq!(boundaries, vec![0., 0.25, 0.5, 0.75, 1.]);
+    }
+
+    #[test]
+    fn sector_segmentation_boundaries_are_sorted_and_closed() {
+        let boundaries = Segmentation::Sectors(vec![0.6, 0., 0.3]).boundaries();
+        let expected = [0., 0.3, 0.6, 1.];
+        assert_eq!(boundaries.len(), expected.len());
+        for (b, e) in boundaries.iter().zip(expected) {
+            assert!((b - e).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn synthetic_lap_takes_fastest_segments() {
+        let laps = [test_lap(50., 25.), test_lap(25., 50.)];
+
+        let variables = synthesize(&[&laps[0], &laps[1]], &Segmentation::Fixed(2), 0.)
+            .unwrap_or_else(|e| panic!("Test Failed: {e}"));
+
+        let time = *variables.lap_current_lap_time.last().unwrap();
+        // Both laps take 30.2 s, the fastest halves take 10.2 s and 10 s
+        assert!((time - 20.2).abs() < 1e-3, "unexpected lap time {time}");
+        assert!(variables.speed.iter().all(|&s| (s - 50.).abs() < 1e-3));
+        assert_eq!(variables.distance.len(), SAMPLES);
+    }
+
+    #[test]
+    fn synthetic_lap_blends_at_seams() {
+        let laps = [test_lap(50., 25.), test_lap(40., 50.)];
+
+        let variables = synthesize(&[&laps[0], &laps[1]], &Segmentation::Fixed(2), 0.1)
+            .unwrap_or_else(|e| panic!("Test Failed: {e}"));
+
+        let seam = variables
+            .distance_pct
+            .iter()
+            .position(|&p| (p - 0.5).abs() < 1e-6)
+            .unwrap();
+        // First lap goes at 25 m/s and second lap at 50 m/s at the seam
+        assert!((variables.speed[seam] - 37.5).abs() < 1e-3);
+        assert!(variables.speed[0] >= 49.9);
+        assert!(variables.speed[SAMPLES - 1] >= 49.9);
+    }
+
+    #[test]
+    fn missing_channels_are_taken_from_the_laps_that_have_them() {
+        let mut old = test_lap(50., 25.);
+        old.variables.lat_accel.clear();
+        old.variables.gear.clear();
+        let mut recent = test_lap(25., 50.);
+        recent.variables.lat_accel = vec![1.; SAMPLES];
+        recent.variables.gear = vec![4; SAMPLES];
+
+        let variables = synthesize(&[&old, &recent], &Segmentation::Fixed(2), 0.)
+            .unwrap_or_else(|e| panic!("Test Failed: {e}"));
+
+        assert_eq!(variables.lat_accel, vec![1.; SAMPLES]);
+        assert_eq!(variables.gear, vec![4; SAMPLES]);
+        // The first half of the old lap is faster, so it keeps its speed
+        assert!((variables.speed[0] - 50.).abs() < 1e-3);
+        assert!(variables.yaw_rate.is_empty());
+    }
+
+    #[test]
+    fn segmentation_from_split_time_info() {
+        let split_time_info: SplitTimeInfo = serde_yaml::from_str(
+            "Sectors:\n- SectorNum: 0\n  SectorStartPct: 0.0\n- SectorNum: 1\n  SectorStartPct: 0.4\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            Segmentation::from(&split_time_info),
+            Segmentation::Sectors(vec![0., 0.4])
+        );
+    }
+
+    #[test]
+    fn incomplete_laps_are_rejected() {
+        let mut lap = test_lap(50., 50.);
+        lap.variables.distance_pct.iter_mut().for_each(|p| *p /= 2.);
+
+        let result = synthesize(&[&lap], &Segmentation::Fixed(2), 0.);
+        assert_eq!(result, Err(Error::NoCompleteLaps));
+    }
+}
@@ -0,0 +1,20 @@
+use crate::lap::domain::lap::variables::Variables;
+use crate::lap::domain::lap::Lap;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+/// Lap `number` of `driver` in a GT3 car at Spa, driven now with the given telemetry.
+pub fn test_lap(number: u16, driver: &str, variables: Variables) -> Lap {
+    Lap::new(
+        Uuid::new_v4(),
+        "file".to_string(),
+        number,
+        driver.to_string(),
+        "GT3".to_string(),
+        "Car".to_string(),
+        "Spa".to_string(),
+        Utc::now(),
+        variables,
+    )
+}
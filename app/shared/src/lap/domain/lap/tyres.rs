@@ -0,0 +1,436 @@
+use crate::common::domain::statistics::mean;
+use crate::ibt::domain::file::session_info::car_setup::tires_aero::TiresAero;
+use crate::lap::domain::lap::wheel_slip::Wheel;
+use crate::lap::domain::lap::Lap;
+
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+
+/// Kilopascals in a pound per square inch.
+const KPA_PER_PSI: f32 = 6.894_757;
+
+/// Values measured across the tread of a tyre, from the inside to the outside of the car.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+pub struct Bands {
+    pub inner: f32,
+    pub middle: f32,
+    pub outer: f32,
+}
+
+impl Bands {
+    #[must_use]
+    pub fn mean(&self) -> f32 {
+        (self.inner + self.middle + self.outer) / 3.
+    }
+
+    /// Difference between the inner and the outer band.
+    #[must_use]
+    pub fn spread(&self) -> f32 {
+        self.inner - self.outer
+    }
+
+    /// Parses setup values such as `85C, 87C, 90C`, listed from the outer band if `outer_first`
+    /// or from the inner band otherwise.
+    fn parse(value: &str, outer_first: bool) -> Option<Self> {
+        let values: Vec<f32> = value
+            .split(',')
+            .map(|part| parse_number(part).map(|(number, unit)| to_celsius(number, unit)))
+            .collect::<Option<Vec<f32>>>()?;
+        let [first, middle, last] = values[..] else {
+            return None;
+        };
+        Some(if outer_first {
+            Self {
+                inner: last,
+                middle,
+                outer: first,
+            }
+        } else {
+            Self {
+                inner: first,
+                middle,
+                outer: last,
+            }
+        })
+    }
+}
+
+/// Live telemetry of a tyre over a lap, sample by sample.
+///
+/// Carcass temperatures and wear are named from the left to the right of the tyre as seen
+/// from the driver seat.
+#[derive(PartialEq, Default, Clone, Debug)]
+pub struct TyreSamples {
+    /// Carcass temperatures (ºC).
+    pub temp_left: Vec<f32>,
+    pub temp_middle: Vec<f32>,
+    pub temp_right: Vec<f32>,
+    /// Pressure (kPa).
+    pub pressure: Vec<f32>,
+    /// Tread remaining, from 0 to 1.
+    pub wear_left: Vec<f32>,
+    pub wear_middle: Vec<f32>,
+    pub wear_right: Vec<f32>,
+}
+
+/// Setup values and live telemetry of a tyre in a lap. Pressures in kPa, temperatures in ºC
+/// and tread in percentage.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Tyre {
+    pub wheel: Wheel,
+    /// Cold pressure set in the garage.
+    pub starting_pressure: Option<f32>,
+    /// Pressure when the car last came into the pits.
+    pub last_hot_pressure: Option<f32>,
+    /// Temperatures when the car last came into the pits.
+    pub last_temps: Option<Bands>,
+    /// Tread remaining when the car last came into the pits.
+    pub tread_remaining: Option<Bands>,
+    /// Average carcass temperatures over the lap.
+    pub temps: Option<Bands>,
+    /// Pressure at the start and the end of the lap.
+    pub start_pressure: Option<f32>,
+    pub end_pressure: Option<f32>,
+    /// Tread remaining at the end of the lap.
+    pub wear: Option<Bands>,
+}
+
+impl Tyre {
+    /// Builds the tyre data of `wheel` from the setup of the car and the live telemetry of
+    /// the lap, if recorded.
+    #[must_use]
+    pub fn new(
+        wheel: Wheel,
+        tires_aero: Option<&TiresAero>,
+        samples: Option<&TyreSamples>,
+    ) -> Self {
+        let (starting_pressure, last_hot_pressure, last_temps, tread_remaining) =
+            tires_aero.map_or((None, None, None, None), |tires| setup(wheel, tires));
+        // Left tyres have their outer band on the left, right tyres on the right
+        let outer_left = matches!(wheel, Wheel::LeftFront | Wheel::LeftRear);
+        let bands = |left: f32, middle: f32, right: f32| {
+            let (inner, outer) = if outer_left {
+                (right, left)
+            } else {
+                (left, right)
+            };
+            Bands {
+                inner,
+                middle,
+                outer,
+            }
+        };
+
+        let temps = samples.and_then(|s| {
+            Some(bands(
+                mean(&s.temp_left)?,
+                mean(&s.temp_middle)?,
+                mean(&s.temp_right)?,
+            ))
+        });
+        let wear = samples.and_then(|s| {
+            Some(bands(
+                s.wear_left.last()? * 100.,
+                s.wear_middle.last()? * 100.,
+                s.wear_right.last()? * 100.,
+            ))
+        });
+
+        Self {
+            wheel,
+            starting_pressure,
+            last_hot_pressure,
+            last_temps,
+            tread_remaining,
+            temps,
+            start_pressure: samples.and_then(|s| s.pressure.first().copied()),
+            end_pressure: samples.and_then(|s| s.pressure.last().copied()),
+            wear,
+        }
+    }
+}
+
+/// Values of the setup of the tyre on `wheel`.
+fn setup(
+    wheel: Wheel,
+    tires: &TiresAero,
+) -> (Option<f32>, Option<f32>, Option<Bands>, Option<Bands>) {
+    let pressure = |value: &Option<String>| value.as_deref().and_then(parse_pressure);
+    let bands = |value: &Option<String>, outer_first: bool| {
+        value
+            .as_deref()
+            .and_then(|value| Bands::parse(value, outer_first))
+    };
+
+    let left = match wheel {
+        Wheel::LeftFront => tires.left_front_tire.as_ref(),
+        Wheel::LeftRear => tires.left_rear_tire.as_ref(),
+        Wheel::RightFront | Wheel::RightRear => None,
+    };
+    if let Some(tire) = left {
+        // Left tyres are reported outer, middle, inner
+        return (
+            pressure(&tire.starting_pressure),
+            pressure(&tire.last_hot_pressure),
+            bands(&tire.last_temps_omi, true),
+            bands(&tire.tread_remaining, true),
+        );
+    }
+
+    let right = match wheel {
+        Wheel::RightFront => tires.right_front_tire.as_ref(),
+        Wheel::RightRear => tires.right_rear_tire.as_ref(),
+        Wheel::LeftFront | Wheel::LeftRear => None,
+    };
+    right.map_or((None, None, None, None), |tire| {
+        (
+            pressure(&tire.starting_pressure),
+            pressure(&tire.last_hot_pressure),
+            bands(&tire.last_temps_imo, false),
+            bands(&tire.tread_remaining, false),
+        )
+    })
+}
+
+/// Splits setup values such as `165.5 kPa` or `85C` into their number and unit.
+fn parse_number(value: &str) -> Option<(f32, &str)> {
+    let value = value.trim();
+    let end = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .unwrap_or(value.len());
+    Some((value[..end].parse().ok()?, value[end..].trim()))
+}
+
+/// Pressure in kPa of setup values such as `165.5 kPa` or `24.0 psi`.
+fn parse_pressure(value: &str) -> Option<f32> {
+    let (number, unit) = parse_number(value)?;
+    Some(if unit.eq_ignore_ascii_case("psi") {
+        number * KPA_PER_PSI
+    } else {
+        number
+    })
+}
+
+/// Temperatures given in Fahrenheit are converted to Celsius, other values are kept.
+fn to_celsius(number: f32, unit: &str) -> f32 {
+    if unit.eq_ignore_ascii_case("F") {
+        (number - 32.) * 5. / 9.
+    } else {
+        number
+    }
+}
+
+/// Represents the tyres of a lap, one per wheel.
+#[derive(Serialize, Deserialize, PartialEq, Default, Clone, Debug)]
+pub struct Tyres {
+    tyres: Vec<Tyre>,
+}
+
+impl From<Vec<Tyre>> for Tyres {
+    /// Converts a vector of tyres into `Tyres`.
+    fn from(tyres: Vec<Tyre>) -> Self {
+        Self { tyres }
+    }
+}
+
+impl Deref for Tyres {
+    type Target = Vec<Tyre>;
+
+    /// Implements dereferencing for `Tyres`.
+    fn deref(&self) -> &Self::Target {
+        &self.tyres
+    }
+}
+
+impl Tyres {
+    /// Builds the data of every tyre from the setup of the car and the live telemetry of the
+    /// lap, given in the order of `Wheel::ALL`.
+    #[must_use]
+    pub fn new(tires_aero: Option<&TiresAero>, samples: &[TyreSamples]) -> Self {
+        Wheel::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, &wheel)| Tyre::new(wheel, tires_aero, samples.get(i)))
+            .collect::<Vec<Tyre>>()
+            .into()
+    }
+}
+
+/// Evolution of a tyre over the laps of a stint.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct TyreEvolution {
+    pub wheel: Wheel,
+    /// Average temperatures over the stint (ºC), or the last ones of the setup.
+    pub balance: Option<Bands>,
+    /// Pressure at the end of every lap (kPa), by lap number.
+    pub pressures: Vec<(u16, f32)>,
+    /// Pressure gained from the cold pressure, or the start of the first lap, to the end of
+    /// the last lap (kPa).
+    pub pressure_build_up: Option<f32>,
+    /// Tread worn over the stint (%), or from new to the last pit stop.
+    pub wear: Option<Bands>,
+}
+
+/// Tyre report of the laps of a file.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct TyreReport {
+    pub file_id: String,
+    pub tyres: Vec<TyreEvolution>,
+}
+
+/// Reports the temperature balance, pressure build-up and wear of every tyre over the driven
+/// laps of `file_id` among `laps`.
+#[must_use]
+pub fn report(file_id: &str, laps: &[Lap]) -> TyreReport {
+    let mut driven: Vec<&Lap> = laps
+        .iter()
+        .filter(|lap| lap.header.file_id == file_id && !lap.header.synthetic)
+        .collect();
+    driven.sort_by_key(|lap| lap.header.number);
+
+    let tyres = Wheel::ALL
+        .iter()
+        .map(|&wheel| {
+            let tyres: Vec<(u16, &Tyre)> = driven
+                .iter()
+                .filter_map(|lap| {
+                    let tyre = lap.tyres.iter().find(|t| t.wheel == wheel)?;
+                    Some((lap.header.number, tyre))
+                })
+                .collect();
+            let last = tyres.last().map(|(_, tyre)| *tyre);
+
+            let temps: Vec<Bands> = tyres.iter().filter_map(|(_, t)| t.temps).collect();
+            let balance = if temps.is_empty() {
+                last.and_then(|t| t.last_temps)
+            } else {
+                Some(Bands {
+                    inner: mean(&temps.iter().map(|b| b.inner).collect::<Vec<f32>>())
+                        .unwrap_or_default(),
+                    middle: mean(&temps.iter().map(|b| b.middle).collect::<Vec<f32>>())
+                        .unwrap_or_default(),
+                    outer: mean(&temps.iter().map(|b| b.outer).collect::<Vec<f32>>())
+                        .unwrap_or_default(),
+                })
+            };
+
+            let pressures: Vec<(u16, f32)> = tyres
+                .iter()
+                .filter_map(|(number, t)| Some((*number, t.end_pressure?)))
+                .collect();
+            let first = tyres.first().map(|(_, tyre)| *tyre);
+            let start = first.and_then(|t| t.starting_pressure.or(t.start_pressure));
+            let end = pressures
+                .last()
+                .map(|(_, p)| *p)
+                .or_else(|| last.and_then(|t| t.last_hot_pressure));
+            let pressure_build_up = start.zip(end).map(|(start, end)| end - start);
+
+            let worn = |from: Bands, to: Bands| Bands {
+                inner: from.inner - to.inner,
+                middle: from.middle - to.middle,
+                outer: from.outer - to.outer,
+            };
+            let new = Bands {
+                inner: 100.,
+                middle: 100.,
+                outer: 100.,
+            };
+            let wear = match (first.and_then(|t| t.wear), last.and_then(|t| t.wear)) {
+                (Some(from), Some(to)) if tyres.len() > 1 => Some(worn(from, to)),
+                _ => last.and_then(|t| t.tread_remaining).map(|to| worn(new, to)),
+            };
+
+            TyreEvolution {
+                wheel,
+                balance,
+                pressures,
+                pressure_build_up,
+                wear,
+            }
+        })
+        .collect();
+
+    TyreReport {
+        file_id: file_id.to_string(),
+        tyres,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ibt::domain::file::session_info::car_setup::tires_aero::left_tire::LeftTire;
+    use crate::lap::domain::lap::test_util;
+    use crate::lap::domain::lap::variables::Variables;
+
+    fn test_setup() -> TiresAero {
+        TiresAero {
+            left_front_tire: Some(LeftTire {
+                starting_pressure: Some("24.0 psi".to_string()),
+                last_hot_pressure: Some("180.0 kPa".to_string()),
+                last_temps_omi: Some("80C, 85C, 90C".to_string()),
+                tread_remaining: Some("98%, 97%, 95%".to_string()),
+            }),
+            left_rear_tire: None,
+            right_front_tire: None,
+            right_rear_tire: None,
+            aero_settings: None,
+            aero_calculator: None,
+        }
+    }
+
+    /// A lap whose front left tyre starts at `pressure` kPa and gains 5 kPa.
+    fn test_lap(number: u16, pressure: f32) -> Lap {
+        let mut lap = test_util::test_lap(number, "Driver", Variables::default());
+        let samples = TyreSamples {
+            temp_left: vec![70., 80.],
+            temp_middle: vec![85., 85.],
+            temp_right: vec![90., 100.],
+            pressure: vec![pressure, pressure + 5.],
+            wear_left: vec![1., 0.99],
+            wear_middle: vec![1., 0.98],
+            wear_right: vec![1., 1. - f32::from(number) / 100.],
+        };
+        lap.tyres = Tyres::new(Some(&test_setup()), &[samples]);
+        lap
+    }
+
+    #[test]
+    fn reads_setup_and_live_values() {
+        let lap = test_lap(1, 160.);
+        let tyre = &lap.tyres[0];
+
+        assert_eq!(tyre.wheel, Wheel::LeftFront);
+        assert!((tyre.starting_pressure.unwrap() - 165.474_17).abs() < 1e-3);
+        assert_eq!(tyre.last_hot_pressure, Some(180.));
+        let last_temps = tyre.last_temps.unwrap();
+        assert!((last_temps.inner - 90.).abs() < f32::EPSILON);
+        assert!((last_temps.outer - 80.).abs() < f32::EPSILON);
+        // The outer band of left tyres is the left one
+        let temps = tyre.temps.unwrap();
+        assert!((temps.outer - 75.).abs() < f32::EPSILON);
+        assert!((temps.inner - 95.).abs() < f32::EPSILON);
+        assert!((temps.spread() - 20.).abs() < f32::EPSILON);
+        assert_eq!(tyre.end_pressure, Some(165.));
+        assert_eq!(lap.tyres[1].temps, None);
+    }
+
+    #[test]
+    fn reports_the_evolution_of_every_tyre() {
+        let laps = vec![test_lap(2, 170.), test_lap(1, 160.)];
+
+        let report = report("file", &laps);
+
+        assert_eq!(report.tyres.len(), 4);
+        let front_left = &report.tyres[0];
+        assert_eq!(front_left.pressures, vec![(1, 165.), (2, 175.)]);
+        // From the cold pressure of the setup
+        assert!((front_left.pressure_build_up.unwrap() - 9.525_83).abs() < 1e-3);
+        let wear = front_left.wear.unwrap();
+        assert!((wear.inner - 1.).abs() < 1e-4);
+        assert!(wear.outer.abs() < 1e-4);
+        assert!((front_left.balance.unwrap().inner - 95.).abs() < 1e-4);
+        assert!(report.tyres[1].pressures.is_empty());
+    }
+}
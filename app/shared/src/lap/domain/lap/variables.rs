@@ -50,4 +50,32 @@ pub struct Variables {
 
     /// Current lap time measurements (s).
     pub lap_current_lap_time: Vec<f32>,
+
+    /// Lateral acceleration, including gravity (m/s²). Positive to the left.
+    #[serde(default)]
+    pub lat_accel: Vec<f32>,
+
+    /// Longitudinal acceleration, including gravity (m/s²). Positive forwards.
+    #[serde(default)]
+    pub long_accel: Vec<f32>,
+
+    /// Yaw rate (rad/s).
+    #[serde(default)]
+    pub yaw_rate: Vec<f32>,
+
+    /// Speed of the left front wheel (m/s).
+    #[serde(default)]
+    pub lf_speed: Vec<f32>,
+
+    /// Speed of the right front wheel (m/s).
+    #[serde(default)]
+    pub rf_speed: Vec<f32>,
+
+    /// Speed of the left rear wheel (m/s).
+    #[serde(default)]
+    pub lr_speed: Vec<f32>,
+
+    /// Speed of the right rear wheel (m/s).
+    #[serde(default)]
+    pub rr_speed: Vec<f32>,
 }
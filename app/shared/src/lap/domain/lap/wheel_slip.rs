@@ -0,0 +1,194 @@
+use crate::analysis::domain::analysis::thresholds::{BRAKE_ON, THROTTLE_ON};
+use crate::lap::domain::lap::variables::Variables;
+
+use serde::{Deserialize, Serialize};
+
+/// Below this car speed (m/s) the car may be turning on the spot and the slip ratio is too noisy
+/// to be used.
+pub(crate) const MIN_SPEED: f32 = 5.;
+/// Ratio of the car speed a wheel has to lose to be locking up.
+const LOCK_UP_SLIP: f32 = 0.15;
+/// Ratio of the car speed a wheel has to gain to be spinning.
+const WHEELSPIN_SLIP: f32 = 0.1;
+
+/// Wheel of the car.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Wheel {
+    LeftFront,
+    RightFront,
+    LeftRear,
+    RightRear,
+}
+
+impl Wheel {
+    pub const ALL: [Self; 4] = [
+        Self::LeftFront,
+        Self::RightFront,
+        Self::LeftRear,
+        Self::RightRear,
+    ];
+
+    /// Speed of the wheel (m/s) in every sample, empty if it was not recorded.
+    #[must_use]
+    pub const fn speeds(self, variables: &Variables) -> &Vec<f32> {
+        match self {
+            Self::LeftFront => &variables.lf_speed,
+            Self::RightFront => &variables.rf_speed,
+            Self::LeftRear => &variables.lr_speed,
+            Self::RightRear => &variables.rr_speed,
+        }
+    }
+}
+
+/// Whether the wheel turns slower or faster than the car moves.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum SlipKind {
+    /// Slower under braking.
+    LockUp,
+    /// Faster on the throttle.
+    Wheelspin,
+}
+
+/// A stretch where a wheel loses grip.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct WheelSlip {
+    pub kind: SlipKind,
+    pub wheel: Wheel,
+    /// Distance where the slip starts (m).
+    pub distance: f32,
+    /// Time the wheel keeps slipping (s).
+    pub duration: f32,
+    /// Highest difference between the wheel and the car speed, as a ratio of the car speed.
+    pub severity: f32,
+}
+
+/// Detects the lock-ups and wheelspins of every wheel of a lap, in track order.
+///
+/// Laps recorded without wheel speeds have no slips.
+#[must_use]
+pub fn detect(variables: &Variables) -> Vec<WheelSlip> {
+    let mut slips: Vec<WheelSlip> = Wheel::ALL
+        .iter()
+        .flat_map(|&wheel| detect_wheel(variables, wheel))
+        .collect();
+    slips.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+    slips
+}
+
+fn detect_wheel(variables: &Variables, wheel: Wheel) -> Vec<WheelSlip> {
+    let wheel_speeds = wheel.speeds(variables);
+    let len = [
+        wheel_speeds.len(),
+        variables.speed.len(),
+        variables.brake.len(),
+        variables.throttle.len(),
+        variables.distance.len(),
+        variables.lap_current_lap_time.len(),
+    ]
+    .into_iter()
+    .min()
+    .unwrap_or_default();
+
+    let slip_at = |i: usize| {
+        let speed = variables.speed[i];
+        if speed < MIN_SPEED {
+            return None;
+        }
+        let ratio = (wheel_speeds[i] - speed) / speed;
+        if variables.brake[i] > BRAKE_ON && -ratio > LOCK_UP_SLIP {
+            Some((SlipKind::LockUp, -ratio))
+        } else if variables.throttle[i] > THROTTLE_ON && ratio > WHEELSPIN_SLIP {
+            Some((SlipKind::Wheelspin, ratio))
+        } else {
+            None
+        }
+    };
+
+    let time = &variables.lap_current_lap_time;
+    let mut slips: Vec<WheelSlip> = Vec::new();
+    let mut current: Option<(WheelSlip, usize)> = None;
+    for i in 0..len {
+        current = match (current, slip_at(i)) {
+            (Some((mut slip, start)), Some((kind, ratio))) if slip.kind == kind => {
+                slip.severity = slip.severity.max(ratio);
+                slip.duration = time[i] - time[start];
+                Some((slip, start))
+            }
+            (previous, next) => {
+                slips.extend(previous.map(|(slip, _)| slip));
+                next.map(|(kind, severity)| {
+                    let slip = WheelSlip {
+                        kind,
+                        wheel,
+                        distance: variables.distance[i],
+                        duration: 0.,
+                        severity,
+                    };
+                    (slip, i)
+                })
+            }
+        };
+    }
+    slips.extend(current.map(|(slip, _)| slip));
+
+    slips
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 200 samples at 10 per second and 40 m/s, locking the front left wheel under braking
+    /// between samples 50 and 59 and spinning the rear wheels on the throttle from 150 to 154.
+    fn test_variables() -> Variables {
+        let mut variables = Variables::default();
+        for i in 0..200 {
+            #[allow(clippy::cast_precision_loss)]
+            let t = i as f32 / 10.;
+            let braking = (40..80).contains(&i);
+            let locked = (50..60).contains(&i);
+            let spinning = (150..155).contains(&i);
+
+            variables.lap_current_lap_time.push(t);
+            variables.distance.push(t * 40.);
+            variables.speed.push(40.);
+            variables.brake.push(if braking { 0.9 } else { 0. });
+            variables.throttle.push(if braking { 0. } else { 1. });
+            variables.lf_speed.push(if locked { 20. } else { 40. });
+            variables.rf_speed.push(38.);
+            variables.lr_speed.push(if spinning { 48. } else { 40. });
+            variables.rr_speed.push(if spinning { 46. } else { 40. });
+        }
+        variables
+    }
+
+    #[test]
+    fn detects_lock_ups_and_wheelspin() {
+        let slips = detect(&test_variables());
+
+        assert_eq!(slips.len(), 3);
+        assert_eq!(slips[0].kind, SlipKind::LockUp);
+        assert_eq!(slips[0].wheel, Wheel::LeftFront);
+        assert!((slips[0].distance - 200.).abs() < 1e-3);
+        assert!((slips[0].duration - 0.9).abs() < 1e-3);
+        assert!((slips[0].severity - 0.5).abs() < 1e-6);
+        assert!(slips[1..]
+            .iter()
+            .all(|s| s.kind == SlipKind::Wheelspin && (s.distance - 600.).abs() < 1e-3));
+        assert!(slips
+            .iter()
+            .any(|s| s.wheel == Wheel::LeftRear && (s.severity - 0.2).abs() < 1e-6));
+    }
+
+    #[test]
+    fn no_slips_without_wheel_speeds() {
+        let mut variables = test_variables();
+        variables.lf_speed.clear();
+        variables.lr_speed.clear();
+        variables.rr_speed.clear();
+
+        assert!(detect(&variables).is_empty());
+    }
+}
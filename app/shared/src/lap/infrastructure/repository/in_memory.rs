@@ -87,7 +87,7 @@ impl Repository for InMemory {
             });
         }
 
-        let opt_laps: Option<Laps> = if laps.len() == 0 { None } else { Some(laps) };
+        let opt_laps: Option<Laps> = if laps.is_empty() { None } else { Some(laps) };
 
         Ok(opt_laps)
     }
@@ -169,6 +169,7 @@ impl InMemory {
             "circuit" => Ok(header.circuit.clone()),
             "time" => Ok(header.time.to_string()),
             "date" => Ok(header.date.to_string()),
+            "synthetic" => Ok(header.synthetic.to_string()),
             _ => Err(format!("`{field}` lap file does not exists in lap")),
         }
     }
@@ -3,6 +3,9 @@ pub mod analysis {
         pub mod analyze {
             pub mod service;
         }
+        pub mod cancel {
+            pub mod service;
+        }
         pub mod create {
             pub mod service;
         }
@@ -23,6 +26,9 @@ pub mod analysis {
                 pub mod service;
             }
         }
+        pub mod reanalyze {
+            pub mod service;
+        }
         pub mod update {
             pub mod service;
         }
@@ -32,6 +38,7 @@ pub mod analysis {
         pub mod analysis;
         pub mod event {
             pub mod created;
+            pub mod reanalyzed;
         }
         pub mod repository;
     }
@@ -46,6 +53,36 @@ pub mod common {
     pub mod domain {
         pub mod criteria;
         pub mod event;
+        pub mod executor;
+        pub mod statistics;
+    }
+}
+
+pub mod consistency {
+    pub mod application {
+        pub mod create {
+            pub mod service;
+        }
+        pub mod delete {
+            pub mod service;
+        }
+        pub mod find {
+            pub mod by_id {
+                pub mod service;
+            }
+        }
+        pub mod focus {
+            pub mod service;
+        }
+    }
+    pub mod domain {
+        pub mod consistency;
+        pub mod repository;
+    }
+    pub mod infrastructure {
+        pub mod repository {
+            pub mod in_memory;
+        }
     }
 }
 
@@ -97,6 +134,12 @@ pub mod ibt {
 
 pub mod lap {
     pub mod application {
+        pub mod analyze_grip {
+            pub mod service;
+        }
+        pub mod analyze_tyres {
+            pub mod service;
+        }
         pub mod create {
             pub mod service;
         }
@@ -117,6 +160,15 @@ pub mod lap {
                 pub mod service;
             }
         }
+        pub mod plan_fuel {
+            pub mod service;
+        }
+        pub mod summarize {
+            pub mod service;
+        }
+        pub mod synthesize {
+            pub mod service;
+        }
     }
     pub mod domain {
         pub mod lap;